@@ -32,6 +32,7 @@ pub const CONSUMED_MSG_TO_L2_ENCODED_DATA_SIZE: usize =
 // Transaction resource names.
 pub const L1_GAS_USAGE: &str = "gas_weight";
 pub const BLOB_GAS_USAGE: &str = "l1_blob_gas_usage";
+pub const L2_GAS_USAGE: &str = "l2_gas_usage";
 pub const N_STEPS_RESOURCE: &str = "n_steps";
 pub const N_EVENTS: &str = "n_events";
 pub const MESSAGE_SEGMENT_LENGTH: &str = "message_segment_length";
@@ -42,6 +43,9 @@ pub const N_MEMORY_HOLES: &str = "n_memory_holes";
 pub const CAIRO0_ENTRY_POINT_STRUCT_SIZE: usize = 2;
 pub const N_STEPS_PER_PEDERSEN: usize = 8;
 
+// The contract class version of all Sierra classes.
+pub const SIERRA_CONTRACT_CLASS_VERSION: &str = "0.1.0";
+
 // OS reserved contract addresses.
 
 // This contract stores the block number -> block hash mapping.
@@ -1,6 +1,9 @@
 pub mod block;
+pub mod block_hash;
 pub mod config;
+pub mod simulation;
 pub mod stateful_validator;
 pub mod transaction_executor;
 #[cfg(test)]
 pub mod transfers_flow_test;
+pub mod view_call;
@@ -31,6 +31,8 @@ pub struct GasPrices {
     pub strk_l1_gas_price: NonZeroU128,      // In fri.
     pub eth_l1_data_gas_price: NonZeroU128,  // In wei.
     pub strk_l1_data_gas_price: NonZeroU128, // In fri.
+    pub eth_l2_gas_price: NonZeroU128,       // In wei.
+    pub strk_l2_gas_price: NonZeroU128,      // In fri.
 }
 
 impl GasPrices {
@@ -47,6 +49,13 @@ impl GasPrices {
             FeeType::Eth => self.eth_l1_data_gas_price,
         }
     }
+
+    pub fn get_l2_gas_price_by_fee_type(&self, fee_type: &FeeType) -> NonZeroU128 {
+        match fee_type {
+            FeeType::Strk => self.strk_l2_gas_price,
+            FeeType::Eth => self.eth_l2_gas_price,
+        }
+    }
 }
 
 // Block pre-processing.
@@ -0,0 +1,195 @@
+use starknet_api::block::{
+    BlockHash, BlockHeaderWithoutHash, GasPrice, GasPricePerToken, StarknetVersion,
+};
+use starknet_api::block_hash::block_hash_calculator::{
+    self, BlockHeaderCommitments, TransactionHashingData, TransactionOutputForHash,
+};
+use starknet_api::core::{ClassHash, ContractAddress, GlobalRoot, SequencerContractAddress};
+use starknet_api::data_availability::L1DataAvailabilityMode;
+use starknet_api::state::ThinStateDiff;
+use starknet_api::transaction::{
+    Event, EventContent, GasVector, MessageToL1 as StarknetApiMessageToL1,
+    RevertedTransactionExecutionStatus, TransactionExecutionStatus,
+};
+use starknet_types_core::felt::Felt;
+
+use crate::blockifier::block::BlockInfo;
+use crate::execution::call_info::{CallInfo, MessageToL1};
+use crate::execution::contract_class::ContractClass;
+use crate::state::cached_state::{CommitmentStateDiff, StateMaps};
+use crate::transaction::account_transaction::AccountTransaction;
+use crate::transaction::objects::{TransactionExecutionInfo, TransactionInfoCreator};
+use crate::transaction::transaction_execution::Transaction;
+
+#[cfg(test)]
+#[path = "block_hash_test.rs"]
+pub mod test;
+
+/// Computes the commitments of a block, given its executed transactions (in execution order), its
+/// state diff (see `block_state_diff`) and its data availability mode.
+pub fn calculate_block_commitments(
+    executed_txs: &[(&Transaction, &TransactionExecutionInfo)],
+    state_diff: &ThinStateDiff,
+    use_kzg_da: bool,
+) -> BlockHeaderCommitments {
+    let transactions_data: Vec<_> = executed_txs
+        .iter()
+        .map(|(tx, execution_info)| transaction_hashing_data(tx, execution_info))
+        .collect();
+    block_hash_calculator::calculate_block_commitments(
+        &transactions_data,
+        state_diff,
+        l1_da_mode(use_kzg_da),
+    )
+}
+
+/// Computes the hash of a block, given its info, its commitments and the global state root after
+/// applying it.
+pub fn calculate_block_hash(
+    block_info: &BlockInfo,
+    block_commitments: BlockHeaderCommitments,
+    global_state_root: Felt,
+    parent_block_hash: BlockHash,
+    starknet_version: &str,
+) -> BlockHash {
+    let gas_prices = &block_info.gas_prices;
+    let header = BlockHeaderWithoutHash {
+        parent_hash: parent_block_hash,
+        block_number: block_info.block_number,
+        l1_gas_price: GasPricePerToken {
+            price_in_fri: GasPrice(gas_prices.strk_l1_gas_price.get()),
+            price_in_wei: GasPrice(gas_prices.eth_l1_gas_price.get()),
+        },
+        l1_data_gas_price: GasPricePerToken {
+            price_in_fri: GasPrice(gas_prices.strk_l1_data_gas_price.get()),
+            price_in_wei: GasPrice(gas_prices.eth_l1_data_gas_price.get()),
+        },
+        state_root: GlobalRoot(global_state_root),
+        sequencer: SequencerContractAddress(block_info.sequencer_address),
+        timestamp: block_info.block_timestamp,
+        l1_da_mode: l1_da_mode(block_info.use_kzg_da),
+        starknet_version: StarknetVersion(starknet_version.to_string()),
+    };
+    block_hash_calculator::calculate_block_hash(header, block_commitments)
+}
+
+/// Returns the state diff of a block, given its executed transactions and its state diff, as
+/// returned by `TransactionExecutor::finalize`; the Cairo 0 classes declared by the transactions
+/// have no compiled class hash, so they are not part of the latter.
+pub fn block_state_diff(
+    executed_txs: &[(&Transaction, &TransactionExecutionInfo)],
+    state_diff: &CommitmentStateDiff,
+) -> ThinStateDiff {
+    let deprecated_declared_classes: Vec<ClassHash> = executed_txs
+        .iter()
+        .filter_map(|(tx, execution_info)| match tx {
+            Transaction::AccountTransaction(AccountTransaction::Declare(declare_tx))
+                if !execution_info.is_reverted()
+                    && matches!(declare_tx.contract_class(), ContractClass::V0(_)) =>
+            {
+                Some(declare_tx.class_hash())
+            }
+            _ => None,
+        })
+        .collect();
+    // Deployed contracts and replaced classes are hashed alike, so the initial class hashes of
+    // the touched contracts are not needed.
+    state_diff.to_thin_state_diff(&StateMaps::default(), &deprecated_declared_classes)
+}
+
+/// Returns the data of an executed transaction that the block hash depends on.
+pub fn transaction_hashing_data(
+    tx: &Transaction,
+    execution_info: &TransactionExecutionInfo,
+) -> TransactionHashingData {
+    let tx_info = tx.create_tx_info();
+    // An empty signature (e.g., of an L1 handler transaction) is hashed as a single zero, which is
+    // how the calculator hashes a missing one.
+    let signature = tx_info.signature();
+    let transaction_signature = (!signature.0.is_empty()).then_some(signature);
+    let execution_status = match &execution_info.revert_error {
+        Some(revert_error) => {
+            TransactionExecutionStatus::Reverted(RevertedTransactionExecutionStatus {
+                revert_reason: revert_error.to_string(),
+            })
+        }
+        None => TransactionExecutionStatus::Succeeded,
+    };
+    let receipt = &execution_info.transaction_receipt;
+    let transaction_output = TransactionOutputForHash {
+        actual_fee: receipt.fee,
+        events: sorted_events(execution_info)
+            .into_iter()
+            .map(|(from_address, content)| Event { from_address, content: content.clone() })
+            .collect(),
+        execution_status,
+        gas_consumed: GasVector {
+            l1_gas: u64::try_from(receipt.gas.l1_gas).expect("L1 gas fits in 64 bits."),
+            l1_data_gas: u64::try_from(receipt.gas.l1_data_gas)
+                .expect("L1 data gas fits in 64 bits."),
+        },
+        messages_sent: sorted_messages(execution_info)
+            .into_iter()
+            .map(|(from_address, message)| StarknetApiMessageToL1 {
+                from_address,
+                to_address: message.to_address,
+                payload: message.payload.clone(),
+            })
+            .collect(),
+    };
+    TransactionHashingData {
+        transaction_signature,
+        transaction_output,
+        transaction_hash: tx_info.transaction_hash(),
+    }
+}
+
+fn l1_da_mode(use_kzg_da: bool) -> L1DataAvailabilityMode {
+    if use_kzg_da { L1DataAvailabilityMode::Blob } else { L1DataAvailabilityMode::Calldata }
+}
+
+/// Returns the events emitted by the transaction, along with their emitting contracts; the events
+/// of each top-level call are ordered by emission.
+fn sorted_events(
+    execution_info: &TransactionExecutionInfo,
+) -> Vec<(ContractAddress, &EventContent)> {
+    sorted_by_order(execution_info, |call_info| {
+        call_info.execution.events.iter().map(|event| (event.order, &event.event)).collect()
+    })
+}
+
+/// Returns the L2-to-L1 messages sent by the transaction, along with their sending contracts; the
+/// messages of each top-level call are ordered by sending.
+fn sorted_messages(
+    execution_info: &TransactionExecutionInfo,
+) -> Vec<(ContractAddress, &MessageToL1)> {
+    sorted_by_order(execution_info, |call_info| {
+        call_info
+            .execution
+            .l2_to_l1_messages
+            .iter()
+            .map(|message| (message.order, &message.message))
+            .collect()
+    })
+}
+
+fn sorted_by_order<'a, T>(
+    execution_info: &'a TransactionExecutionInfo,
+    get_ordered_items: impl Fn(&'a CallInfo) -> Vec<(usize, T)>,
+) -> Vec<(ContractAddress, T)> {
+    execution_info
+        .non_optional_call_infos()
+        .flat_map(|top_level_call_info| {
+            let mut items: Vec<_> = top_level_call_info
+                .iter()
+                .flat_map(|call_info| {
+                    get_ordered_items(call_info)
+                        .into_iter()
+                        .map(move |(order, item)| (order, call_info.call.storage_address, item))
+                })
+                .collect();
+            items.sort_by_key(|(order, ..)| *order);
+            items.into_iter().map(|(_, address, item)| (address, item))
+        })
+        .collect()
+}
@@ -0,0 +1,411 @@
+use indexmap::{indexmap, IndexMap};
+use pretty_assertions::assert_eq;
+use starknet_api::block::{BlockHash, BlockNumber, BlockTimestamp};
+use starknet_api::block_hash::block_hash_calculator::{
+    TransactionHashingData, TransactionOutputForHash,
+};
+use starknet_api::core::{ClassHash, ContractAddress, EthAddress, PatriciaKey};
+use starknet_api::state::ThinStateDiff;
+use starknet_api::transaction::{
+    Event as StarknetApiEvent, EventContent, EventData, EventKey, Fee,
+    GasVector as StarknetApiGasVector, L2ToL1Payload, MessageToL1 as StarknetApiMessageToL1,
+    RevertedTransactionExecutionStatus, TransactionExecutionStatus, TransactionHash,
+    TransactionSignature, TransactionVersion,
+};
+use starknet_api::{class_hash, contract_address, felt, patricia_key};
+use starknet_types_core::felt::Felt;
+
+use crate::blockifier::block::{BlockInfo, GasPrices};
+use crate::blockifier::block_hash::{
+    block_state_diff, calculate_block_commitments, calculate_block_hash, transaction_hashing_data,
+};
+use crate::execution::call_info::{
+    CallExecution, CallInfo, MessageToL1, OrderedEvent, OrderedL2ToL1Message,
+};
+use crate::execution::entry_point::CallEntryPoint;
+use crate::execution::stack_trace::{ErrorStack, ErrorStackHeader, Frame};
+use crate::fee::actual_cost::TransactionReceipt;
+use crate::state::cached_state::CommitmentStateDiff;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::declare::declare_tx;
+use crate::test_utils::invoke::invoke_tx;
+use crate::test_utils::CairoVersion;
+use crate::transaction::account_transaction::AccountTransaction;
+use crate::transaction::objects::{GasVector, RevertError, TransactionExecutionInfo};
+use crate::transaction::test_utils::{account_invoke_tx, calculate_class_info_for_testing};
+use crate::transaction::transaction_execution::Transaction;
+use crate::transaction::transactions::{InvokeTransaction, L1HandlerTransaction};
+use crate::{compiled_class_hash, declare_tx_args, invoke_tx_args, nonce, storage_key};
+
+fn execution_info_with_events(event_keys_by_order: &[(usize, u8)]) -> TransactionExecutionInfo {
+    let events = event_keys_by_order
+        .iter()
+        .map(|(order, key)| OrderedEvent {
+            order: *order,
+            event: EventContent { keys: vec![EventKey(felt!(*key))], data: EventData(vec![]) },
+        })
+        .collect();
+    TransactionExecutionInfo {
+        execute_call_info: Some(CallInfo {
+            execution: CallExecution { events, ..Default::default() },
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_block_commitments() {
+    let tx = Transaction::AccountTransaction(account_invoke_tx(invoke_tx_args! {}));
+    let state_diff = ThinStateDiff {
+        nonces: indexmap! {contract_address!("0x100") => nonce!(1_u8)},
+        ..Default::default()
+    };
+    let execution_info = execution_info_with_events(&[(1, 11), (0, 10)]);
+    let block_commitments =
+        calculate_block_commitments(&[(&tx, &execution_info)], &state_diff, false);
+
+    // Events are committed to in emission order.
+    let ordered_execution_info = execution_info_with_events(&[(0, 10), (1, 11)]);
+    assert_eq!(
+        calculate_block_commitments(&[(&tx, &ordered_execution_info)], &state_diff, false),
+        block_commitments
+    );
+
+    // The revert reason is part of the receipt only.
+    let reverted_execution_info = TransactionExecutionInfo {
+        revert_error: Some(RevertError::Execution(ErrorStack::default())),
+        ..execution_info_with_events(&[(0, 10), (1, 11)])
+    };
+    let reverted_block_commitments =
+        calculate_block_commitments(&[(&tx, &reverted_execution_info)], &state_diff, false);
+    assert_ne!(reverted_block_commitments.receipt_commitment, block_commitments.receipt_commitment);
+    assert_eq!(
+        reverted_block_commitments.transaction_commitment,
+        block_commitments.transaction_commitment
+    );
+}
+
+#[test]
+fn test_block_hash() {
+    let block_info = BlockInfo::create_for_testing();
+    let block_commitments = calculate_block_commitments(&[], &ThinStateDiff::default(), false);
+    let block_hash = calculate_block_hash(
+        &block_info,
+        block_commitments.clone(),
+        Felt::ONE,
+        BlockHash(Felt::TWO),
+        "0.13.2",
+    );
+
+    assert_ne!(
+        calculate_block_hash(
+            &block_info,
+            block_commitments,
+            Felt::ONE,
+            BlockHash(Felt::THREE),
+            "0.13.2"
+        ),
+        block_hash
+    );
+    // The data availability mode is part of the commitments.
+    assert_ne!(
+        calculate_block_hash(
+            &block_info,
+            calculate_block_commitments(&[], &ThinStateDiff::default(), true),
+            Felt::ONE,
+            BlockHash(Felt::TWO),
+            "0.13.2"
+        ),
+        block_hash
+    );
+}
+
+fn invoke_tx_with_hash(tx_hash: u8, signature: Vec<Felt>) -> Transaction {
+    let invoke_tx = invoke_tx(invoke_tx_args! { signature: TransactionSignature(signature) });
+    Transaction::AccountTransaction(AccountTransaction::Invoke(InvokeTransaction::new(
+        invoke_tx.tx,
+        TransactionHash(felt!(tx_hash)),
+    )))
+}
+
+fn call_info(
+    storage_address: ContractAddress,
+    events: Vec<OrderedEvent>,
+    l2_to_l1_messages: Vec<OrderedL2ToL1Message>,
+    inner_calls: Vec<CallInfo>,
+) -> CallInfo {
+    CallInfo {
+        call: CallEntryPoint { storage_address, ..Default::default() },
+        execution: CallExecution { events, l2_to_l1_messages, ..Default::default() },
+        inner_calls,
+        ..Default::default()
+    }
+}
+
+fn event(key: u8) -> EventContent {
+    EventContent { keys: vec![EventKey(felt!(key))], data: EventData(vec![felt!(key)]) }
+}
+
+/// Returns a message to the given L1 address, whose payload is the two following numbers.
+fn message(to_address: u64) -> MessageToL1 {
+    MessageToL1 {
+        to_address: EthAddress::try_from(felt!(to_address)).unwrap(),
+        payload: L2ToL1Payload(vec![felt!(to_address + 1), felt!(to_address + 2)]),
+    }
+}
+
+fn aborted_revert_error() -> RevertError {
+    let mut error_stack = ErrorStack::new(ErrorStackHeader::None);
+    error_stack.push(Frame::from("aborted".to_string()));
+    RevertError::Execution(error_stack)
+}
+
+fn receipt(fee: u128, l1_gas: u128, l1_data_gas: u128) -> TransactionReceipt {
+    TransactionReceipt {
+        fee: Fee(fee),
+        gas: GasVector { l1_gas, l1_data_gas, l2_gas: 0 },
+        ..Default::default()
+    }
+}
+
+/// A state diff with a replaced class (0x13) along with the deployed contracts.
+fn regression_state_diff() -> CommitmentStateDiff {
+    CommitmentStateDiff {
+        address_to_class_hash: indexmap! {
+            contract_address!("0x0") => class_hash!("0x1"),
+            contract_address!("0x2") => class_hash!("0x3"),
+            contract_address!("0x13") => class_hash!("0x14"),
+        },
+        storage_updates: indexmap! {
+            contract_address!("0x4") => indexmap! {
+                storage_key!(5_u8) => felt!(6_u8),
+                storage_key!(7_u8) => felt!(8_u8),
+            },
+            contract_address!("0x9") => indexmap! {storage_key!(10_u8) => felt!(11_u8)},
+        },
+        class_hash_to_compiled_class_hash: indexmap! {
+            class_hash!("0xc") => compiled_class_hash!(13_u8),
+            class_hash!("0xe") => compiled_class_hash!(15_u8),
+        },
+        address_to_nonce: indexmap! {contract_address!("0x11") => nonce!(18_u8)},
+    }
+}
+
+/// The block of the regression test of `starknet_api`'s block hash calculator: a single reverted
+/// transaction sending two messages, and a state diff with a Cairo 0 declared class (0x10).
+#[test]
+fn test_block_hash_regression() {
+    let tx = invoke_tx_with_hash(1, vec![Felt::TWO, Felt::THREE]);
+    let execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info(
+            contract_address!("0x22"),
+            vec![],
+            vec![OrderedL2ToL1Message { order: 0, message: message(35) }],
+            vec![],
+        )),
+        execute_call_info: Some(call_info(
+            contract_address!("0x38"),
+            vec![],
+            vec![OrderedL2ToL1Message { order: 0, message: message(57) }],
+            vec![],
+        )),
+        revert_error: Some(aborted_revert_error()),
+        transaction_receipt: receipt(99804, 16580, 32),
+        ..Default::default()
+    };
+    let state_diff = ThinStateDiff {
+        deprecated_declared_classes: vec![class_hash!("0x10")],
+        ..block_state_diff(&[], &regression_state_diff())
+    };
+    let block_commitments =
+        calculate_block_commitments(&[(&tx, &execution_info)], &state_diff, true);
+    let block_info = BlockInfo {
+        block_number: BlockNumber(1),
+        block_timestamp: BlockTimestamp(4),
+        sequencer_address: contract_address!("0x3"),
+        gas_prices: GasPrices {
+            eth_l1_gas_price: 7_u128.try_into().unwrap(),
+            strk_l1_gas_price: 6_u128.try_into().unwrap(),
+            eth_l1_data_gas_price: 9_u128.try_into().unwrap(),
+            strk_l1_data_gas_price: 10_u128.try_into().unwrap(),
+            eth_l2_gas_price: 1_u128.try_into().unwrap(),
+            strk_l2_gas_price: 1_u128.try_into().unwrap(),
+        },
+        use_kzg_da: true,
+    };
+
+    assert_eq!(
+        calculate_block_hash(
+            &block_info,
+            block_commitments,
+            felt!(2_u8),
+            BlockHash(felt!(11_u8)),
+            "10"
+        ),
+        BlockHash(felt!("0x061e4998d51a248f1d0288d7e17f6287757b0e5e6c5e1e58ddf740616e312134"))
+    );
+}
+
+/// Checks that the Cairo 0 classes declared by the (non-reverted) transactions are added to the
+/// state diff.
+#[test]
+fn test_block_state_diff() {
+    let declare_cairo0_tx = |class_hash| {
+        let declared_contract = FeatureContract::Empty(CairoVersion::Cairo0);
+        Transaction::AccountTransaction(declare_tx(
+            declare_tx_args! { class_hash, version: TransactionVersion::ONE },
+            calculate_class_info_for_testing(declared_contract.get_class()),
+        ))
+    };
+    let (declared_class_hash, reverted_class_hash) = (class_hash!("0x20"), class_hash!("0x10"));
+    let declare_tx = declare_cairo0_tx(declared_class_hash);
+    let reverted_declare_tx = declare_cairo0_tx(reverted_class_hash);
+    let reverted_execution_info = TransactionExecutionInfo {
+        revert_error: Some(aborted_revert_error()),
+        ..Default::default()
+    };
+    let state_diff = regression_state_diff();
+
+    let block_state_diff = block_state_diff(
+        &[
+            (&declare_tx, &TransactionExecutionInfo::default()),
+            (&reverted_declare_tx, &reverted_execution_info),
+        ],
+        &state_diff,
+    );
+    assert_eq!(block_state_diff.deprecated_declared_classes, vec![declared_class_hash]);
+    assert_eq!(
+        block_state_diff,
+        ThinStateDiff {
+            deployed_contracts: state_diff.address_to_class_hash.clone(),
+            storage_diffs: state_diff.storage_updates.clone(),
+            declared_classes: state_diff.class_hash_to_compiled_class_hash.clone(),
+            deprecated_declared_classes: vec![declared_class_hash],
+            nonces: state_diff.address_to_nonce.clone(),
+            replaced_classes: IndexMap::new(),
+        }
+    );
+}
+
+/// Checks the hashing data of transactions with events, messages, an L1 handler and a reverted
+/// transaction.
+#[test]
+fn test_transaction_hashing_data() {
+    let (account, contract, inner_contract, l1_handler_contract) = (
+        contract_address!("0x100"),
+        contract_address!("0x200"),
+        contract_address!("0x300"),
+        contract_address!("0x400"),
+    );
+    let invoke_tx = invoke_tx_with_hash(0x10, vec![felt!(2_u8), felt!(3_u8)]);
+    let invoke_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info(
+            account,
+            vec![OrderedEvent { order: 0, event: event(1) }],
+            vec![],
+            vec![],
+        )),
+        execute_call_info: Some(call_info(
+            contract,
+            vec![OrderedEvent { order: 2, event: event(3) }],
+            vec![OrderedL2ToL1Message { order: 1, message: message(8) }],
+            vec![call_info(
+                inner_contract,
+                vec![OrderedEvent { order: 1, event: event(2) }],
+                vec![OrderedL2ToL1Message { order: 0, message: message(5) }],
+                vec![],
+            )],
+        )),
+        transaction_receipt: receipt(100, 10, 1),
+        ..Default::default()
+    };
+    let l1_handler_tx = Transaction::L1HandlerTransaction(L1HandlerTransaction {
+        tx: Default::default(),
+        tx_hash: TransactionHash(felt!(0x11_u8)),
+        paid_fee_on_l1: Fee(1),
+    });
+    let l1_handler_execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(call_info(
+            l1_handler_contract,
+            vec![OrderedEvent { order: 0, event: event(4) }],
+            vec![],
+            vec![],
+        )),
+        transaction_receipt: receipt(0, 5, 0),
+        ..Default::default()
+    };
+    let reverted_tx = invoke_tx_with_hash(0x12, vec![felt!(7_u8)]);
+    let reverted_execution_info = TransactionExecutionInfo {
+        validate_call_info: Some(call_info(account, vec![], vec![], vec![])),
+        revert_error: Some(aborted_revert_error()),
+        transaction_receipt: receipt(50, 20, 2),
+        ..Default::default()
+    };
+
+    let expected_event = |from_address, key| StarknetApiEvent { from_address, content: event(key) };
+    let expected_message = |from_address, to_address| {
+        let MessageToL1 { to_address, payload } = message(to_address);
+        StarknetApiMessageToL1 { from_address, to_address, payload }
+    };
+    let expected_output = |events, messages_sent, execution_status, fee, l1_gas, l1_data_gas| {
+        TransactionOutputForHash {
+            actual_fee: Fee(fee),
+            events,
+            execution_status,
+            gas_consumed: StarknetApiGasVector { l1_gas, l1_data_gas },
+            messages_sent,
+        }
+    };
+    assert_eq!(
+        transaction_hashing_data(&invoke_tx, &invoke_execution_info),
+        TransactionHashingData {
+            transaction_signature: Some(TransactionSignature(vec![felt!(2_u8), felt!(3_u8)])),
+            transaction_output: expected_output(
+                vec![
+                    expected_event(account, 1),
+                    expected_event(inner_contract, 2),
+                    expected_event(contract, 3),
+                ],
+                vec![expected_message(inner_contract, 5), expected_message(contract, 8)],
+                TransactionExecutionStatus::Succeeded,
+                100,
+                10,
+                1,
+            ),
+            transaction_hash: TransactionHash(felt!(0x10_u8)),
+        }
+    );
+    assert_eq!(
+        transaction_hashing_data(&l1_handler_tx, &l1_handler_execution_info),
+        TransactionHashingData {
+            transaction_signature: None,
+            transaction_output: expected_output(
+                vec![expected_event(l1_handler_contract, 4)],
+                vec![],
+                TransactionExecutionStatus::Succeeded,
+                0,
+                5,
+                0,
+            ),
+            transaction_hash: TransactionHash(felt!(0x11_u8)),
+        }
+    );
+    assert_eq!(
+        transaction_hashing_data(&reverted_tx, &reverted_execution_info),
+        TransactionHashingData {
+            transaction_signature: Some(TransactionSignature(vec![felt!(7_u8)])),
+            transaction_output: expected_output(
+                vec![],
+                vec![],
+                TransactionExecutionStatus::Reverted(RevertedTransactionExecutionStatus {
+                    revert_reason: "aborted".to_string(),
+                }),
+                50,
+                20,
+                2,
+            ),
+            transaction_hash: TransactionHash(felt!(0x12_u8)),
+        }
+    );
+}
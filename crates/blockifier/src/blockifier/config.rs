@@ -1,11 +1,139 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use starknet_api::core::{ContractAddress, Nonce};
+
+use crate::transaction::objects::TransactionInfoCreator;
+use crate::transaction::transaction_execution::Transaction;
+
 #[derive(Debug, Default, Clone)]
 pub struct TransactionExecutorConfig {
+    pub block_filling_policy: BlockFillingPolicy,
     pub concurrency_config: ConcurrencyConfig,
+    pub transaction_ordering: SharedTransactionOrdering,
+    // If set, transactions whose hash does not match the one computed from their content are
+    // rejected without being executed.
+    pub verify_tx_hashes: bool,
 }
 impl TransactionExecutorConfig {
     #[cfg(any(test, feature = "testing"))]
     pub fn create_for_testing() -> Self {
-        Self { concurrency_config: ConcurrencyConfig::create_for_testing() }
+        Self {
+            block_filling_policy: BlockFillingPolicy::default(),
+            concurrency_config: ConcurrencyConfig::create_for_testing(),
+            transaction_ordering: SharedTransactionOrdering::default(),
+            verify_tx_hashes: false,
+        }
+    }
+}
+
+/// Determines how the transaction executor proceeds when there is no room in the block for a
+/// transaction. Rejected transactions are not executed on the block state, so that later (smaller)
+/// transactions may still fit in the block.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BlockFillingPolicy {
+    /// Stops at the first rejected transaction.
+    #[default]
+    StopAtFirstRejection,
+    /// Skips rejected transactions, and stops at the first rejection once `max_skips` transactions
+    /// were skipped.
+    SkipRejected { max_skips: usize },
+    /// Skips rejected transactions, and stops once `n_rejections` consecutive transactions were
+    /// rejected.
+    StopAfterConsecutiveRejections { n_rejections: usize },
+}
+
+impl BlockFillingPolicy {
+    /// Returns whether to stop filling the block after a rejection, given the total number of
+    /// rejected transactions and the number of consecutive rejected transactions, up to and
+    /// including the last one.
+    pub fn should_stop(&self, n_rejections: usize, n_consecutive_rejections: usize) -> bool {
+        match *self {
+            Self::StopAtFirstRejection => true,
+            Self::SkipRejected { max_skips } => n_rejections > max_skips,
+            Self::StopAfterConsecutiveRejections { n_rejections: max_consecutive_rejections } => {
+                n_consecutive_rejections >= max_consecutive_rejections
+            }
+        }
+    }
+}
+
+/// Determines the order in which a block builder executes a batch of transactions (see
+/// `TransactionExecutor::order_txs`), e.g., to prioritize transactions by their tip.
+pub trait TransactionOrdering: Debug + Send + Sync {
+    /// Reorders the given transactions, in place.
+    fn order(&self, txs: &mut [Transaction]);
+}
+
+/// A transaction ordering shared between executor configurations.
+#[derive(Clone, Debug)]
+pub struct SharedTransactionOrdering(pub Arc<dyn TransactionOrdering>);
+
+impl SharedTransactionOrdering {
+    pub fn new(transaction_ordering: impl TransactionOrdering + 'static) -> Self {
+        Self(Arc::new(transaction_ordering))
+    }
+}
+
+impl AsRef<dyn TransactionOrdering> for SharedTransactionOrdering {
+    fn as_ref(&self) -> &(dyn TransactionOrdering + 'static) {
+        self.0.as_ref()
+    }
+}
+
+impl Default for SharedTransactionOrdering {
+    fn default() -> Self {
+        Self::new(ArrivalOrdering)
+    }
+}
+
+/// Keeps the transactions in their arrival order.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ArrivalOrdering;
+
+impl TransactionOrdering for ArrivalOrdering {
+    fn order(&self, _txs: &mut [Transaction]) {}
+}
+
+/// Orders the transactions by their tip, from highest to lowest, while keeping the transactions of
+/// each sender in nonce order: a transaction is prioritized by the lowest tip among it and the
+/// preceding transactions of its sender. Transactions of equal priority are ordered by the arrival
+/// of the first transaction of their sender, and then by nonce.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TipOrdering;
+
+impl TransactionOrdering for TipOrdering {
+    fn order(&self, txs: &mut [Transaction]) {
+        let mut txs_by_sender: HashMap<ContractAddress, Vec<(Nonce, u64)>> = HashMap::new();
+        let mut sender_arrival_indices = HashMap::new();
+        for (arrival_index, tx) in txs.iter().enumerate() {
+            let tx_info = tx.create_tx_info();
+            let sender_address = tx_info.sender_address();
+            txs_by_sender.entry(sender_address).or_default().push((tx_info.nonce(), tx.tip().0));
+            sender_arrival_indices.entry(sender_address).or_insert(arrival_index);
+        }
+
+        let mut priorities = HashMap::new();
+        for (sender_address, mut sender_txs) in txs_by_sender {
+            sender_txs.sort_by_key(|&(nonce, _)| nonce);
+            let mut priority = u64::MAX;
+            for (nonce, tip) in sender_txs {
+                priority = priority.min(tip);
+                priorities.insert((sender_address, nonce), priority);
+            }
+        }
+
+        txs.sort_by_cached_key(|tx| {
+            let tx_info = tx.create_tx_info();
+            let (sender_address, nonce) = (tx_info.sender_address(), tx_info.nonce());
+            (
+                Reverse(priorities[&(sender_address, nonce)]),
+                sender_arrival_indices[&sender_address],
+                nonce,
+            )
+        });
     }
 }
 
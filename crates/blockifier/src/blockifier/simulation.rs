@@ -0,0 +1,422 @@
+use std::collections::BTreeMap;
+
+use cairo_vm::types::builtin_name::BuiltinName;
+use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use serde::Serialize;
+use starknet_api::core::{
+    ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, EthAddress, Nonce,
+};
+use starknet_api::deprecated_contract_class::EntryPointType;
+use starknet_api::state::{StorageKey, ThinStateDiff};
+use starknet_api::transaction::Calldata;
+use starknet_types_core::felt::Felt;
+
+use crate::execution::call_info::CallInfo;
+use crate::execution::entry_point::CallType;
+use crate::fee::actual_cost::TransactionReceipt;
+use crate::state::cached_state::StateMaps;
+use crate::transaction::account_transaction::AccountTransaction;
+use crate::transaction::objects::TransactionExecutionInfo;
+use crate::transaction::transaction_execution::Transaction;
+
+#[cfg(test)]
+#[path = "simulation_test.rs"]
+pub mod test;
+
+/// Flags controlling which parts of a transaction are executed during simulation.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SimulationFlags {
+    pub skip_validate: bool,
+    pub skip_fee_charge: bool,
+}
+
+/// The result of simulating a single transaction, in the Starknet JSON-RPC `TransactionTrace`
+/// shape.
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum TransactionTrace {
+    #[serde(rename = "INVOKE")]
+    Invoke(InvokeTransactionTrace),
+    #[serde(rename = "DECLARE")]
+    Declare(DeclareTransactionTrace),
+    #[serde(rename = "DEPLOY_ACCOUNT")]
+    DeployAccount(Box<DeployAccountTransactionTrace>),
+    #[serde(rename = "L1_HANDLER")]
+    L1Handler(L1HandlerTransactionTrace),
+}
+
+impl TransactionTrace {
+    /// Builds the trace of a transaction from its execution info and the state diff it induced.
+    pub fn new(
+        tx: &Transaction,
+        tx_execution_info: &TransactionExecutionInfo,
+        state_diff: TraceStateDiff,
+    ) -> Self {
+        let TransactionExecutionInfo {
+            validate_call_info,
+            execute_call_info,
+            fee_transfer_call_info,
+            revert_error,
+            transaction_receipt,
+            ..
+        } = tx_execution_info;
+        let validate_invocation = validate_call_info.as_ref().map(FunctionInvocation::from);
+        let fee_transfer_invocation = fee_transfer_call_info.as_ref().map(FunctionInvocation::from);
+        let execution_resources = TraceExecutionResources::from(transaction_receipt);
+
+        match tx {
+            Transaction::AccountTransaction(AccountTransaction::Invoke(_)) => {
+                let execute_invocation = match revert_error {
+                    Some(revert_reason) => ExecuteInvocation::Reverted(RevertedInvocation {
+                        revert_reason: revert_reason.to_string(),
+                    }),
+                    None => ExecuteInvocation::Function(Box::new(
+                        execute_call_info
+                            .as_ref()
+                            .map(FunctionInvocation::from)
+                            .expect("A successful invoke transaction must have an execute call."),
+                    )),
+                };
+                Self::Invoke(InvokeTransactionTrace {
+                    validate_invocation,
+                    execute_invocation,
+                    fee_transfer_invocation,
+                    state_diff,
+                    execution_resources,
+                })
+            }
+            Transaction::AccountTransaction(AccountTransaction::Declare(_)) => {
+                Self::Declare(DeclareTransactionTrace {
+                    validate_invocation,
+                    fee_transfer_invocation,
+                    state_diff,
+                    execution_resources,
+                })
+            }
+            Transaction::AccountTransaction(AccountTransaction::DeployAccount(_)) => {
+                Self::DeployAccount(Box::new(DeployAccountTransactionTrace {
+                    validate_invocation,
+                    constructor_invocation: execute_call_info
+                        .as_ref()
+                        .map(FunctionInvocation::from),
+                    fee_transfer_invocation,
+                    state_diff,
+                    execution_resources,
+                }))
+            }
+            Transaction::L1HandlerTransaction(_) => Self::L1Handler(L1HandlerTransactionTrace {
+                function_invocation: execute_call_info
+                    .as_ref()
+                    .map(FunctionInvocation::from)
+                    .expect("An L1 handler transaction must have an execute call."),
+                state_diff,
+                execution_resources,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct InvokeTransactionTrace {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validate_invocation: Option<FunctionInvocation>,
+    pub execute_invocation: ExecuteInvocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_transfer_invocation: Option<FunctionInvocation>,
+    pub state_diff: TraceStateDiff,
+    pub execution_resources: TraceExecutionResources,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct DeclareTransactionTrace {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validate_invocation: Option<FunctionInvocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_transfer_invocation: Option<FunctionInvocation>,
+    pub state_diff: TraceStateDiff,
+    pub execution_resources: TraceExecutionResources,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct DeployAccountTransactionTrace {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validate_invocation: Option<FunctionInvocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constructor_invocation: Option<FunctionInvocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_transfer_invocation: Option<FunctionInvocation>,
+    pub state_diff: TraceStateDiff,
+    pub execution_resources: TraceExecutionResources,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct L1HandlerTransactionTrace {
+    pub function_invocation: FunctionInvocation,
+    pub state_diff: TraceStateDiff,
+    pub execution_resources: TraceExecutionResources,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ExecuteInvocation {
+    Function(Box<FunctionInvocation>),
+    Reverted(RevertedInvocation),
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct RevertedInvocation {
+    pub revert_reason: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum TraceCallType {
+    #[serde(rename = "CALL")]
+    Call,
+    #[serde(rename = "LIBRARY_CALL")]
+    LibraryCall,
+}
+
+impl From<CallType> for TraceCallType {
+    fn from(call_type: CallType) -> Self {
+        match call_type {
+            CallType::Call => Self::Call,
+            CallType::Delegate => Self::LibraryCall,
+        }
+    }
+}
+
+/// A single call in the execution tree of a transaction.
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct FunctionInvocation {
+    pub contract_address: ContractAddress,
+    pub entry_point_selector: EntryPointSelector,
+    pub calldata: Calldata,
+    pub caller_address: ContractAddress,
+    pub class_hash: ClassHash,
+    pub entry_point_type: EntryPointType,
+    pub call_type: TraceCallType,
+    pub result: Vec<Felt>,
+    pub calls: Vec<FunctionInvocation>,
+    pub events: Vec<TraceEvent>,
+    pub messages: Vec<TraceMessageToL1>,
+    pub execution_resources: ComputationResources,
+}
+
+impl From<&CallInfo> for FunctionInvocation {
+    fn from(call_info: &CallInfo) -> Self {
+        let CallInfo { call, execution, resources, inner_calls, .. } = call_info;
+        Self {
+            contract_address: call.storage_address,
+            entry_point_selector: call.entry_point_selector,
+            calldata: call.calldata.clone(),
+            caller_address: call.caller_address,
+            class_hash: call.class_hash.expect("Class hash must be set after execution."),
+            entry_point_type: call.entry_point_type,
+            call_type: call.call_type.into(),
+            result: execution.retdata.0.clone(),
+            calls: inner_calls.iter().map(Self::from).collect(),
+            events: execution
+                .events
+                .iter()
+                .map(|ordered_event| TraceEvent {
+                    order: ordered_event.order,
+                    keys: ordered_event.event.keys.iter().map(|key| key.0).collect(),
+                    data: ordered_event.event.data.0.clone(),
+                })
+                .collect(),
+            messages: execution
+                .l2_to_l1_messages
+                .iter()
+                .map(|ordered_message| TraceMessageToL1 {
+                    order: ordered_message.order,
+                    from_address: call.storage_address,
+                    to_address: ordered_message.message.to_address,
+                    payload: ordered_message.message.payload.0.clone(),
+                })
+                .collect(),
+            execution_resources: ComputationResources::from(resources),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct TraceEvent {
+    pub order: usize,
+    pub keys: Vec<Felt>,
+    pub data: Vec<Felt>,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct TraceMessageToL1 {
+    pub order: usize,
+    pub from_address: ContractAddress,
+    pub to_address: EthAddress,
+    pub payload: Vec<Felt>,
+}
+
+/// The Cairo VM resources consumed by a call (or a transaction), keyed by their RPC names.
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub struct ComputationResources {
+    pub steps: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_holes: Option<usize>,
+    #[serde(flatten)]
+    pub builtin_applications: BTreeMap<String, usize>,
+}
+
+impl From<&ExecutionResources> for ComputationResources {
+    fn from(resources: &ExecutionResources) -> Self {
+        let builtin_applications = resources
+            .builtin_instance_counter
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(builtin, &count)| {
+                let name = match builtin {
+                    BuiltinName::segment_arena => builtin.to_str_with_suffix().to_string(),
+                    _ => format!("{}_applications", builtin.to_str_with_suffix()),
+                };
+                (name, count)
+            })
+            .collect();
+
+        Self {
+            steps: resources.n_steps,
+            memory_holes: match resources.n_memory_holes {
+                0 => None,
+                n_memory_holes => Some(n_memory_holes),
+            },
+            builtin_applications,
+        }
+    }
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub struct DataAvailabilityResources {
+    pub l1_gas: u128,
+    pub l1_data_gas: u128,
+}
+
+/// The total resources consumed by a transaction, including the OS overhead.
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub struct TraceExecutionResources {
+    #[serde(flatten)]
+    pub computation_resources: ComputationResources,
+    pub data_availability: DataAvailabilityResources,
+}
+
+impl From<&TransactionReceipt> for TraceExecutionResources {
+    fn from(tx_receipt: &TransactionReceipt) -> Self {
+        Self {
+            computation_resources: ComputationResources::from(&tx_receipt.resources.vm_resources),
+            data_availability: DataAvailabilityResources {
+                l1_gas: tx_receipt.da_gas.l1_gas,
+                l1_data_gas: tx_receipt.da_gas.l1_data_gas,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct StorageEntryDiff {
+    pub key: StorageKey,
+    pub value: Felt,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct ContractStorageDiff {
+    pub address: ContractAddress,
+    pub storage_entries: Vec<StorageEntryDiff>,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct DeclaredClass {
+    pub class_hash: ClassHash,
+    pub compiled_class_hash: CompiledClassHash,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct DeployedContract {
+    pub address: ContractAddress,
+    pub class_hash: ClassHash,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct ReplacedClass {
+    pub contract_address: ContractAddress,
+    pub class_hash: ClassHash,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct NonceUpdate {
+    pub contract_address: ContractAddress,
+    pub nonce: Nonce,
+}
+
+/// The state diff induced by a single transaction, in the RPC `STATE_DIFF` shape.
+/// All entries are sorted by their keys, so that the output is deterministic.
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub struct TraceStateDiff {
+    pub storage_diffs: Vec<ContractStorageDiff>,
+    pub deprecated_declared_classes: Vec<ClassHash>,
+    pub declared_classes: Vec<DeclaredClass>,
+    pub deployed_contracts: Vec<DeployedContract>,
+    pub replaced_classes: Vec<ReplacedClass>,
+    pub nonces: Vec<NonceUpdate>,
+}
+
+impl TraceStateDiff {
+    /// Builds the trace state diff from the given diff and the initial class hashes of the
+    /// touched contracts (see `StateMaps::to_thin_state_diff`).
+    pub fn new(state_diff: &StateMaps, initial_class_hashes: &StateMaps) -> Self {
+        Self::from(state_diff.to_thin_state_diff(initial_class_hashes))
+    }
+}
+
+impl From<ThinStateDiff> for TraceStateDiff {
+    fn from(state_diff: ThinStateDiff) -> Self {
+        let ThinStateDiff {
+            deployed_contracts,
+            storage_diffs,
+            declared_classes,
+            deprecated_declared_classes,
+            nonces,
+            replaced_classes,
+        } = state_diff;
+
+        Self {
+            storage_diffs: storage_diffs
+                .into_iter()
+                .map(|(address, storage_entries)| ContractStorageDiff {
+                    address,
+                    storage_entries: storage_entries
+                        .into_iter()
+                        .map(|(key, value)| StorageEntryDiff { key, value })
+                        .collect(),
+                })
+                .collect(),
+            deprecated_declared_classes,
+            declared_classes: declared_classes
+                .into_iter()
+                .map(|(class_hash, compiled_class_hash)| DeclaredClass {
+                    class_hash,
+                    compiled_class_hash,
+                })
+                .collect(),
+            deployed_contracts: deployed_contracts
+                .into_iter()
+                .map(|(address, class_hash)| DeployedContract { address, class_hash })
+                .collect(),
+            replaced_classes: replaced_classes
+                .into_iter()
+                .map(|(contract_address, class_hash)| ReplacedClass {
+                    contract_address,
+                    class_hash,
+                })
+                .collect(),
+            nonces: nonces
+                .into_iter()
+                .map(|(contract_address, nonce)| NonceUpdate { contract_address, nonce })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::core::{Nonce, PatriciaKey};
+use starknet_api::felt;
+use starknet_api::transaction::ResourceBoundsMapping;
+use starknet_types_core::felt::Felt;
+
+use crate::blockifier::config::TransactionExecutorConfig;
+use crate::blockifier::simulation::{
+    ExecuteInvocation, SimulationFlags, StorageEntryDiff, TransactionTrace,
+};
+use crate::blockifier::transaction_executor::{TransactionExecutor, BLOCK_STATE_ACCESS_ERR};
+use crate::context::BlockContext;
+use crate::state::state_api::StateReader;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::initial_test_state::test_state;
+use crate::test_utils::{create_calldata, CairoVersion, NonceManager, BALANCE};
+use crate::transaction::test_utils::{account_invoke_tx, block_context, max_resource_bounds};
+use crate::transaction::transaction_execution::Transaction;
+use crate::{invoke_tx_args, nonce, storage_key};
+
+#[rstest]
+fn test_simulate_transactions(
+    block_context: BlockContext,
+    max_resource_bounds: ResourceBoundsMapping,
+    #[values(false, true)] skip_validate: bool,
+    #[values(false, true)] skip_fee_charge: bool,
+) {
+    let account_contract = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1);
+    let state = test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(account_contract, 1), (test_contract, 1)],
+    );
+    let account_address = account_contract.get_instance_address(0);
+    let test_contract_address = test_contract.get_instance_address(0);
+    let storage_key = storage_key!(1993_u16);
+    let nonce_manager = &mut NonceManager::default();
+
+    // Two transactions of the same account; the second one is valid only on top of the first.
+    let txs: Vec<Transaction> = [felt!(93_u8), felt!(94_u8)]
+        .into_iter()
+        .map(|storage_value| {
+            Transaction::AccountTransaction(account_invoke_tx(invoke_tx_args! {
+                sender_address: account_address,
+                calldata: create_calldata(
+                    test_contract_address,
+                    "test_storage_read_write",
+                    &[*storage_key.0.key(), storage_value],
+                ),
+                resource_bounds: max_resource_bounds.clone(),
+                nonce: nonce_manager.next(account_address),
+            }))
+        })
+        .collect();
+
+    let mut tx_executor =
+        TransactionExecutor::new(state, block_context, TransactionExecutorConfig::default());
+    let traces =
+        tx_executor.simulate_transactions(&txs, SimulationFlags { skip_validate, skip_fee_charge });
+
+    assert_eq!(traces.len(), 2);
+    for (trace, (expected_value, expected_nonce)) in
+        traces.into_iter().zip([(felt!(93_u8), nonce!(1_u8)), (felt!(94_u8), nonce!(2_u8))])
+    {
+        let trace = trace.unwrap();
+        let TransactionTrace::Invoke(invoke_trace) = trace else {
+            panic!("Expected an invoke trace.");
+        };
+        assert_eq!(invoke_trace.validate_invocation.is_some(), !skip_validate);
+        assert_eq!(invoke_trace.fee_transfer_invocation.is_some(), !skip_fee_charge);
+        assert_matches!(
+            invoke_trace.execute_invocation,
+            ExecuteInvocation::Function(invocation)
+            if invocation.contract_address == account_address
+                && invocation.calls.len() == 1
+                && invocation.calls[0].contract_address == test_contract_address
+        );
+
+        let state_diff = invoke_trace.state_diff;
+        let contract_storage_diff = state_diff
+            .storage_diffs
+            .iter()
+            .find(|storage_diff| storage_diff.address == test_contract_address)
+            .unwrap();
+        assert_eq!(
+            contract_storage_diff.storage_entries,
+            vec![StorageEntryDiff { key: storage_key, value: expected_value }]
+        );
+        assert_eq!(state_diff.nonces.len(), 1);
+        assert_eq!(state_diff.nonces[0].contract_address, account_address);
+        assert_eq!(state_diff.nonces[0].nonce, expected_nonce);
+        assert!(state_diff.deployed_contracts.is_empty());
+        assert!(invoke_trace.execution_resources.computation_resources.steps > 0);
+    }
+
+    // Simulation must not affect the block state.
+    let block_state = tx_executor.block_state.as_ref().expect(BLOCK_STATE_ACCESS_ERR);
+    assert_eq!(block_state.get_nonce_at(account_address).unwrap(), Nonce(Felt::ZERO));
+    assert_eq!(block_state.get_storage_at(test_contract_address, storage_key).unwrap(), Felt::ZERO);
+}
@@ -71,9 +71,8 @@ impl<S: StateReader> StatefulValidator<S> {
         }
 
         // `__validate__` call.
-        let versioned_constants = &tx_context.block_context.versioned_constants();
-        let (_optional_call_info, actual_cost) =
-            self.validate(&tx, versioned_constants.tx_initial_gas())?;
+        let initial_gas = tx_context.initial_gas().map_err(TransactionExecutionError::from)?;
+        let (_optional_call_info, actual_cost) = self.validate(&tx, initial_gas)?;
 
         // Post validations.
         PostValidationReport::verify(&tx_context, &actual_cost)?;
@@ -133,6 +132,7 @@ impl<S: StateReader> StatefulValidator<S> {
             &execution_resources,
             validate_call_info.iter(),
             0,
+            None,
         )?;
 
         Ok((validate_call_info, tx_receipt))
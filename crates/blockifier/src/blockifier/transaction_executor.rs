@@ -7,22 +7,22 @@ use std::sync::Arc;
 #[cfg(feature = "concurrency")]
 use std::sync::Mutex;
 
-use itertools::FoldWhile::{Continue, Done};
-use itertools::Itertools;
 use starknet_api::core::ClassHash;
 use thiserror::Error;
 
-use crate::blockifier::config::TransactionExecutorConfig;
-use crate::bouncer::{Bouncer, BouncerWeights};
+use crate::blockifier::config::{BlockFillingPolicy, TransactionExecutorConfig};
+use crate::blockifier::simulation::{SimulationFlags, TraceStateDiff, TransactionTrace};
+use crate::bouncer::{Bouncer, BouncerCheckpoint, BouncerWeights};
 #[cfg(feature = "concurrency")]
 use crate::concurrency::worker_logic::WorkerExecutor;
 use crate::context::BlockContext;
-use crate::state::cached_state::{CachedState, CommitmentStateDiff, TransactionalState};
+use crate::state::cached_state::{CachedState, CommitmentStateDiff, Savepoint, TransactionalState};
 use crate::state::errors::StateError;
 use crate::state::state_api::StateReader;
 use crate::transaction::errors::TransactionExecutionError;
 use crate::transaction::objects::TransactionExecutionInfo;
 use crate::transaction::transaction_execution::Transaction;
+use crate::transaction::transaction_hash::verify_tx_hash;
 use crate::transaction::transactions::{ExecutableTransaction, ExecutionFlags};
 
 #[cfg(test)]
@@ -35,6 +35,11 @@ pub const BLOCK_STATE_ACCESS_ERR: &str = "Error: The block state should be `Some
 pub enum TransactionExecutorError {
     #[error("Transaction cannot be added to the current block, block capacity reached.")]
     BlockFull,
+    #[error(
+        "The checkpoint is already closed, by a rollback or a release of it or of an enclosing \
+         checkpoint."
+    )]
+    ClosedCheckpoint,
     #[error(transparent)]
     StateError(#[from] StateError),
     #[error(transparent)]
@@ -44,11 +49,83 @@ pub enum TransactionExecutorError {
 pub type TransactionExecutorResult<T> = Result<T, TransactionExecutorError>;
 pub type VisitedSegmentsMapping = Vec<(ClassHash, Vec<usize>)>;
 
+/// The outcome of executing a sequence of transactions on the block; transactions are identified by
+/// their index in the given sequence.
+#[derive(Debug, Default)]
+pub struct TransactionsExecutionResults {
+    /// The results of the processed transactions that were not rejected for lack of room in the
+    /// block, in order. Failed transactions are included, with their error.
+    pub results: Vec<(usize, TransactionExecutorResult<TransactionExecutionInfo>)>,
+    /// The transactions rejected for lack of room in the block, in order. They did not affect the
+    /// block state, and may be retried in a later block.
+    pub rejected_for_capacity: Vec<usize>,
+}
+
+impl TransactionsExecutionResults {
+    /// Returns the number of processed transactions; transactions following the last processed
+    /// one were not attempted, since the block filling stopped.
+    pub fn n_processed_txs(&self) -> usize {
+        self.results.len() + self.rejected_for_capacity.len()
+    }
+}
+
+/// Collects the results of executing transactions on the block, and decides when to stop filling
+/// the block, according to the block filling policy.
+struct BlockFiller {
+    policy: BlockFillingPolicy,
+    execution_results: TransactionsExecutionResults,
+    n_consecutive_rejections: usize,
+}
+
+impl BlockFiller {
+    fn new(policy: BlockFillingPolicy) -> Self {
+        Self {
+            policy,
+            execution_results: TransactionsExecutionResults::default(),
+            n_consecutive_rejections: 0,
+        }
+    }
+
+    /// Adds the result of the transaction at the given index, and returns whether to keep filling
+    /// the block.
+    fn add_result(
+        &mut self,
+        tx_index: usize,
+        result: TransactionExecutorResult<TransactionExecutionInfo>,
+    ) -> bool {
+        match result {
+            Err(TransactionExecutorError::BlockFull) => {
+                self.execution_results.rejected_for_capacity.push(tx_index);
+                self.n_consecutive_rejections += 1;
+                !self.policy.should_stop(
+                    self.execution_results.rejected_for_capacity.len(),
+                    self.n_consecutive_rejections,
+                )
+            }
+            result => {
+                self.execution_results.results.push((tx_index, result));
+                self.n_consecutive_rejections = 0;
+                true
+            }
+        }
+    }
+}
+
+/// A checkpoint of the block built by a transaction executor, to roll back to or release; see
+/// [TransactionExecutor::checkpoint].
+#[derive(Debug)]
+#[must_use = "A checkpoint must be rolled back to or released."]
+pub struct BlockCheckpoint {
+    savepoint: Savepoint,
+    bouncer_checkpoint: BouncerCheckpoint,
+}
+
 // TODO(Gilad): make this hold TransactionContext instead of BlockContext.
 pub struct TransactionExecutor<S: StateReader> {
     pub block_context: BlockContext,
     pub bouncer: Bouncer,
-    // Note: this config must not affect the execution result (e.g. state diff and traces).
+    // Note: apart from rejecting transactions with invalid hashes, this config must not affect the
+    // execution result (e.g. state diff and traces).
     pub config: TransactionExecutorConfig,
 
     // State-related fields.
@@ -87,6 +164,10 @@ impl<S: StateReader> TransactionExecutor<S> {
         &mut self,
         tx: &Transaction,
     ) -> TransactionExecutorResult<TransactionExecutionInfo> {
+        if self.config.verify_tx_hashes {
+            verify_tx_hash(tx, &self.block_context.chain_info.chain_id)?;
+        }
+
         let mut transactional_state = TransactionalState::create_transactional(
             self.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR),
         );
@@ -96,15 +177,16 @@ impl<S: StateReader> TransactionExecutor<S> {
         let tx_execution_result =
             tx.execute_raw(&mut transactional_state, &self.block_context, execution_flags);
         match tx_execution_result {
-            Ok(tx_execution_info) => {
+            Ok(mut tx_execution_info) => {
                 let tx_state_changes_keys =
                     transactional_state.get_actual_state_changes()?.into_keys();
-                self.bouncer.try_update(
+                let tx_weights = self.bouncer.try_update(
                     &transactional_state,
                     &tx_state_changes_keys,
                     &tx_execution_info.summarize(),
                     &tx_execution_info.transaction_receipt.resources,
                 )?;
+                tx_execution_info.bouncer_weights = Some(tx_weights);
                 transactional_state.commit();
                 Ok(tx_execution_info)
             }
@@ -115,19 +197,124 @@ impl<S: StateReader> TransactionExecutor<S> {
         }
     }
 
+    /// Opens a checkpoint of the block built so far: the transactions executed from now on may be
+    /// removed from the block by rolling back to it; e.g., to remove a bundle of transactions that
+    /// must be included atomically. Checkpoints may be nested, and are closed (by a rollback or a
+    /// release) innermost first; closing a checkpoint also closes the checkpoints opened after it.
+    /// Note: while a checkpoint is open, the block state writes and the bouncer additions of each
+    /// transaction are recorded; a rollback takes time linear in the ones of the removed
+    /// transactions.
+    pub fn checkpoint(&mut self) -> BlockCheckpoint {
+        BlockCheckpoint {
+            savepoint: self.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR).savepoint(),
+            bouncer_checkpoint: self.bouncer.checkpoint(),
+        }
+    }
+
+    /// Removes the transactions executed since the given checkpoint was opened from the block
+    /// state and the bouncer, and closes it. Fails if the checkpoint is already closed.
+    pub fn rollback(&mut self, checkpoint: BlockCheckpoint) -> TransactionExecutorResult<()> {
+        self.verify_checkpoint_open(&checkpoint)?;
+        let BlockCheckpoint { savepoint, bouncer_checkpoint } = checkpoint;
+        self.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR).rollback_to_savepoint(savepoint);
+        self.bouncer.rollback(bouncer_checkpoint);
+        Ok(())
+    }
+
+    /// Closes the given checkpoint, keeping the transactions executed since it was opened; they are
+    /// removed if an enclosing checkpoint is rolled back. Fails if the checkpoint is already
+    /// closed.
+    pub fn release_checkpoint(
+        &mut self,
+        checkpoint: BlockCheckpoint,
+    ) -> TransactionExecutorResult<()> {
+        self.verify_checkpoint_open(&checkpoint)?;
+        let BlockCheckpoint { savepoint, bouncer_checkpoint } = checkpoint;
+        self.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR).release_savepoint(savepoint);
+        self.bouncer.release_checkpoint(bouncer_checkpoint);
+        Ok(())
+    }
+
+    fn verify_checkpoint_open(
+        &self,
+        checkpoint: &BlockCheckpoint,
+    ) -> TransactionExecutorResult<()> {
+        let block_state = self.block_state.as_ref().expect(BLOCK_STATE_ACCESS_ERR);
+        if !block_state.is_savepoint_open(&checkpoint.savepoint)
+            || !self.bouncer.is_checkpoint_open(&checkpoint.bouncer_checkpoint)
+        {
+            return Err(TransactionExecutorError::ClosedCheckpoint);
+        }
+
+        Ok(())
+    }
+
+    /// Simulates the given transactions, one on top of the other, without affecting the block
+    /// state or the bouncer. Returns, per transaction, its trace in the Starknet JSON-RPC
+    /// `TransactionTrace` shape, or the execution error.
+    /// Note: a failing transaction does not affect the state on which subsequent transactions are
+    /// simulated.
+    pub fn simulate_transactions(
+        &mut self,
+        txs: &[Transaction],
+        simulation_flags: SimulationFlags,
+    ) -> Vec<TransactionExecutorResult<TransactionTrace>> {
+        let mut simulation_state = TransactionalState::create_transactional(
+            self.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR),
+        );
+        let execution_flags = ExecutionFlags {
+            charge_fee: !simulation_flags.skip_fee_charge,
+            validate: !simulation_flags.skip_validate,
+            concurrency_mode: false,
+        };
+
+        let results = txs
+            .iter()
+            .map(|tx| -> TransactionExecutorResult<TransactionTrace> {
+                let mut transactional_state =
+                    TransactionalState::create_transactional(&mut simulation_state);
+                let tx_execution_result =
+                    tx.execute_raw(&mut transactional_state, &self.block_context, execution_flags);
+                match tx_execution_result {
+                    Ok(tx_execution_info) => {
+                        let state_diff = transactional_state.to_state_diff()?;
+                        let trace_state_diff = TraceStateDiff::new(
+                            &state_diff,
+                            &transactional_state.cache.borrow().initial_reads,
+                        );
+                        transactional_state.commit();
+                        Ok(TransactionTrace::new(tx, &tx_execution_info, trace_state_diff))
+                    }
+                    Err(error) => {
+                        transactional_state.abort();
+                        Err(TransactionExecutorError::TransactionExecutionError(error))
+                    }
+                }
+            })
+            .collect();
+
+        // Simulation must not affect the block state.
+        simulation_state.abort();
+        results
+    }
+
+    /// Reorders the given transactions for execution, by the configured transaction ordering.
+    pub fn order_txs(&self, txs: &mut [Transaction]) {
+        self.config.transaction_ordering.as_ref().order(txs);
+    }
+
     pub fn execute_txs_sequentially(
         &mut self,
         txs: &[Transaction],
-    ) -> Vec<TransactionExecutorResult<TransactionExecutionInfo>> {
-        let mut results = Vec::new();
-        for tx in txs {
-            match self.execute(tx) {
-                Ok(tx_execution_info) => results.push(Ok(tx_execution_info)),
-                Err(TransactionExecutorError::BlockFull) => break,
-                Err(error) => results.push(Err(error)),
+    ) -> TransactionsExecutionResults {
+        let mut block_filler = BlockFiller::new(self.config.block_filling_policy);
+        for (tx_index, tx) in txs.iter().enumerate() {
+            let keep_filling = block_filler.add_result(tx_index, self.execute(tx));
+            if !keep_filling {
+                break;
             }
         }
-        results
+        block_filler.execution_results
     }
 
     #[cfg(not(feature = "concurrency"))]
@@ -174,12 +361,10 @@ impl<S: StateReader> TransactionExecutor<S> {
 
 impl<S: StateReader + Send + Sync> TransactionExecutor<S> {
     /// Executes the given transactions on the state maintained by the executor.
-    /// Stops if and when there is no more room in the block, and returns the executed transactions'
-    /// results.
-    pub fn execute_txs(
-        &mut self,
-        txs: &[Transaction],
-    ) -> Vec<TransactionExecutorResult<TransactionExecutionInfo>> {
+    /// Transactions for which there is no room in the block are rejected; whether to stop or to
+    /// skip them is determined by the block filling policy. Returns the processed transactions'
+    /// results, and the rejected transactions, separately.
+    pub fn execute_txs(&mut self, txs: &[Transaction]) -> TransactionsExecutionResults {
         if !self.config.concurrency_config.enabled {
             log::debug!("Executing transactions sequentially.");
             self.execute_txs_sequentially(txs)
@@ -199,19 +384,79 @@ impl<S: StateReader + Send + Sync> TransactionExecutor<S> {
                  than 0. It equals {:?} ",
                 n_workers
             );
-            txs.chunks(chunk_size)
-                .fold_while(Vec::new(), |mut results, chunk| {
-                    let chunk_results = self.execute_chunk(chunk);
-                    if chunk_results.len() < chunk.len() {
-                        // Block is full.
-                        results.extend(chunk_results);
-                        Done(results)
-                    } else {
-                        results.extend(chunk_results);
-                        Continue(results)
-                    }
-                })
-                .into_inner()
+            let mut block_filler = BlockFiller::new(self.config.block_filling_policy);
+            if self.config.verify_tx_hashes {
+                self.execute_verified_txs_concurrently(txs, &mut block_filler);
+            } else {
+                self.execute_txs_concurrently(txs, 0, &mut block_filler);
+            }
+            block_filler.execution_results
+        }
+    }
+
+    /// Executes the given transactions concurrently, chunk by chunk, and adds their results to the
+    /// block filler; `first_tx_index` is the index of the first given transaction.
+    /// Returns whether to keep filling the block.
+    fn execute_txs_concurrently(
+        &mut self,
+        txs: &[Transaction],
+        first_tx_index: usize,
+        block_filler: &mut BlockFiller,
+    ) -> bool {
+        let chunk_size = self.config.concurrency_config.chunk_size;
+        let mut tx_index = 0;
+        while tx_index < txs.len() {
+            let chunk = &txs[tx_index..txs.len().min(tx_index + chunk_size)];
+            let chunk_results = self.execute_chunk(chunk);
+            let n_committed_txs = chunk_results.len();
+            for result in chunk_results {
+                block_filler.add_result(first_tx_index + tx_index, result);
+                tx_index += 1;
+            }
+
+            if n_committed_txs < chunk.len() {
+                // No room in the block for the next transaction; the transactions following it in
+                // the chunk are (re-)executed in the next chunk, if the block filling continues.
+                let keep_filling = block_filler.add_result(
+                    first_tx_index + tx_index,
+                    Err(TransactionExecutorError::BlockFull),
+                );
+                if !keep_filling {
+                    return false;
+                }
+                tx_index += 1;
+            }
+        }
+        true
+    }
+
+    /// Rejects the transactions with invalid hashes, and executes each run of consecutive valid
+    /// transactions concurrently; the results keep the order of the given transactions.
+    fn execute_verified_txs_concurrently(
+        &mut self,
+        txs: &[Transaction],
+        block_filler: &mut BlockFiller,
+    ) {
+        let chain_id = &self.block_context.chain_info.chain_id;
+        let mut verification_errors: Vec<_> =
+            txs.iter().map(|tx| verify_tx_hash(tx, chain_id).err()).collect();
+
+        let mut tx_index = 0;
+        while tx_index < txs.len() {
+            let n_valid_txs = verification_errors[tx_index..]
+                .iter()
+                .position(Option::is_some)
+                .unwrap_or(txs.len() - tx_index);
+            let valid_txs = &txs[tx_index..tx_index + n_valid_txs];
+            if !self.execute_txs_concurrently(valid_txs, tx_index, block_filler) {
+                break;
+            }
+
+            tx_index += n_valid_txs;
+            if let Some(error) = verification_errors.get_mut(tx_index).and_then(Option::take) {
+                block_filler.add_result(tx_index, Err(error.into()));
+                tx_index += 1;
+            }
         }
     }
 
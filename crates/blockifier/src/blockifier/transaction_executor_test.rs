@@ -1,11 +1,14 @@
 use assert_matches::assert_matches;
 use pretty_assertions::assert_eq;
 use rstest::rstest;
-use starknet_api::felt;
-use starknet_api::transaction::{Fee, TransactionVersion};
+use starknet_api::core::{ContractAddress, PatriciaKey};
+use starknet_api::transaction::{Fee, Tip, TransactionVersion};
+use starknet_api::{contract_address, felt, patricia_key};
 use starknet_types_core::felt::Felt;
 
-use crate::blockifier::config::TransactionExecutorConfig;
+use crate::blockifier::config::{
+    BlockFillingPolicy, SharedTransactionOrdering, TipOrdering, TransactionExecutorConfig,
+};
 use crate::blockifier::transaction_executor::{
     TransactionExecutor, TransactionExecutorError, BLOCK_STATE_ACCESS_ERR,
 };
@@ -22,11 +25,13 @@ use crate::test_utils::{
 };
 use crate::transaction::account_transaction::AccountTransaction;
 use crate::transaction::errors::TransactionExecutionError;
+use crate::transaction::objects::TransactionInfoCreator;
 use crate::transaction::test_utils::{
     account_invoke_tx, block_context, calculate_class_info_for_testing, create_test_init_data,
     emit_n_events_tx, l1_resource_bounds, TestInitData,
 };
 use crate::transaction::transaction_execution::Transaction;
+use crate::transaction::transaction_hash::calculate_tx_hash;
 use crate::transaction::transactions::L1HandlerTransaction;
 use crate::{declare_tx_args, deploy_account_tx_args, invoke_tx_args, nonce};
 
@@ -41,8 +46,10 @@ fn tx_executor_test_body<S: StateReader>(
     // TODO(Arni, 30/03/2024): Consider adding a test for the transaction execution info. If A test
     // should not be added, rename the test to `test_bouncer_info`.
     // TODO(Arni, 30/03/2024): Test all bouncer weights.
-    let _tx_execution_info = tx_executor.execute(&tx).unwrap();
+    let tx_execution_info = tx_executor.execute(&tx).unwrap();
     let bouncer_weights = tx_executor.bouncer.get_accumulated_weights();
+    // The block holds the transaction only.
+    assert_eq!(tx_execution_info.bouncer_weights.as_ref(), Some(bouncer_weights));
     assert_eq!(bouncer_weights.state_diff_size, expected_bouncer_weights.state_diff_size);
     assert_eq!(
         bouncer_weights.message_segment_length,
@@ -298,16 +305,18 @@ fn test_execute_txs_bouncing() {
 
     // Check execution results.
     let expected_offset = 3;
-    assert_eq!(results.len(), expected_offset);
+    assert_eq!(results.results.len(), expected_offset);
+    assert_eq!(results.rejected_for_capacity, vec![expected_offset]);
 
-    assert!(results[0].is_ok());
+    assert_eq!(results.results[0].0, 0);
+    assert!(results.results[0].1.is_ok());
     assert_matches!(
-        results[1].as_ref().unwrap_err(),
+        results.results[1].1.as_ref().unwrap_err(),
         TransactionExecutorError::TransactionExecutionError(
             TransactionExecutionError::TransactionTooLarge
         )
     );
-    assert!(results[2].is_ok());
+    assert!(results.results[2].1.is_ok());
 
     // Check state.
     assert_eq!(
@@ -323,15 +332,17 @@ fn test_execute_txs_bouncing() {
     // Check idempotency: excess transactions should not be added.
     let remaining_txs = &txs[expected_offset..];
     let remaining_tx_results = tx_executor.execute_txs(remaining_txs);
-    assert_eq!(remaining_tx_results.len(), 0);
+    assert_eq!(remaining_tx_results.results.len(), 0);
+    assert_eq!(remaining_tx_results.rejected_for_capacity, vec![0]);
 
     // Reset the bouncer and add the remaining transactions.
     tx_executor.bouncer = Bouncer::new(tx_executor.block_context.bouncer_config.clone());
     let remaining_tx_results = tx_executor.execute_txs(remaining_txs);
 
-    assert_eq!(remaining_tx_results.len(), 2);
-    assert!(remaining_tx_results[0].is_ok());
-    assert!(remaining_tx_results[1].is_ok());
+    assert_eq!(remaining_tx_results.results.len(), 2);
+    assert!(remaining_tx_results.rejected_for_capacity.is_empty());
+    assert!(remaining_tx_results.results[0].1.is_ok());
+    assert!(remaining_tx_results.results[1].1.is_ok());
     assert_eq!(
         tx_executor
             .block_state
@@ -342,3 +353,245 @@ fn test_execute_txs_bouncing() {
         nonce!(4_u32)
     );
 }
+
+#[rstest]
+#[case::stop_at_first_rejection(BlockFillingPolicy::StopAtFirstRejection, vec![0, 1], vec![2])]
+#[case::exhausted_skips(BlockFillingPolicy::SkipRejected { max_skips: 1 }, vec![0, 1], vec![2, 3])]
+#[case::skip_rejected(
+    BlockFillingPolicy::SkipRejected { max_skips: 2 },
+    vec![0, 1, 4],
+    vec![2, 3, 5]
+)]
+#[case::consecutive_rejections(
+    BlockFillingPolicy::StopAfterConsecutiveRejections { n_rejections: 2 },
+    vec![0, 1],
+    vec![2, 3]
+)]
+#[case::non_consecutive_rejections(
+    BlockFillingPolicy::StopAfterConsecutiveRejections { n_rejections: 3 },
+    vec![0, 1, 4],
+    vec![2, 3, 5]
+)]
+fn test_execute_txs_block_filling_policies(
+    #[case] block_filling_policy: BlockFillingPolicy,
+    #[case] expected_executed_txs: Vec<usize>,
+    #[case] expected_rejected_txs: Vec<usize>,
+) {
+    let config = TransactionExecutorConfig {
+        block_filling_policy,
+        ..TransactionExecutorConfig::create_for_testing()
+    };
+    let max_n_events_in_block = 10;
+    let block_context = BlockContext::create_for_bouncer_testing(max_n_events_in_block);
+    let TestInitData { state, account_address, contract_address, .. } =
+        create_test_init_data(&block_context.chain_info, CairoVersion::Cairo1);
+    let mut tx_executor = TransactionExecutor::new(state, block_context, config);
+
+    let txs: Vec<Transaction> = [
+        emit_n_events_tx(1, account_address, contract_address, nonce!(0_u32)),
+        emit_n_events_tx(8, account_address, contract_address, nonce!(1_u32)),
+        // No room for these in block.
+        emit_n_events_tx(2, account_address, contract_address, nonce!(2_u32)),
+        emit_n_events_tx(2, account_address, contract_address, nonce!(2_u32)),
+        // Fills the block, if reached.
+        emit_n_events_tx(1, account_address, contract_address, nonce!(2_u32)),
+        emit_n_events_tx(1, account_address, contract_address, nonce!(3_u32)),
+    ]
+    .into_iter()
+    .map(Transaction::AccountTransaction)
+    .collect();
+
+    let results = tx_executor.execute_txs(&txs);
+
+    let executed_txs: Vec<usize> = results
+        .results
+        .iter()
+        .map(|(tx_index, result)| {
+            assert!(result.is_ok());
+            *tx_index
+        })
+        .collect();
+    assert_eq!(executed_txs, expected_executed_txs);
+    assert_eq!(results.rejected_for_capacity, expected_rejected_txs);
+    assert_eq!(results.n_processed_txs(), expected_rejected_txs.last().unwrap() + 1);
+
+    // Rejected transactions do not affect the state.
+    assert_eq!(
+        tx_executor
+            .block_state
+            .as_ref()
+            .expect(BLOCK_STATE_ACCESS_ERR)
+            .get_nonce_at(account_address)
+            .unwrap(),
+        nonce!(expected_executed_txs.len())
+    );
+}
+
+#[rstest]
+fn test_checkpoint_and_rollback(block_context: BlockContext) {
+    let TestInitData { state, account_address, contract_address, .. } =
+        create_test_init_data(&block_context.chain_info, CairoVersion::Cairo1);
+    let mut tx_executor = TransactionExecutor::new(
+        state,
+        block_context,
+        TransactionExecutorConfig::create_for_testing(),
+    );
+    let emit_events_tx = |nonce| {
+        Transaction::AccountTransaction(emit_n_events_tx(
+            1,
+            account_address,
+            contract_address,
+            nonce,
+        ))
+    };
+    let get_nonce = |tx_executor: &TransactionExecutor<_>| {
+        tx_executor
+            .block_state
+            .as_ref()
+            .expect(BLOCK_STATE_ACCESS_ERR)
+            .get_nonce_at(account_address)
+            .unwrap()
+    };
+
+    tx_executor.execute(&emit_events_tx(nonce!(0_u32))).unwrap();
+    let (state_diff_at_checkpoint, _, weights_at_checkpoint) = tx_executor.finalize().unwrap();
+    let checkpoint = tx_executor.checkpoint();
+
+    // Speculatively add a bundle of transactions, and remove it.
+    tx_executor.execute(&emit_events_tx(nonce!(1_u32))).unwrap();
+    tx_executor.execute(&emit_events_tx(nonce!(2_u32))).unwrap();
+    assert_eq!(get_nonce(&tx_executor), nonce!(3_u32));
+    tx_executor.rollback(checkpoint).unwrap();
+
+    assert_eq!(get_nonce(&tx_executor), nonce!(1_u32));
+    let (state_diff, _, weights) = tx_executor.finalize().unwrap();
+    assert_eq!(state_diff, state_diff_at_checkpoint);
+    assert_eq!(weights, weights_at_checkpoint);
+
+    // The block can be built on top of the rolled back state, and a bundle can be kept.
+    let checkpoint = tx_executor.checkpoint();
+    tx_executor.execute(&emit_events_tx(nonce!(1_u32))).unwrap();
+    let inner_checkpoint = tx_executor.checkpoint();
+    tx_executor.release_checkpoint(checkpoint).unwrap();
+    assert_eq!(get_nonce(&tx_executor), nonce!(2_u32));
+
+    // A closed checkpoint is not applied, even if a checkpoint was opened after it.
+    let new_checkpoint = tx_executor.checkpoint();
+    let new_inner_checkpoint = tx_executor.checkpoint();
+    assert_matches!(
+        tx_executor.rollback(inner_checkpoint).unwrap_err(),
+        TransactionExecutorError::ClosedCheckpoint
+    );
+    tx_executor.release_checkpoint(new_checkpoint).unwrap();
+    assert_matches!(
+        tx_executor.release_checkpoint(new_inner_checkpoint).unwrap_err(),
+        TransactionExecutorError::ClosedCheckpoint
+    );
+}
+
+#[rstest]
+fn test_execute_txs_verifying_hashes(block_context: BlockContext) {
+    let config = TransactionExecutorConfig {
+        verify_tx_hashes: true,
+        ..TransactionExecutorConfig::create_for_testing()
+    };
+    let chain_id = block_context.chain_info.chain_id.clone();
+    let TestInitData { state, account_address, contract_address, .. } =
+        create_test_init_data(&block_context.chain_info, CairoVersion::Cairo1);
+    let mut tx_executor = TransactionExecutor::new(state, block_context, config);
+
+    let with_valid_hash = |tx: AccountTransaction| {
+        let mut tx = Transaction::AccountTransaction(tx);
+        let tx_hash = calculate_tx_hash(&tx, &chain_id);
+        if let Transaction::AccountTransaction(AccountTransaction::Invoke(invoke_tx)) = &mut tx {
+            invoke_tx.tx_hash = tx_hash;
+        }
+        tx
+    };
+    let txs = [
+        with_valid_hash(emit_n_events_tx(1, account_address, contract_address, nonce!(0_u32))),
+        // The hash of this transaction is not set.
+        Transaction::AccountTransaction(emit_n_events_tx(
+            1,
+            account_address,
+            contract_address,
+            nonce!(1_u32),
+        )),
+        with_valid_hash(emit_n_events_tx(1, account_address, contract_address, nonce!(1_u32))),
+    ];
+
+    let results = tx_executor.execute_txs(&txs);
+
+    assert_eq!(results.results.len(), 3);
+    assert!(results.results[0].1.is_ok());
+    assert_matches!(
+        results.results[1].1.as_ref().unwrap_err(),
+        TransactionExecutorError::TransactionExecutionError(
+            TransactionExecutionError::InvalidTransactionHash { .. }
+        )
+    );
+    assert!(results.results[2].1.is_ok());
+    assert_eq!(
+        tx_executor
+            .block_state
+            .as_ref()
+            .expect(BLOCK_STATE_ACCESS_ERR)
+            .get_nonce_at(account_address)
+            .unwrap(),
+        nonce!(2_u32)
+    );
+}
+
+#[rstest]
+fn test_tip_ordering(block_context: BlockContext) {
+    let config = TransactionExecutorConfig {
+        transaction_ordering: SharedTransactionOrdering::new(TipOrdering),
+        ..TransactionExecutorConfig::create_for_testing()
+    };
+    let state = test_state(&block_context.chain_info, BALANCE, &[]);
+    let tx_executor = TransactionExecutor::new(state, block_context, config);
+
+    // The transactions of the first sender arrive out of nonce order, and the last transaction is
+    // of an older version, which offers no tip.
+    let txs_data = [
+        ("0x1", 1_u8, 5, TransactionVersion::THREE),
+        ("0x2", 0, 3, TransactionVersion::THREE),
+        ("0x1", 0, 1, TransactionVersion::THREE),
+        ("0x2", 1, 0, TransactionVersion::THREE),
+        ("0x3", 0, 3, TransactionVersion::THREE),
+        ("0x4", 0, 2, TransactionVersion::ONE),
+    ];
+    let mut txs: Vec<Transaction> = txs_data
+        .into_iter()
+        .map(|(sender_address, tx_nonce, tip, version)| {
+            Transaction::AccountTransaction(account_invoke_tx(invoke_tx_args! {
+                sender_address: contract_address!(sender_address),
+                tip: Tip(tip),
+                version,
+                nonce: nonce!(tx_nonce),
+            }))
+        })
+        .collect();
+    tx_executor.order_txs(&mut txs);
+
+    // A transaction never precedes the preceding transactions of its sender, whose tips bound its
+    // priority; transactions of equal priority are ordered by the arrival of their senders.
+    let ordered_txs: Vec<_> = txs
+        .iter()
+        .map(|tx| {
+            let tx_info = tx.create_tx_info();
+            (tx_info.sender_address(), tx_info.nonce())
+        })
+        .collect();
+    assert_eq!(
+        ordered_txs,
+        vec![
+            (contract_address!("0x2"), nonce!(0_u8)),
+            (contract_address!("0x3"), nonce!(0_u8)),
+            (contract_address!("0x1"), nonce!(0_u8)),
+            (contract_address!("0x1"), nonce!(1_u8)),
+            (contract_address!("0x2"), nonce!(1_u8)),
+            (contract_address!("0x4"), nonce!(0_u8)),
+        ]
+    );
+}
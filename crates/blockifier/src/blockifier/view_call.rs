@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use starknet_api::transaction::TransactionVersion;
+use thiserror::Error;
+
+use crate::context::{BlockContext, TransactionContext};
+use crate::execution::call_info::Retdata;
+use crate::execution::entry_point::{CallEntryPoint, EntryPointExecutionContext};
+use crate::execution::errors::EntryPointExecutionError;
+use crate::execution::stack_trace::{gen_entry_point_execution_error_trace, ErrorStack};
+use crate::state::cached_state::{CachedState, MutRefState};
+use crate::state::state_api::StateReader;
+use crate::transaction::errors::TransactionExecutionError;
+use crate::transaction::objects::{
+    CommonAccountFields, DeprecatedTransactionInfo, TransactionInfo,
+};
+
+#[cfg(test)]
+#[path = "view_call_test.rs"]
+pub mod test;
+
+#[derive(Debug, Error)]
+pub enum ViewCallError {
+    #[error("{error}")]
+    ExecutionError { error: EntryPointExecutionError, error_stack: ErrorStack },
+    #[error(transparent)]
+    TransactionExecutionError(#[from] TransactionExecutionError),
+}
+
+pub type ViewCallResult<T> = Result<T, ViewCallError>;
+
+/// Executes the given call against the given state, without an enclosing transaction (the
+/// equivalent of `starknet_call`), and returns its return data.
+/// All writes made by the call are discarded.
+/// In strict mode, state-modifying syscalls (e.g., `storage_write`, `deploy`, `emit_event`) fail
+/// the call.
+pub fn call<S: StateReader>(
+    state: &mut S,
+    block_context: &BlockContext,
+    call: CallEntryPoint,
+    strict: bool,
+) -> ViewCallResult<Retdata> {
+    let tx_context = Arc::new(TransactionContext {
+        block_context: block_context.clone(),
+        tx_info: view_call_tx_info(),
+    });
+    let limit_steps_by_resources = false;
+    let mut context = if strict {
+        EntryPointExecutionContext::new_view(tx_context, limit_steps_by_resources)?
+    } else {
+        EntryPointExecutionContext::new_invoke(tx_context, limit_steps_by_resources)?
+    };
+
+    // The cached state is dropped at the end of the call, discarding all writes.
+    let mut cached_state = CachedState::new(MutRefState::new(state));
+    let storage_address = call.storage_address;
+    let selector = call.entry_point_selector;
+    let class_hash = call.class_hash;
+    match call.execute(&mut cached_state, &mut ExecutionResources::default(), &mut context) {
+        Ok(call_info) => Ok(call_info.execution.retdata),
+        Err(error) => {
+            let class_hash = match class_hash {
+                Some(class_hash) => class_hash,
+                None => cached_state.get_class_hash_at(storage_address).unwrap_or_default(),
+            };
+            let error_stack = gen_entry_point_execution_error_trace(
+                &error,
+                &storage_address,
+                &class_hash,
+                &selector,
+            );
+            Err(ViewCallError::ExecutionError { error, error_stack })
+        }
+    }
+}
+
+/// Returns the (synthetic) transaction info of a view call: a query transaction with no fee.
+fn view_call_tx_info() -> TransactionInfo {
+    TransactionInfo::Deprecated(DeprecatedTransactionInfo {
+        common_fields: CommonAccountFields {
+            version: TransactionVersion::ONE,
+            only_query: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
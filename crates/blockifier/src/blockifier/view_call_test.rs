@@ -0,0 +1,92 @@
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::core::PatriciaKey;
+use starknet_api::transaction::Calldata;
+use starknet_api::{calldata, felt};
+use starknet_types_core::felt::Felt;
+
+use crate::abi::abi_utils::selector_from_name;
+use crate::blockifier::view_call::{call, ViewCallError};
+use crate::context::BlockContext;
+use crate::execution::call_info::Retdata;
+use crate::execution::entry_point::CallEntryPoint;
+use crate::state::state_api::StateReader;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::initial_test_state::test_state;
+use crate::test_utils::{trivial_external_entry_point_new, CairoVersion, BALANCE};
+use crate::{retdata, storage_key};
+
+#[rstest]
+fn test_view_call(
+    #[values(CairoVersion::Cairo0, CairoVersion::Cairo1)] cairo_version: CairoVersion,
+    #[values(false, true)] strict: bool,
+) {
+    let block_context = BlockContext::create_for_testing();
+    let test_contract = FeatureContract::TestContract(cairo_version);
+    let mut state = test_state(&block_context.chain_info, BALANCE, &[(test_contract, 1)]);
+    let (key, value) = (felt!(1993_u16), felt!(93_u8));
+    let entry_point_call = CallEntryPoint {
+        entry_point_selector: selector_from_name("test_storage_read_write"),
+        calldata: calldata![key, value],
+        ..trivial_external_entry_point_new(test_contract)
+    };
+
+    let result = call(&mut state, &block_context, entry_point_call, strict);
+    if strict {
+        let error = result.unwrap_err();
+        let ViewCallError::ExecutionError { error_stack, .. } = error else {
+            panic!("Expected an execution error.");
+        };
+        assert!(String::from(error_stack)
+            .contains("Unauthorized syscall storage_write in execution mode View."));
+    } else {
+        assert_eq!(result.unwrap(), retdata![value]);
+    }
+
+    // Writes are never committed.
+    assert_eq!(
+        state
+            .get_storage_at(test_contract.get_instance_address(0), storage_key!(1993_u16))
+            .unwrap(),
+        Felt::ZERO
+    );
+}
+
+#[rstest]
+#[case::emit_event("emit_event")]
+#[case::send_message_to_l1("send_message_to_l1")]
+#[case::deploy("deploy")]
+#[case::replace_class("replace_class")]
+fn test_strict_view_call_rejects_state_modifying_syscalls(
+    #[values(CairoVersion::Cairo0, CairoVersion::Cairo1)] cairo_version: CairoVersion,
+    #[case] syscall_name: &str,
+) {
+    let block_context = BlockContext::create_for_testing();
+    let test_contract = FeatureContract::TestContract(cairo_version);
+    let mut state = test_state(&block_context.chain_info, BALANCE, &[(test_contract, 1)]);
+    let class_hash = test_contract.get_class_hash().0;
+    let (entry_point_name, calldata) = match (syscall_name, cairo_version) {
+        // Emits a single event, with one key and one data element.
+        ("emit_event", _) => ("test_emit_events", vec![felt!(1_u8); 5]),
+        ("send_message_to_l1", CairoVersion::Cairo0) => ("send_message", vec![felt!(1_u8)]),
+        ("send_message_to_l1", CairoVersion::Cairo1) => {
+            ("test_send_message_to_l1", vec![felt!(1_u8), Felt::ZERO])
+        }
+        // Deploys the test contract with no constructor calldata, from a zero deployer address.
+        ("deploy", _) => ("test_deploy", vec![class_hash, felt!(1_u8), Felt::ZERO, Felt::ONE]),
+        ("replace_class", _) => ("test_replace_class", vec![class_hash]),
+        _ => unreachable!(),
+    };
+    let entry_point_call = CallEntryPoint {
+        entry_point_selector: selector_from_name(entry_point_name),
+        calldata: Calldata(calldata.into()),
+        ..trivial_external_entry_point_new(test_contract)
+    };
+
+    let error = call(&mut state, &block_context, entry_point_call, true).unwrap_err();
+    let ViewCallError::ExecutionError { error_stack, .. } = error else {
+        panic!("Expected an execution error.");
+    };
+    assert!(String::from(error_stack)
+        .contains(&format!("Unauthorized syscall {syscall_name} in execution mode View.")));
+}
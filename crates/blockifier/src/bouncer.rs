@@ -1,9 +1,12 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::sync::Arc;
 
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use starknet_api::core::ClassHash;
+use starknet_api::core::{ClassHash, ContractAddress};
 
 use crate::blockifier::transaction_executor::{
     TransactionExecutorError, TransactionExecutorResult,
@@ -11,7 +14,7 @@ use crate::blockifier::transaction_executor::{
 use crate::execution::call_info::ExecutionSummary;
 use crate::fee::gas_usage::get_onchain_data_segment_length;
 use crate::state::cached_state::{StateChangesKeys, StorageEntry};
-use crate::state::state_api::StateReader;
+use crate::state::state_api::{StateReader, StateResult};
 use crate::transaction::errors::TransactionExecutionError;
 use crate::transaction::objects::{
     ExecutionResourcesTraits, TransactionExecutionResult, TransactionResources,
@@ -37,14 +40,21 @@ macro_rules! impl_checked_sub {
 
 pub type HashMapWrapper = HashMap<BuiltinName, usize>;
 
+/// An upper bound on the height of a storage tree, used when it cannot be estimated.
+const TREE_HEIGHT_UPPER_BOUND: usize = 24;
+
+static DEFAULT_PATRICIA_COST_ESTIMATOR: Lazy<SharedPatriciaCostEstimator> =
+    Lazy::new(|| SharedPatriciaCostEstimator(Arc::new(ConstantPatriciaCostEstimator::default())));
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct BouncerConfig {
     pub block_max_capacity: BouncerWeights,
+    pub patricia_cost_estimator: SharedPatriciaCostEstimator,
 }
 
 impl BouncerConfig {
     pub fn max() -> Self {
-        Self { block_max_capacity: BouncerWeights::max() }
+        Self { block_max_capacity: BouncerWeights::max(), ..Default::default() }
     }
 
     pub fn empty() -> Self {
@@ -72,6 +82,7 @@ impl BouncerConfig {
 pub struct BouncerWeights {
     pub builtin_count: BuiltinCount,
     pub gas: usize,
+    pub l2_gas: usize,
     pub message_segment_length: usize,
     pub n_events: usize,
     pub n_steps: usize,
@@ -82,6 +93,7 @@ impl BouncerWeights {
     impl_checked_sub!(
         builtin_count,
         gas,
+        l2_gas,
         message_segment_length,
         n_events,
         n_steps,
@@ -95,6 +107,7 @@ impl BouncerWeights {
     pub fn max() -> Self {
         Self {
             gas: usize::MAX,
+            l2_gas: usize::MAX,
             n_steps: usize::MAX,
             message_segment_length: usize::MAX,
             state_diff_size: usize::MAX,
@@ -212,6 +225,59 @@ pub struct Bouncer {
     pub bouncer_config: BouncerConfig,
 
     accumulated_weights: BouncerWeights,
+    undo_log: BouncerUndoLog,
+}
+
+/// A checkpoint of a bouncer, to roll back to or release; see [Bouncer::checkpoint].
+#[derive(Debug)]
+#[must_use = "A checkpoint must be rolled back to or released."]
+pub struct BouncerCheckpoint {
+    depth: usize,
+    // Tells the checkpoint apart from the checkpoints opened at the same depth before or after it.
+    id: usize,
+}
+
+/// The additions of an update of a bouncer while a checkpoint is open; used to undo the update.
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(test, derive(Clone))]
+struct BouncerUndoEntry {
+    executed_class_hashes: Vec<ClassHash>,
+    visited_storage_entries: Vec<StorageEntry>,
+    state_changes_keys: StateChangesKeys,
+}
+
+/// Records the updates of a bouncer while a checkpoint is open.
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(test, derive(Clone))]
+struct BouncerUndoLog {
+    entries: Vec<BouncerUndoEntry>,
+    // The ID of each open checkpoint, the number of entries at its opening and the accumulated
+    // weights at its opening, outermost first.
+    checkpoints: Vec<(usize, usize, BouncerWeights)>,
+    n_opened_checkpoints: usize,
+}
+
+impl BouncerUndoLog {
+    fn open(&mut self, accumulated_weights: BouncerWeights) -> BouncerCheckpoint {
+        let checkpoint =
+            BouncerCheckpoint { depth: self.checkpoints.len(), id: self.n_opened_checkpoints };
+        self.checkpoints.push((checkpoint.id, self.entries.len(), accumulated_weights));
+        self.n_opened_checkpoints += 1;
+        checkpoint
+    }
+
+    fn is_open(&self, checkpoint: &BouncerCheckpoint) -> bool {
+        self.checkpoints.get(checkpoint.depth).is_some_and(|&(id, ..)| id == checkpoint.id)
+    }
+
+    /// Closes the given checkpoint, along with the checkpoints opened after it, and returns the
+    /// number of entries and the accumulated weights at its opening.
+    fn close(&mut self, checkpoint: BouncerCheckpoint) -> (usize, BouncerWeights) {
+        assert!(self.is_open(&checkpoint), "Checkpoint {} is already closed.", checkpoint.id);
+        let (_, n_entries, accumulated_weights) = self.checkpoints[checkpoint.depth];
+        self.checkpoints.truncate(checkpoint.depth);
+        (n_entries, accumulated_weights)
+    }
 }
 
 impl Bouncer {
@@ -223,14 +289,14 @@ impl Bouncer {
         &self.accumulated_weights
     }
 
-    /// Updates the bouncer with a new transaction.
+    /// Updates the bouncer with a new transaction, and returns its marginal weights.
     pub fn try_update<S: StateReader>(
         &mut self,
         state_reader: &S,
         tx_state_changes_keys: &StateChangesKeys,
         tx_execution_summary: &ExecutionSummary,
         tx_resources: &TransactionResources,
-    ) -> TransactionExecutorResult<()> {
+    ) -> TransactionExecutorResult<BouncerWeights> {
         // The countings here should be linear in the transactional state changes and execution info
         // rather than the cumulative state attributes.
         let marginal_state_changes_keys =
@@ -240,14 +306,16 @@ impl Bouncer {
             .difference(&self.executed_class_hashes)
             .cloned()
             .collect();
-        let n_marginal_visited_storage_entries = tx_execution_summary
+        let marginal_visited_storage_entries = tx_execution_summary
             .visited_storage_entries
             .difference(&self.visited_storage_entries)
-            .count();
+            .cloned()
+            .collect();
         let tx_weights = get_tx_weights(
             state_reader,
+            self.bouncer_config.patricia_cost_estimator.as_ref(),
             &marginal_executed_class_hashes,
-            n_marginal_visited_storage_entries,
+            &marginal_visited_storage_entries,
             tx_resources,
             &marginal_state_changes_keys,
         )?;
@@ -264,7 +332,7 @@ impl Bouncer {
 
         self.update(tx_weights, tx_execution_summary, &marginal_state_changes_keys);
 
-        Ok(())
+        Ok(tx_weights)
     }
 
     fn update(
@@ -274,11 +342,72 @@ impl Bouncer {
         state_changes_keys: &StateChangesKeys,
     ) {
         self.accumulated_weights += tx_weights;
-        self.visited_storage_entries.extend(&tx_execution_summary.visited_storage_entries);
-        self.executed_class_hashes.extend(&tx_execution_summary.executed_class_hashes);
-        // Note: cancelling writes (0 -> 1 -> 0) will not be removed, but it's fine since fee was
-        // charged for them.
-        self.state_changes_keys.extend(state_changes_keys);
+        if self.undo_log.checkpoints.is_empty() {
+            self.visited_storage_entries.extend(&tx_execution_summary.visited_storage_entries);
+            self.executed_class_hashes.extend(&tx_execution_summary.executed_class_hashes);
+            // Note: cancelling writes (0 -> 1 -> 0) will not be removed, but it's fine since fee
+            // was charged for them.
+            self.state_changes_keys.extend(state_changes_keys);
+            return;
+        }
+
+        // Record the additions, to undo them on a rollback.
+        let undo_entry = BouncerUndoEntry {
+            executed_class_hashes: tx_execution_summary
+                .executed_class_hashes
+                .iter()
+                .filter(|&&class_hash| self.executed_class_hashes.insert(class_hash))
+                .copied()
+                .collect(),
+            visited_storage_entries: tx_execution_summary
+                .visited_storage_entries
+                .iter()
+                .filter(|&&storage_entry| self.visited_storage_entries.insert(storage_entry))
+                .copied()
+                .collect(),
+            state_changes_keys: state_changes_keys.difference(&self.state_changes_keys),
+        };
+        self.state_changes_keys.extend(&undo_entry.state_changes_keys);
+        self.undo_log.entries.push(undo_entry);
+    }
+
+    /// Opens a checkpoint: the transactions added from now on may be removed by rolling back to
+    /// it. Checkpoints may be nested, and are closed (by a rollback or a release) innermost first;
+    /// closing a checkpoint also closes the checkpoints opened after it.
+    /// Note: while a checkpoint is open, the additions of each transaction are recorded; a rollback
+    /// takes time linear in the additions of the removed transactions.
+    pub fn checkpoint(&mut self) -> BouncerCheckpoint {
+        self.undo_log.open(self.accumulated_weights)
+    }
+
+    /// Returns whether the given checkpoint is open; i.e., it was opened on this bouncer, and was
+    /// not closed yet (directly, or along with an enclosing checkpoint).
+    pub fn is_checkpoint_open(&self, checkpoint: &BouncerCheckpoint) -> bool {
+        self.undo_log.is_open(checkpoint)
+    }
+
+    /// Removes the transactions added since the given checkpoint was opened, and closes it.
+    pub fn rollback(&mut self, checkpoint: BouncerCheckpoint) {
+        let (n_entries, accumulated_weights) = self.undo_log.close(checkpoint);
+        for undo_entry in self.undo_log.entries.drain(n_entries..) {
+            for class_hash in &undo_entry.executed_class_hashes {
+                self.executed_class_hashes.remove(class_hash);
+            }
+            for storage_entry in &undo_entry.visited_storage_entries {
+                self.visited_storage_entries.remove(storage_entry);
+            }
+            self.state_changes_keys.remove(&undo_entry.state_changes_keys);
+        }
+        self.accumulated_weights = accumulated_weights;
+    }
+
+    /// Closes the given checkpoint, keeping the transactions added since it was opened; they are
+    /// removed if an enclosing checkpoint is rolled back.
+    pub fn release_checkpoint(&mut self, checkpoint: BouncerCheckpoint) {
+        self.undo_log.close(checkpoint);
+        if self.undo_log.checkpoints.is_empty() {
+            self.undo_log.entries.clear();
+        }
     }
 
     #[cfg(test)]
@@ -289,8 +418,9 @@ impl Bouncer {
 
 pub fn get_tx_weights<S: StateReader>(
     state_reader: &S,
+    patricia_cost_estimator: &dyn PatriciaCostEstimator,
     executed_class_hashes: &HashSet<ClassHash>,
-    n_visited_storage_entries: usize,
+    visited_storage_entries: &HashSet<StorageEntry>,
     tx_resources: &TransactionResources,
     state_changes_keys: &StateChangesKeys,
 ) -> TransactionExecutionResult<BouncerWeights> {
@@ -299,12 +429,15 @@ pub fn get_tx_weights<S: StateReader>(
 
     let mut additional_os_resources =
         get_casm_hash_calculation_resources(state_reader, executed_class_hashes)?;
-    additional_os_resources += &get_particia_update_resources(n_visited_storage_entries);
+    additional_os_resources += &patricia_cost_estimator
+        .estimate_update_resources(state_reader, visited_storage_entries)?;
 
     let vm_resources = &additional_os_resources + &tx_resources.vm_resources;
 
     Ok(BouncerWeights {
         gas: gas_usage,
+        l2_gas: usize::try_from(tx_resources.sierra_gas)
+            .expect("The Sierra gas consumed by a transaction should fit in usize."),
         message_segment_length,
         n_events: tx_resources.starknet_resources.n_events,
         n_steps: vm_resources.total_n_steps(),
@@ -329,15 +462,124 @@ pub fn get_casm_hash_calculation_resources<S: StateReader>(
     Ok(casm_hash_computation_resources)
 }
 
-/// Returns the estimated Cairo resources for Patricia tree updates, or hash invocations
-/// (done by the OS), required for accessing (read/write) the given storage entries.
+/// Estimates the Cairo resources for Patricia tree updates, or hash invocations (done by the OS),
+/// required for accessing (read/write) the given storage entries.
 // For each tree: n_visited_leaves * log(n_initialized_leaves)
 // as the height of a Patricia tree with N uniformly distributed leaves is ~log(N),
 // and number of visited leaves includes reads and writes.
+pub trait PatriciaCostEstimator: Debug + Send + Sync {
+    fn estimate_update_resources(
+        &self,
+        state_reader: &dyn StateReader,
+        visited_storage_entries: &HashSet<StorageEntry>,
+    ) -> StateResult<ExecutionResources>;
+}
+
+/// A Patricia cost estimator shared between bouncer configurations; compared by identity.
+#[derive(Clone, Debug)]
+pub struct SharedPatriciaCostEstimator(pub Arc<dyn PatriciaCostEstimator>);
+
+impl SharedPatriciaCostEstimator {
+    pub fn new(patricia_cost_estimator: impl PatriciaCostEstimator + 'static) -> Self {
+        Self(Arc::new(patricia_cost_estimator))
+    }
+}
+
+impl AsRef<dyn PatriciaCostEstimator> for SharedPatriciaCostEstimator {
+    fn as_ref(&self) -> &(dyn PatriciaCostEstimator + 'static) {
+        self.0.as_ref()
+    }
+}
+
+impl Default for SharedPatriciaCostEstimator {
+    fn default() -> Self {
+        DEFAULT_PATRICIA_COST_ESTIMATOR.clone()
+    }
+}
+
+impl PartialEq for SharedPatriciaCostEstimator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Estimates all the storage trees to be of the same height.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConstantPatriciaCostEstimator {
+    pub tree_height: usize,
+}
+
+impl Default for ConstantPatriciaCostEstimator {
+    fn default() -> Self {
+        Self { tree_height: TREE_HEIGHT_UPPER_BOUND }
+    }
+}
+
+impl PatriciaCostEstimator for ConstantPatriciaCostEstimator {
+    fn estimate_update_resources(
+        &self,
+        _state_reader: &dyn StateReader,
+        visited_storage_entries: &HashSet<StorageEntry>,
+    ) -> StateResult<ExecutionResources> {
+        Ok(get_patricia_update_resources(visited_storage_entries.len() * self.tree_height))
+    }
+}
+
+/// Estimates the height of each storage tree by its number of leaves, as reported by the state
+/// reader; trees of unknown size are estimated to be of the default height.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LeafCountPatriciaCostEstimator {
+    pub default_tree_height: usize,
+}
+
+impl Default for LeafCountPatriciaCostEstimator {
+    fn default() -> Self {
+        Self { default_tree_height: TREE_HEIGHT_UPPER_BOUND }
+    }
+}
+
+impl PatriciaCostEstimator for LeafCountPatriciaCostEstimator {
+    fn estimate_update_resources(
+        &self,
+        state_reader: &dyn StateReader,
+        visited_storage_entries: &HashSet<StorageEntry>,
+    ) -> StateResult<ExecutionResources> {
+        let mut n_visited_leaves_by_contract: HashMap<ContractAddress, usize> = HashMap::new();
+        for (contract_address, _) in visited_storage_entries {
+            *n_visited_leaves_by_contract.entry(*contract_address).or_default() += 1;
+        }
+
+        let mut n_updates = 0;
+        for (contract_address, n_visited_leaves) in n_visited_leaves_by_contract {
+            let tree_height =
+                match state_reader.get_n_initialized_storage_leaves(contract_address)? {
+                    Some(n_initialized_leaves) => estimate_tree_height(n_initialized_leaves),
+                    None => self.default_tree_height,
+                };
+            n_updates += n_visited_leaves * tree_height;
+        }
+
+        Ok(get_patricia_update_resources(n_updates))
+    }
+}
+
+/// Returns the estimated height of a Patricia tree with the given number of uniformly distributed
+/// leaves: log(N), rounded up, and at least one.
+fn estimate_tree_height(n_leaves: usize) -> usize {
+    let height = usize::BITS - n_leaves.saturating_sub(1).leading_zeros();
+    usize::try_from(height).expect("Bit counts fit in usize.").max(1)
+}
+
+/// Returns the estimated Cairo resources of the Patricia tree updates of the given number of
+/// visited storage entries, assuming all the storage trees are of the maximal estimated height.
+#[deprecated(note = "Use a `PatriciaCostEstimator`, e.g., `ConstantPatriciaCostEstimator`.")]
 pub fn get_particia_update_resources(n_visited_storage_entries: usize) -> ExecutionResources {
-    const TREE_HEIGHT_UPPER_BOUND: usize = 24;
-    let n_updates = n_visited_storage_entries * TREE_HEIGHT_UPPER_BOUND;
+    get_patricia_update_resources(n_visited_storage_entries * TREE_HEIGHT_UPPER_BOUND)
+}
 
+/// Returns the Cairo resources of the given number of Patricia tree updates (one per accessed tree
+/// level).
+fn get_patricia_update_resources(n_updates: usize) -> ExecutionResources {
     ExecutionResources {
         // TODO(Yoni, 1/5/2024): re-estimate this.
         n_steps: 32 * n_updates,
@@ -356,8 +598,9 @@ pub fn verify_tx_weights_in_bounds<S: StateReader>(
 ) -> TransactionExecutionResult<()> {
     let tx_weights = get_tx_weights(
         state_reader,
+        bouncer_config.patricia_cost_estimator.as_ref(),
         &tx_execution_summary.executed_class_hashes,
-        tx_execution_summary.visited_storage_entries.len(),
+        &tx_execution_summary.visited_storage_entries,
         tx_resources,
         tx_state_changes_keys,
     )?;
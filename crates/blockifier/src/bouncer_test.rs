@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
 use cairo_vm::types::builtin_name::BuiltinName;
+use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use rstest::rstest;
 use starknet_api::core::{ClassHash, ContractAddress, PatriciaKey};
 use starknet_api::{class_hash, contract_address, felt, patricia_key};
@@ -9,11 +10,15 @@ use super::BouncerConfig;
 use crate::blockifier::transaction_executor::{
     TransactionExecutorError, TransactionExecutorResult,
 };
-use crate::bouncer::{verify_tx_weights_in_bounds, Bouncer, BouncerWeights, BuiltinCount};
+use crate::bouncer::{
+    estimate_tree_height, verify_tx_weights_in_bounds, Bouncer, BouncerWeights, BuiltinCount,
+    ConstantPatriciaCostEstimator, LeafCountPatriciaCostEstimator, PatriciaCostEstimator,
+};
 use crate::context::BlockContext;
 use crate::execution::call_info::ExecutionSummary;
 use crate::state::cached_state::{StateChangesKeys, TransactionalState};
 use crate::storage_key;
+use crate::test_utils::dict_state_reader::DictStateReader;
 use crate::test_utils::initial_test_state::test_state;
 use crate::transaction::errors::TransactionExecutionError;
 
@@ -33,6 +38,7 @@ fn test_block_weights_has_room() {
             range_check96: 10,
         },
         gas: 10,
+        l2_gas: 10,
         message_segment_length: 10,
         n_events: 10,
         n_steps: 10,
@@ -53,6 +59,7 @@ fn test_block_weights_has_room() {
             range_check96: 10,
         },
         gas: 7,
+        l2_gas: 7,
         message_segment_length: 10,
         n_steps: 0,
         n_events: 2,
@@ -75,6 +82,7 @@ fn test_block_weights_has_room() {
             range_check96: 5,
         },
         gas: 5,
+        l2_gas: 5,
         message_segment_length: 5,
         n_steps: 5,
         n_events: 5,
@@ -82,6 +90,9 @@ fn test_block_weights_has_room() {
     };
 
     assert!(!max_bouncer_weights.has_room(bouncer_weights_exceeds_max));
+
+    let l2_gas_exceeds_max = BouncerWeights { l2_gas: 11, ..Default::default() };
+    assert!(!max_bouncer_weights.has_room(l2_gas_exceeds_max));
 }
 
 #[rstest]
@@ -110,11 +121,13 @@ fn test_block_weights_has_room() {
             range_check96: 10,
         },
         gas: 10,
+        l2_gas: 10,
         message_segment_length: 10,
         n_steps: 10,
         n_events: 10,
         state_diff_size: 10,
     },
+    undo_log: Default::default(),
 })]
 fn test_bouncer_update(#[case] initial_bouncer: Bouncer) {
     let execution_summary_to_update = ExecutionSummary {
@@ -140,6 +153,7 @@ fn test_bouncer_update(#[case] initial_bouncer: Bouncer) {
             range_check96: 0,
         },
         gas: 9,
+        l2_gas: 9,
         message_segment_length: 10,
         n_steps: 0,
         n_events: 1,
@@ -169,6 +183,63 @@ fn test_bouncer_update(#[case] initial_bouncer: Bouncer) {
     assert_eq!(updated_bouncer, expected_bouncer);
 }
 
+#[test]
+fn test_bouncer_checkpoint_and_rollback() {
+    let mut bouncer = Bouncer::new(BouncerConfig::max());
+    let add_tx = |bouncer: &mut Bouncer, index: u128| {
+        let execution_summary = ExecutionSummary {
+            executed_class_hashes: HashSet::from([class_hash!(index)]),
+            visited_storage_entries: HashSet::from([(
+                ContractAddress::from(index),
+                storage_key!(index),
+            )]),
+            ..Default::default()
+        };
+        let state_changes_keys =
+            StateChangesKeys::create_for_testing(HashSet::from([ContractAddress::from(index)]));
+        let weights = BouncerWeights { n_events: 1, ..Default::default() };
+        bouncer.update(weights, &execution_summary, &state_changes_keys);
+    };
+
+    let assert_bouncer_eq = |bouncer: &Bouncer, expected_bouncer: &Bouncer| {
+        assert_eq!(bouncer.executed_class_hashes, expected_bouncer.executed_class_hashes);
+        assert_eq!(bouncer.visited_storage_entries, expected_bouncer.visited_storage_entries);
+        assert_eq!(bouncer.state_changes_keys, expected_bouncer.state_changes_keys);
+        assert_eq!(bouncer.get_accumulated_weights(), expected_bouncer.get_accumulated_weights());
+    };
+
+    add_tx(&mut bouncer, 1);
+    let bouncer_at_checkpoint = bouncer.clone();
+    let checkpoint = bouncer.checkpoint();
+
+    add_tx(&mut bouncer, 2);
+    // The additions made before the checkpoint are kept on a rollback.
+    add_tx(&mut bouncer, 1);
+    let inner_checkpoint = bouncer.checkpoint();
+    add_tx(&mut bouncer, 3);
+    bouncer.release_checkpoint(inner_checkpoint);
+    assert_eq!(bouncer.get_accumulated_weights().n_events, 4);
+
+    // The released checkpoint's additions are removed with the enclosing checkpoint.
+    bouncer.rollback(checkpoint);
+    assert_bouncer_eq(&bouncer, &bouncer_at_checkpoint);
+    assert!(bouncer.undo_log.entries.is_empty());
+
+    // Rolling back to a checkpoint closes the checkpoints opened after it.
+    let checkpoint = bouncer.checkpoint();
+    add_tx(&mut bouncer, 4);
+    let inner_checkpoint = bouncer.checkpoint();
+    add_tx(&mut bouncer, 5);
+    bouncer.rollback(checkpoint);
+    assert_bouncer_eq(&bouncer, &bouncer_at_checkpoint);
+    let new_checkpoint = bouncer.checkpoint();
+    let new_inner_checkpoint = bouncer.checkpoint();
+    assert!(!bouncer.is_checkpoint_open(&inner_checkpoint));
+    assert!(bouncer.is_checkpoint_open(&new_inner_checkpoint));
+    bouncer.release_checkpoint(new_checkpoint);
+    assert!(!bouncer.is_checkpoint_open(&new_inner_checkpoint));
+}
+
 #[rstest]
 #[case::positive_flow(1, Ok(()))]
 #[case::block_full(11, Err(TransactionExecutorError::BlockFull))]
@@ -182,8 +253,6 @@ fn test_bouncer_try_update(
     #[case] added_ecdsa: usize,
     #[case] expected_result: TransactionExecutorResult<()>,
 ) {
-    use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
-
     use crate::transaction::objects::TransactionResources;
 
     let state = &mut test_state(&BlockContext::create_for_account_testing().chain_info, 0, &[]);
@@ -204,12 +273,13 @@ fn test_bouncer_try_update(
             range_check96: 20,
         },
         gas: 20,
+        l2_gas: 20,
         message_segment_length: 20,
         n_steps: 20,
         n_events: 20,
         state_diff_size: 20,
     };
-    let bouncer_config = BouncerConfig { block_max_capacity };
+    let bouncer_config = BouncerConfig { block_max_capacity, ..Default::default() };
 
     let accumulated_weights = BouncerWeights {
         builtin_count: BuiltinCount {
@@ -225,6 +295,7 @@ fn test_bouncer_try_update(
             range_check96: 10,
         },
         gas: 10,
+        l2_gas: 10,
         message_segment_length: 10,
         n_steps: 10,
         n_events: 10,
@@ -267,14 +338,70 @@ fn test_bouncer_try_update(
 
     if result.is_ok() {
         // Try to update the bouncer.
-        result = bouncer.try_update(
+        let weights_before_update = *bouncer.get_accumulated_weights();
+        let update_result = bouncer.try_update(
             &transactional_state,
             &tx_state_changes_keys,
             &execution_summary,
             &tx_resources,
         );
+        result = update_result.map(|tx_weights| {
+            // The returned weights are the marginal weights of the transaction.
+            assert_eq!(*bouncer.get_accumulated_weights(), weights_before_update + tx_weights);
+        });
     }
 
     // TODO(yael 27/3/24): compare the results without using string comparison.
     assert_eq!(format!("{:?}", result), format!("{:?}", expected_result));
 }
+
+#[rstest]
+#[case(0, 1)]
+#[case(1, 1)]
+#[case(2, 1)]
+#[case(3, 2)]
+#[case(8, 3)]
+#[case(1000, 10)]
+fn test_estimate_tree_height(#[case] n_leaves: usize, #[case] expected_tree_height: usize) {
+    assert_eq!(estimate_tree_height(n_leaves), expected_tree_height);
+}
+
+#[test]
+fn test_patricia_cost_estimators() {
+    let (large_contract, empty_contract) = (contract_address!(1_u128), contract_address!(2_u128));
+    let state_reader = DictStateReader {
+        storage_view: (1..=8_u128)
+            .map(|key| ((large_contract, storage_key!(key)), felt!(key)))
+            .chain([((large_contract, storage_key!(9_u128)), felt!(0_u8))])
+            .collect(),
+        ..Default::default()
+    };
+    let visited_storage_entries = HashSet::from([
+        (large_contract, storage_key!(1_u128)),
+        (large_contract, storage_key!(100_u128)),
+        (empty_contract, storage_key!(1_u128)),
+    ]);
+    let update_resources = |n_updates: usize| ExecutionResources {
+        n_steps: 32 * n_updates,
+        n_memory_holes: 0,
+        builtin_instance_counter: HashMap::from([(BuiltinName::pedersen, 2 * n_updates)]),
+    };
+
+    assert_eq!(
+        ConstantPatriciaCostEstimator::default()
+            .estimate_update_resources(&state_reader, &visited_storage_entries)
+            .unwrap(),
+        update_resources(3 * 24)
+    );
+    #[allow(deprecated)]
+    let deprecated_update_resources =
+        crate::bouncer::get_particia_update_resources(visited_storage_entries.len());
+    assert_eq!(deprecated_update_resources, update_resources(3 * 24));
+    // The storage tree of the large contract is of height 3, and the empty one's is of height 1.
+    assert_eq!(
+        LeafCountPatriciaCostEstimator::default()
+            .estimate_update_resources(&state_reader, &visited_storage_entries)
+            .unwrap(),
+        update_resources(2 * 3 + 1)
+    );
+}
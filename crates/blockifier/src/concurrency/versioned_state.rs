@@ -10,7 +10,7 @@ use crate::concurrency::TxIndex;
 use crate::execution::contract_class::ContractClass;
 use crate::state::cached_state::{ContractClassMapping, StateMaps};
 use crate::state::errors::StateError;
-use crate::state::state_api::{StateReader, StateResult, UpdatableState};
+use crate::state::state_api::{verify_bulk_read_length, StateReader, StateResult, UpdatableState};
 
 #[cfg(test)]
 #[path = "versioned_state_test.rs"]
@@ -361,4 +361,62 @@ impl<S: StateReader> StateReader for VersionedStateProxy<S> {
             },
         }
     }
+
+    fn get_n_initialized_storage_leaves(
+        &self,
+        contract_address: ContractAddress,
+    ) -> StateResult<Option<usize>> {
+        self.state().initial_state.get_n_initialized_storage_leaves(contract_address)
+    }
+
+    fn get_storage_many(
+        &self,
+        storage_keys: &[(ContractAddress, StorageKey)],
+    ) -> StateResult<Vec<Felt>> {
+        let mut state = self.state();
+        let missing_keys: Vec<(ContractAddress, StorageKey)> = storage_keys
+            .iter()
+            .filter(|&&storage_key| state.storage.read(self.tx_index, storage_key).is_none())
+            .copied()
+            .collect();
+        if !missing_keys.is_empty() {
+            let initial_values = state.initial_state.get_storage_many(&missing_keys)?;
+            verify_bulk_read_length(missing_keys.len(), initial_values.len())?;
+            for (storage_key, initial_value) in missing_keys.into_iter().zip(initial_values) {
+                state.storage.set_initial_value(storage_key, initial_value);
+            }
+        }
+
+        Ok(storage_keys
+            .iter()
+            .map(|&storage_key| state.storage.read(self.tx_index, storage_key).expect(READ_ERR))
+            .collect())
+    }
+
+    fn get_nonces_many(&self, contract_addresses: &[ContractAddress]) -> StateResult<Vec<Nonce>> {
+        let mut state = self.state();
+        let missing_addresses: Vec<ContractAddress> = contract_addresses
+            .iter()
+            .filter(|&&contract_address| {
+                state.nonces.read(self.tx_index, contract_address).is_none()
+            })
+            .copied()
+            .collect();
+        if !missing_addresses.is_empty() {
+            let initial_values = state.initial_state.get_nonces_many(&missing_addresses)?;
+            verify_bulk_read_length(missing_addresses.len(), initial_values.len())?;
+            for (contract_address, initial_value) in
+                missing_addresses.into_iter().zip(initial_values)
+            {
+                state.nonces.set_initial_value(contract_address, initial_value);
+            }
+        }
+
+        Ok(contract_addresses
+            .iter()
+            .map(|&contract_address| {
+                state.nonces.read(self.tx_index, contract_address).expect(READ_ERR)
+            })
+            .collect())
+    }
 }
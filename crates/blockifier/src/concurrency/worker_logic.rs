@@ -242,13 +242,12 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
                 &tx_execution_info.summarize(),
                 &tx_execution_info.transaction_receipt.resources,
             );
-            if let Err(error) = bouncer_result {
-                match error {
-                    TransactionExecutorError::BlockFull => return false,
-                    _ => {
-                        // TODO(Avi, 01/07/2024): Consider propagating the error.
-                        panic!("Bouncer update failed. {error:?}: {error}");
-                    }
+            match bouncer_result {
+                Ok(tx_weights) => tx_execution_info.bouncer_weights = Some(tx_weights),
+                Err(TransactionExecutorError::BlockFull) => return false,
+                Err(error) => {
+                    // TODO(Avi, 01/07/2024): Consider propagating the error.
+                    panic!("Bouncer update failed. {error:?}: {error}");
                 }
             }
             complete_fee_transfer_flow(&tx_context, tx_execution_info, &mut tx_versioned_state);
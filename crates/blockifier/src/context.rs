@@ -3,7 +3,7 @@ use starknet_api::core::{ChainId, ContractAddress};
 use crate::blockifier::block::BlockInfo;
 use crate::bouncer::BouncerConfig;
 use crate::transaction::objects::{
-    FeeType, HasRelatedFeeType, TransactionInfo, TransactionInfoCreator,
+    FeeType, HasRelatedFeeType, TransactionFeeResult, TransactionInfo, TransactionInfoCreator,
 };
 use crate::versioned_constants::VersionedConstants;
 
@@ -21,6 +21,21 @@ impl TransactionContext {
     pub fn is_sequencer_the_sender(&self) -> bool {
         self.tx_info.sender_address() == self.block_context.block_info.sequencer_address
     }
+
+    /// Returns the initial gas of the transaction to run with; if L2 gas is enabled, the initial
+    /// gas of a transaction with enforced resource bounds is capped by its L2 gas bound.
+    pub fn initial_gas(&self) -> TransactionFeeResult<u64> {
+        let versioned_constants = &self.block_context.versioned_constants;
+        let tx_initial_gas = versioned_constants.tx_initial_gas();
+        match &self.tx_info {
+            TransactionInfo::Current(context)
+                if versioned_constants.enable_l2_gas && self.tx_info.enforce_fee()? =>
+            {
+                Ok(tx_initial_gas.min(context.l2_resource_bounds().max_amount))
+            }
+            _ => Ok(tx_initial_gas),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -1,4 +1,5 @@
 pub mod call_info;
+pub mod class_hash;
 pub mod common_hints;
 pub mod contract_address;
 pub mod contract_class;
@@ -9,5 +10,6 @@ pub mod entry_point_execution;
 pub mod errors;
 pub mod execution_utils;
 pub mod hint_code;
+pub mod panic_data;
 pub mod stack_trace;
 pub mod syscalls;
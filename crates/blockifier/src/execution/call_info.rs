@@ -161,12 +161,24 @@ impl TestExecutionSummary {
     }
 }
 
+/// The resource by which the execution of a call is charged.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub enum TrackedResource {
+    /// The VM resources of the call are charged as L1 gas.
+    #[default]
+    CairoSteps,
+    /// The Sierra gas consumed by the call is charged as L2 gas; applies to Cairo 1 calls, if L2
+    /// gas is enabled.
+    SierraGas,
+}
+
 /// Represents the full effects of executing an entry point, including the inner calls it invoked.
 #[derive(Debug, Default, Eq, PartialEq, Serialize)]
 pub struct CallInfo {
     pub call: CallEntryPoint,
     pub execution: CallExecution,
     pub resources: ExecutionResources,
+    pub tracked_resource: TrackedResource,
     pub inner_calls: Vec<CallInfo>,
 
     // Additional information gathered during execution.
@@ -217,6 +229,48 @@ impl CallInfo {
             n_events,
         }
     }
+
+    /// Returns the Sierra gas consumed by the given calls and their inner calls that are charged by
+    /// Sierra gas, along with the VM resources of these calls, which the gas covers (the resources
+    /// of their inner calls charged by Cairo steps are excluded).
+    pub fn summarize_sierra_gas_usage<'a>(
+        call_infos: impl Iterator<Item = &'a CallInfo>,
+    ) -> (u64, ExecutionResources) {
+        let mut sierra_gas = 0;
+        let mut vm_resources = ExecutionResources::default();
+        for call_info in call_infos.flat_map(|call_info| call_info.iter()) {
+            if call_info.tracked_resource != TrackedResource::SierraGas {
+                continue;
+            }
+
+            // The gas consumed and the resources of a call include those of its inner calls.
+            let mut call_gas = call_info.execution.gas_consumed;
+            let mut call_resources = call_info.resources.clone();
+            for inner_call in &call_info.inner_calls {
+                call_gas = call_gas.saturating_sub(inner_call.execution.gas_consumed);
+                saturating_sub_resources(&mut call_resources, &inner_call.resources);
+            }
+            sierra_gas += call_gas;
+            vm_resources += &call_resources;
+        }
+
+        (sierra_gas, vm_resources)
+    }
+}
+
+/// Subtracts the given resources from the first ones, saturating at zero; the resources of a failed
+/// call do not necessarily include those of its failed inner calls.
+pub(crate) fn saturating_sub_resources(
+    resources: &mut ExecutionResources,
+    other: &ExecutionResources,
+) {
+    resources.n_steps = resources.n_steps.saturating_sub(other.n_steps);
+    resources.n_memory_holes = resources.n_memory_holes.saturating_sub(other.n_memory_holes);
+    for (builtin, count) in &other.builtin_instance_counter {
+        if let Some(resource_count) = resources.builtin_instance_counter.get_mut(builtin) {
+            *resource_count = resource_count.saturating_sub(*count);
+        }
+    }
 }
 
 pub struct CallInfoIter<'a> {
@@ -0,0 +1,312 @@
+use cairo_lang_starknet_classes::NestedIntList;
+use cairo_vm::types::relocatable::MaybeRelocatable;
+use serde_json::Value;
+use starknet_api::core::{ClassHash, CompiledClassHash};
+use starknet_api::deprecated_contract_class::{
+    ContractClass as DeprecatedContractClass, EntryPointType,
+};
+use starknet_api::state::{
+    ContractClass as SierraContractClass, EntryPointType as SierraEntryPointType,
+};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+
+use crate::abi::abi_utils::starknet_keccak;
+use crate::execution::contract_class::{ContractClassResult, ContractClassV1};
+use crate::execution::errors::ContractClassError;
+use crate::utils::u128_from_usize;
+
+#[cfg(test)]
+#[path = "class_hash_test.rs"]
+pub mod test;
+
+const COMPILED_CLASS_VERSION: &[u8] = b"COMPILED_CLASS_V1";
+const SIERRA_CLASS_VERSION: &[u8] = b"CONTRACT_CLASS_V0.1.0";
+const DEPRECATED_CLASS_API_VERSION: Felt = Felt::ZERO;
+/// The order in which the entry points of each type are hashed.
+const ENTRY_POINT_TYPES: [EntryPointType; 3] =
+    [EntryPointType::External, EntryPointType::L1Handler, EntryPointType::Constructor];
+const SIERRA_ENTRY_POINT_TYPES: [SierraEntryPointType; 3] = [
+    SierraEntryPointType::External,
+    SierraEntryPointType::L1Handler,
+    SierraEntryPointType::Constructor,
+];
+
+/// A contract class, as it was declared (i.e., before compilation, for Cairo 1 classes); the class
+/// hash is defined over it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DeclaredClass {
+    V0(Box<DeprecatedContractClass>),
+    V1(SierraContractClass),
+}
+
+impl DeclaredClass {
+    pub fn class_hash(&self) -> ClassHash {
+        match self {
+            DeclaredClass::V0(class) => compute_deprecated_class_hash(class),
+            DeclaredClass::V1(class) => compute_sierra_class_hash(class),
+        }
+    }
+
+    /// Returns the Cairo version of the class.
+    pub fn cairo_version(&self) -> u64 {
+        match self {
+            DeclaredClass::V0(_) => 0,
+            DeclaredClass::V1(_) => 1,
+        }
+    }
+}
+
+/// Computes the hash of a Sierra class.
+pub fn compute_sierra_class_hash(contract_class: &SierraContractClass) -> ClassHash {
+    let mut elements = vec![Felt::from_bytes_be_slice(SIERRA_CLASS_VERSION)];
+    elements.extend(SIERRA_ENTRY_POINT_TYPES.iter().map(|entry_point_type| {
+        let entry_points = contract_class
+            .entry_points_by_type
+            .get(entry_point_type)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let flattened_entry_points: Vec<Felt> = entry_points
+            .iter()
+            .flat_map(|entry_point| {
+                [entry_point.selector.0, Felt::from(u128_from_usize(entry_point.function_idx.0))]
+            })
+            .collect();
+        Poseidon::hash_array(&flattened_entry_points)
+    }));
+    elements.push(starknet_keccak(contract_class.abi.as_bytes()));
+    elements.push(Poseidon::hash_array(&contract_class.sierra_program));
+
+    ClassHash(Poseidon::hash_array(&elements))
+}
+
+/// Computes the hash of a compiled (Casm) class; the bytecode is hashed according to its
+/// segmentation.
+pub fn compute_compiled_class_hash(
+    contract_class: &ContractClassV1,
+) -> ContractClassResult<CompiledClassHash> {
+    let mut elements = vec![Felt::from_bytes_be_slice(COMPILED_CLASS_VERSION)];
+    elements.extend(ENTRY_POINT_TYPES.iter().map(|entry_point_type| {
+        let entry_points = contract_class
+            .entry_points_by_type
+            .get(entry_point_type)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let flattened_entry_points: Vec<Felt> = entry_points
+            .iter()
+            .flat_map(|entry_point| {
+                let builtins: Vec<Felt> = entry_point
+                    .builtins
+                    .iter()
+                    .map(|builtin| {
+                        let builtin_name =
+                            builtin.strip_suffix("_builtin").unwrap_or(builtin.as_str());
+                        Felt::from_bytes_be_slice(builtin_name.as_bytes())
+                    })
+                    .collect();
+                [
+                    entry_point.selector.0,
+                    Felt::from(u128_from_usize(entry_point.offset.0)),
+                    Poseidon::hash_array(&builtins),
+                ]
+            })
+            .collect();
+        Poseidon::hash_array(&flattened_entry_points)
+    }));
+
+    let bytecode: Vec<Felt> = contract_class
+        .program
+        .iter_data()
+        .map(|value| match value {
+            MaybeRelocatable::Int(value) => *value,
+            MaybeRelocatable::RelocatableValue(_) => {
+                unreachable!("Casm bytecode consists of field elements.")
+            }
+        })
+        .collect();
+    let (bytecode_length, bytecode_hash) =
+        bytecode_segment_hash(&bytecode, contract_class.bytecode_segment_lengths())?;
+    if bytecode_length != bytecode.len() {
+        return Err(ContractClassError::InvalidBytecodeSegmentLengths);
+    }
+    elements.push(bytecode_hash);
+
+    Ok(CompiledClassHash(Poseidon::hash_array(&elements)))
+}
+
+/// Hashes the given segment of the bytecode, which starts at the beginning of the given data.
+/// Returns the length of the segment and its hash: a leaf segment is hashed as is, and an inner
+/// segment is hashed over the (length, hash) pairs of its sub-segments, plus one.
+fn bytecode_segment_hash(
+    bytecode: &[Felt],
+    segment_lengths: &NestedIntList,
+) -> ContractClassResult<(usize, Felt)> {
+    match segment_lengths {
+        NestedIntList::Leaf(length) => {
+            let segment =
+                bytecode.get(..*length).ok_or(ContractClassError::InvalidBytecodeSegmentLengths)?;
+            Ok((*length, Poseidon::hash_array(segment)))
+        }
+        NestedIntList::Node(segments) => {
+            let mut segment_start = 0;
+            let mut elements = Vec::new();
+            for segment in segments {
+                let remaining_bytecode = bytecode
+                    .get(segment_start..)
+                    .ok_or(ContractClassError::InvalidBytecodeSegmentLengths)?;
+                let (segment_length, segment_hash) =
+                    bytecode_segment_hash(remaining_bytecode, segment)?;
+                elements.extend([Felt::from(u128_from_usize(segment_length)), segment_hash]);
+                segment_start += segment_length;
+            }
+            Ok((segment_start, Poseidon::hash_array(&elements) + Felt::ONE))
+        }
+    }
+}
+
+/// Computes the hash of a Cairo 0 class.
+/// Note: the hash of classes compiled before the compiler version was part of the program (i.e.,
+/// before Cairo 0.10) relies on formatting quirks that are not reproduced here.
+pub fn compute_deprecated_class_hash(contract_class: &DeprecatedContractClass) -> ClassHash {
+    let mut elements = vec![DEPRECATED_CLASS_API_VERSION];
+    elements.extend(ENTRY_POINT_TYPES.iter().map(|entry_point_type| {
+        let entry_points = contract_class
+            .entry_points_by_type
+            .get(entry_point_type)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let flattened_entry_points: Vec<Felt> = entry_points
+            .iter()
+            .flat_map(|entry_point| {
+                [entry_point.selector.0, Felt::from(u128_from_usize(entry_point.offset.0))]
+            })
+            .collect();
+        Pedersen::hash_array(&flattened_entry_points)
+    }));
+
+    let program = &contract_class.program;
+    let builtins: Vec<Felt> = json_array(&program.builtins)
+        .iter()
+        .map(|builtin| Felt::from_bytes_be_slice(json_str(builtin).as_bytes()))
+        .collect();
+    elements.push(Pedersen::hash_array(&builtins));
+    elements.push(compute_hinted_class_hash(contract_class));
+    let data: Vec<Felt> = json_array(&program.data)
+        .iter()
+        .map(|value| {
+            Felt::from_hex(json_str(value)).expect("Program data should consist of hex felts.")
+        })
+        .collect();
+    elements.push(Pedersen::hash_array(&data));
+
+    ClassHash(Pedersen::hash_array(&elements))
+}
+
+/// Returns the hash of the ABI and the program of the given Cairo 0 class (without its debug
+/// info), serialized as the Python `json.dumps(..., sort_keys=True)` does.
+fn compute_hinted_class_hash(contract_class: &DeprecatedContractClass) -> Felt {
+    let mut program = serde_json::to_value(&contract_class.program)
+        .expect("A deprecated program should be serializable.");
+    let program_object = program.as_object_mut().expect("A program should be a JSON object.");
+    program_object.insert("debug_info".to_string(), Value::Null);
+    if program_object.get("compiler_version").is_some_and(Value::is_null) {
+        program_object.remove("compiler_version");
+    }
+    // For backward compatibility, empty attributes and empty attribute fields are not hashed.
+    let has_attributes = program_object
+        .get("attributes")
+        .and_then(Value::as_array)
+        .is_some_and(|attributes| !attributes.is_empty());
+    if !has_attributes {
+        program_object.remove("attributes");
+    } else if let Some(Value::Array(attributes)) = program_object.get_mut("attributes") {
+        for attribute in attributes.iter_mut().filter_map(Value::as_object_mut) {
+            let has_accessible_scopes = attribute
+                .get("accessible_scopes")
+                .and_then(Value::as_array)
+                .is_some_and(|accessible_scopes| !accessible_scopes.is_empty());
+            if !has_accessible_scopes {
+                attribute.remove("accessible_scopes");
+            }
+            if attribute.get("flow_tracking_data").is_some_and(Value::is_null) {
+                attribute.remove("flow_tracking_data");
+            }
+        }
+    }
+
+    let abi = serde_json::to_value(&contract_class.abi)
+        .expect("A deprecated contract class ABI should be serializable.");
+    let hashed_object = Value::Object(
+        [("abi".to_string(), abi), ("program".to_string(), program)].into_iter().collect(),
+    );
+    let mut serialized = String::new();
+    write_python_json(&hashed_object, &mut serialized);
+
+    starknet_keccak(serialized.as_bytes())
+}
+
+/// Writes the given value as the Python `json.dumps(value, sort_keys=True)` does: with spaces
+/// after separators, sorted keys and non-ASCII characters escaped.
+fn write_python_json(value: &Value, output: &mut String) {
+    match value {
+        Value::Null => output.push_str("null"),
+        Value::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+        Value::Number(value) => output.push_str(&value.to_string()),
+        Value::String(value) => write_python_json_string(value, output),
+        Value::Array(values) => {
+            output.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    output.push_str(", ");
+                }
+                write_python_json(value, output);
+            }
+            output.push(']');
+        }
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            output.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    output.push_str(", ");
+                }
+                write_python_json_string(key, output);
+                output.push_str(": ");
+                write_python_json(value, output);
+            }
+            output.push('}');
+        }
+    }
+}
+
+fn write_python_json_string(string: &str, output: &mut String) {
+    output.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\u{8}' => output.push_str("\\b"),
+            '\u{c}' => output.push_str("\\f"),
+            ' '..='~' => output.push(c),
+            _ => {
+                let mut utf16_units = [0_u16; 2];
+                for unit in c.encode_utf16(&mut utf16_units) {
+                    output.push_str(&format!("\\u{unit:04x}"));
+                }
+            }
+        }
+    }
+    output.push('"');
+}
+
+fn json_array(value: &Value) -> &[Value] {
+    value.as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+fn json_str(value: &Value) -> &str {
+    value.as_str().expect("Expected a JSON string.")
+}
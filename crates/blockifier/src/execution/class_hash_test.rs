@@ -0,0 +1,160 @@
+use assert_matches::assert_matches;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::NestedIntList;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use serde_json::json;
+use starknet_api::core::ClassHash;
+use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
+use starknet_api::state::ContractClass as SierraContractClass;
+use starknet_api::transaction::TransactionVersion;
+use starknet_api::{class_hash, felt};
+
+use crate::declare_tx_args;
+use crate::execution::class_hash::{
+    compute_compiled_class_hash, compute_deprecated_class_hash, compute_sierra_class_hash,
+    write_python_json, DeclaredClass,
+};
+use crate::execution::contract_class::{ContractClass, ContractClassV1};
+use crate::execution::errors::ContractClassError;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::declare::declare_tx;
+use crate::test_utils::{get_raw_contract_class, CairoVersion};
+use crate::transaction::account_transaction::AccountTransaction;
+use crate::transaction::errors::TransactionExecutionError;
+use crate::transaction::test_utils::calculate_class_info_for_testing;
+
+#[rstest]
+fn test_compiled_class_hash(
+    #[values(
+        FeatureContract::Empty(CairoVersion::Cairo1),
+        FeatureContract::LegacyTestContract,
+        FeatureContract::TestContract(CairoVersion::Cairo1)
+    )]
+    contract: FeatureContract,
+) {
+    let casm_contract_class: CasmContractClass =
+        serde_json::from_str(&contract.get_raw_class()).unwrap();
+    let ContractClass::V1(contract_class) = contract.get_class() else {
+        panic!("Expected a Cairo 1 contract class.");
+    };
+
+    // Compare against the reference implementation.
+    assert_eq!(
+        compute_compiled_class_hash(&contract_class).unwrap().0,
+        casm_contract_class.compiled_class_hash()
+    );
+}
+
+#[rstest]
+#[case::longer_than_bytecode(|length| NestedIntList::Leaf(length + 1))]
+#[case::shorter_than_bytecode(|length| NestedIntList::Leaf(length - 1))]
+#[case::nested_longer_than_bytecode(
+    |length| NestedIntList::Node(vec![NestedIntList::Leaf(length), NestedIntList::Leaf(1)])
+)]
+fn test_compiled_class_hash_invalid_segment_lengths(
+    #[case] segment_lengths: fn(usize) -> NestedIntList,
+) {
+    let mut casm_contract_class: CasmContractClass =
+        serde_json::from_str(&FeatureContract::TestContract(CairoVersion::Cairo1).get_raw_class())
+            .unwrap();
+    casm_contract_class.bytecode_segment_lengths =
+        Some(segment_lengths(casm_contract_class.bytecode.len()));
+    let contract_class = ContractClassV1::try_from(casm_contract_class).unwrap();
+
+    assert_matches!(
+        compute_compiled_class_hash(&contract_class).unwrap_err(),
+        ContractClassError::InvalidBytecodeSegmentLengths
+    );
+}
+
+#[test]
+fn test_sierra_class_hash_known_answer() {
+    let raw_class = get_raw_contract_class("resources/test_data/sierra_class.json");
+    let contract_class: SierraContractClass = serde_json::from_str(&raw_class).unwrap();
+
+    assert_eq!(
+        compute_sierra_class_hash(&contract_class),
+        class_hash!("0x29927c8af6bccf3f6fda035981e765a7bdbf18a2dc0d630494f8758aa908e2b")
+    );
+}
+
+#[test]
+fn test_deprecated_class_hash_known_answer() {
+    // A class compiled by Cairo 0.11.1.1.
+    let raw_class = get_raw_contract_class("resources/test_data/deprecated_class.json");
+    let contract_class: DeprecatedContractClass = serde_json::from_str(&raw_class).unwrap();
+
+    assert_eq!(
+        compute_deprecated_class_hash(&contract_class),
+        class_hash!("0x7b283352c15a3bed42aed999bfa6653da69fdaa711e93812b2af150eee99a0b")
+    );
+}
+
+#[test]
+fn test_deprecated_class_hash() {
+    let raw_class = FeatureContract::TestContract(CairoVersion::Cairo0).get_raw_class();
+    let contract_class: DeprecatedContractClass = serde_json::from_str(&raw_class).unwrap();
+    let class_hash = compute_deprecated_class_hash(&contract_class);
+
+    // The debug info is not part of the hash.
+    let mut with_other_debug_info = contract_class.clone();
+    with_other_debug_info.program.debug_info = json!({"file_contents": {}});
+    assert_eq!(compute_deprecated_class_hash(&with_other_debug_info), class_hash);
+
+    // The ABI is.
+    let mut without_abi = contract_class;
+    without_abi.abi = None;
+    assert_ne!(compute_deprecated_class_hash(&without_abi), class_hash);
+}
+
+#[test]
+fn test_write_python_json() {
+    let value = json!({"b": [1, "\u{e9}\n\u{1f600}"], "a": null, "c": {}});
+    let mut serialized = String::new();
+    write_python_json(&value, &mut serialized);
+
+    assert_eq!(serialized, r#"{"a": null, "b": [1, "\u00e9\n\ud83d\ude00"], "c": {}}"#);
+}
+
+#[rstest]
+fn test_verify_class_hashes(
+    #[values(TransactionVersion::TWO, TransactionVersion::THREE)] version: TransactionVersion,
+) {
+    let empty_contract = FeatureContract::Empty(CairoVersion::Cairo1);
+    let class_info = calculate_class_info_for_testing(empty_contract.get_class());
+    let ContractClass::V1(contract_class) = empty_contract.get_class() else {
+        panic!("Expected a Cairo 1 contract class.");
+    };
+    let sierra_class = SierraContractClass { abi: "[]".to_string(), ..Default::default() };
+    let declared_class = DeclaredClass::V1(sierra_class.clone());
+    let class_hash = compute_sierra_class_hash(&sierra_class);
+    let compiled_class_hash = compute_compiled_class_hash(&contract_class).unwrap();
+    let create_declare_tx = |class_hash, compiled_class_hash| {
+        let AccountTransaction::Declare(tx) = declare_tx(
+            declare_tx_args! { version, class_hash, compiled_class_hash },
+            class_info.clone(),
+        ) else {
+            panic!("Expected a declare transaction.");
+        };
+        tx
+    };
+
+    create_declare_tx(class_hash, compiled_class_hash)
+        .verify_class_hashes(&declared_class)
+        .unwrap();
+    assert_matches!(
+        create_declare_tx(empty_contract.get_class_hash(), compiled_class_hash)
+            .verify_class_hashes(&declared_class)
+            .unwrap_err(),
+        TransactionExecutionError::InvalidClassHash { expected_class_hash, .. }
+        if expected_class_hash == class_hash
+    );
+    assert_matches!(
+        create_declare_tx(class_hash, empty_contract.get_compiled_class_hash())
+            .verify_class_hashes(&declared_class)
+            .unwrap_err(),
+        TransactionExecutionError::InvalidCompiledClassHash { expected_compiled_class_hash, .. }
+        if expected_compiled_class_hash == compiled_class_hash
+    );
+}
@@ -29,6 +29,9 @@ pub enum ExecutionMode {
     Execute,
     /// Validate execution mode.
     Validate,
+    /// Read-only execution mode, used by strict view calls; state-modifying syscalls are not
+    /// allowed.
+    View,
 }
 
 pub type HintExecutionResult = Result<(), HintError>;
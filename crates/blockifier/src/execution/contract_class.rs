@@ -1,11 +1,19 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::Deref;
 use std::sync::Arc;
 
 use cairo_lang_casm;
 use cairo_lang_casm::hints::Hint;
-use cairo_lang_starknet_classes::casm_contract_class::{CasmContractClass, CasmContractEntryPoint};
+use cairo_lang_starknet_classes::casm_contract_class::{
+    CasmContractClass, CasmContractEntryPoint, CasmContractEntryPoints,
+};
+use cairo_lang_starknet_classes::compiler_version::current_compiler_version_id;
+use cairo_lang_starknet_classes::contract_class::{
+    version_id_from_serialized_sierra_program, ContractClass as CairoLangContractClass,
+    ContractEntryPoint, ContractEntryPoints,
+};
 use cairo_lang_starknet_classes::NestedIntList;
+use cairo_lang_utils::bigint::BigUintAsHex;
 use cairo_vm::serde::deserialize_program::{
     ApTracking, FlowTrackingData, HintParams, ReferenceManager,
 };
@@ -22,6 +30,9 @@ use starknet_api::deprecated_contract_class::{
     ContractClass as DeprecatedContractClass, EntryPoint, EntryPointOffset, EntryPointType,
     Program as DeprecatedProgram,
 };
+use starknet_api::state::{
+    ContractClass as SierraContractClass, EntryPointType as SierraEntryPointType,
+};
 use starknet_types_core::felt::Felt;
 
 use super::execution_utils::poseidon_hash_many_cost;
@@ -32,6 +43,7 @@ use crate::execution::errors::{ContractClassError, PreExecutionError};
 use crate::execution::execution_utils::sn_api_to_cairo_vm_program;
 use crate::fee::eth_gas_constants;
 use crate::transaction::errors::TransactionExecutionError;
+use crate::versioned_constants::{SierraCompilationLimits, SierraVersion};
 
 #[cfg(test)]
 #[path = "contract_class_test.rs"]
@@ -226,6 +238,31 @@ impl ContractClassV1 {
         Ok(contract_class)
     }
 
+    /// Compiles the given Sierra class, provided that its Sierra version and its compiled bytecode
+    /// size are within the given limits.
+    pub fn try_from_sierra(
+        sierra_class: &SierraContractClass,
+        limits: &SierraCompilationLimits,
+    ) -> ContractClassResult<ContractClassV1> {
+        let cairo_lang_contract_class = to_cairo_lang_contract_class(sierra_class);
+        let sierra_version = sierra_version(&cairo_lang_contract_class.sierra_program)?;
+        if sierra_version < limits.min_sierra_version || sierra_version > limits.max_sierra_version
+        {
+            return Err(ContractClassError::UnsupportedSierraVersion {
+                sierra_version,
+                min_sierra_version: limits.min_sierra_version,
+                max_sierra_version: limits.max_sierra_version,
+            });
+        }
+
+        let casm_contract_class = CasmContractClass::from_contract_class(
+            cairo_lang_contract_class,
+            false,
+            limits.max_bytecode_size,
+        )?;
+        Ok(casm_contract_class.try_into()?)
+    }
+
     /// Returns an empty contract class for testing purposes.
     #[cfg(any(feature = "testing", test))]
     pub fn empty_for_testing() -> Self {
@@ -421,6 +458,80 @@ impl TryFrom<CasmContractClass> for ContractClassV1 {
     }
 }
 
+/// Converts the class back into its CASM form; the inverse of the conversion above.
+impl TryFrom<&ContractClassV1> for CasmContractClass {
+    type Error = ContractClassError;
+
+    fn try_from(class: &ContractClassV1) -> Result<Self, Self::Error> {
+        let bytecode = class
+            .program
+            .iter_data()
+            .map(|value| match value {
+                MaybeRelocatable::Int(felt) => Ok(BigUintAsHex { value: felt.to_biguint() }),
+                MaybeRelocatable::RelocatableValue(_) => Err(
+                    ContractClassError::InvalidCompiledClass("relocatable bytecode".to_string()),
+                ),
+            })
+            .collect::<ContractClassResult<_>>()?;
+
+        // The hints of each PC are only exposed through the program serialization; each hint code
+        // is the serialization of the CASM hint.
+        let program_hints: ProgramHints =
+            serde_json::from_slice(&class.program.serialize()?).map_err(ProgramError::from)?;
+        let hints = program_hints
+            .hints
+            .into_iter()
+            .map(|(pc, hint_codes)| -> ContractClassResult<(usize, Vec<Hint>)> {
+                let hints = hint_codes
+                    .into_iter()
+                    .map(|hint_code| {
+                        class.hints.get(&hint_code.code).cloned().ok_or_else(|| {
+                            ContractClassError::InvalidCompiledClass(format!(
+                                "unknown hint {}",
+                                hint_code.code
+                            ))
+                        })
+                    })
+                    .collect::<ContractClassResult<_>>()?;
+                Ok((pc, hints))
+            })
+            .collect::<ContractClassResult<_>>()?;
+
+        let entry_points = |entry_point_type: EntryPointType| -> Vec<CasmContractEntryPoint> {
+            class
+                .entry_points_by_type
+                .get(&entry_point_type)
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+                .iter()
+                .map(|entry_point| CasmContractEntryPoint {
+                    selector: entry_point.selector.0.to_biguint(),
+                    offset: entry_point.offset.0,
+                    builtins: entry_point
+                        .builtins
+                        .iter()
+                        .map(|builtin| builtin.strip_suffix("_builtin").unwrap_or(builtin).into())
+                        .collect(),
+                })
+                .collect()
+        };
+
+        Ok(Self {
+            prime: Felt::prime(),
+            compiler_version: current_compiler_version_id().to_string(),
+            bytecode,
+            bytecode_segment_lengths: Some(class.bytecode_segment_lengths.clone()),
+            hints,
+            pythonic_hints: None,
+            entry_points_by_type: CasmContractEntryPoints {
+                external: entry_points(EntryPointType::External),
+                l1_handler: entry_points(EntryPointType::L1Handler),
+                constructor: entry_points(EntryPointType::Constructor),
+            },
+        })
+    }
+}
+
 // V0 utilities.
 
 /// Converts the program type from SN API into a Cairo VM-compatible type.
@@ -446,6 +557,64 @@ fn hint_to_hint_params(hint: &cairo_lang_casm::hints::Hint) -> Result<HintParams
     })
 }
 
+/// The hints of a serialized Cairo VM program, by PC.
+#[derive(Deserialize)]
+struct ProgramHints {
+    hints: BTreeMap<usize, Vec<ProgramHint>>,
+}
+
+#[derive(Deserialize)]
+struct ProgramHint {
+    code: String,
+}
+
+/// Returns the version of the given Sierra program, encoded in its header.
+fn sierra_version(sierra_program: &[BigUintAsHex]) -> ContractClassResult<SierraVersion> {
+    let (sierra_version_id, _) = version_id_from_serialized_sierra_program(sierra_program)
+        .map_err(|_| ContractClassError::InvalidSierraVersion)?;
+    let to_u64 =
+        |value: usize| u64::try_from(value).map_err(|_| ContractClassError::InvalidSierraVersion);
+
+    Ok(SierraVersion {
+        major: to_u64(sierra_version_id.major)?,
+        minor: to_u64(sierra_version_id.minor)?,
+        patch: to_u64(sierra_version_id.patch)?,
+    })
+}
+
+fn to_cairo_lang_contract_class(sierra_class: &SierraContractClass) -> CairoLangContractClass {
+    let entry_points = |entry_point_type: SierraEntryPointType| -> Vec<ContractEntryPoint> {
+        sierra_class
+            .entry_points_by_type
+            .get(&entry_point_type)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|entry_point| ContractEntryPoint {
+                selector: entry_point.selector.0.to_biguint(),
+                function_idx: entry_point.function_idx.0,
+            })
+            .collect()
+    };
+
+    CairoLangContractClass {
+        sierra_program: sierra_class
+            .sierra_program
+            .iter()
+            .map(|felt| BigUintAsHex { value: felt.to_biguint() })
+            .collect(),
+        sierra_program_debug_info: None,
+        contract_class_version: constants::SIERRA_CONTRACT_CLASS_VERSION.to_string(),
+        entry_points_by_type: ContractEntryPoints {
+            external: entry_points(SierraEntryPointType::External),
+            l1_handler: entry_points(SierraEntryPointType::L1Handler),
+            constructor: entry_points(SierraEntryPointType::Constructor),
+        },
+        // The ABI is not required for compilation.
+        abi: None,
+    }
+}
+
 fn convert_entry_points_v1(
     external: Vec<CasmContractEntryPoint>,
 ) -> Result<Vec<EntryPointV1>, ProgramError> {
@@ -493,6 +662,20 @@ impl ClassInfo {
             + self.abi_length()
     }
 
+    /// Compiles the given Sierra class (see [ContractClassV1::try_from_sierra]) and returns its
+    /// class info.
+    pub fn try_from_sierra(
+        sierra_class: &SierraContractClass,
+        limits: &SierraCompilationLimits,
+    ) -> ContractClassResult<Self> {
+        let contract_class = ContractClassV1::try_from_sierra(sierra_class, limits)?;
+        Self::new(
+            &ContractClass::V1(contract_class),
+            sierra_class.sierra_program.len(),
+            sierra_class.abi.len(),
+        )
+    }
+
     pub fn new(
         contract_class: &ContractClass,
         sierra_program_length: usize,
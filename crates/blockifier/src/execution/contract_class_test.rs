@@ -2,11 +2,46 @@ use std::collections::HashSet;
 use std::sync::Arc;
 
 use assert_matches::assert_matches;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use cairo_lang_starknet_classes::NestedIntList;
 use rstest::rstest;
+use starknet_api::core::CompiledClassHash;
+use starknet_api::state::ContractClass as SierraContractClass;
+use starknet_types_core::felt::Felt;
 
-use crate::execution::contract_class::{ContractClassV1, ContractClassV1Inner};
+use crate::execution::class_hash::compute_compiled_class_hash;
+use crate::execution::contract_class::{ContractClass, ContractClassV1, ContractClassV1Inner};
+use crate::execution::errors::ContractClassError;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::CairoVersion;
 use crate::transaction::errors::TransactionExecutionError;
+use crate::versioned_constants::{SierraCompilationLimits, SierraVersion};
+
+const HELLO_STARKNET_SIERRA: &str =
+    include_str!("../../resources/test_data/hello_starknet.sierra.json");
+const HELLO_STARKNET_CASM: &str =
+    include_str!("../../resources/test_data/hello_starknet.casm.json");
+
+/// Returns a Sierra program consisting only of a version header: the Sierra version followed by
+/// the compiler version.
+fn sierra_program_header(major: u8, minor: u8, patch: u8) -> Vec<Felt> {
+    [major, minor, patch, 2, 7, 0].into_iter().map(Felt::from).collect()
+}
+
+fn hello_starknet_classes() -> (SierraContractClass, CasmContractClass) {
+    (
+        serde_json::from_str(HELLO_STARKNET_SIERRA).unwrap(),
+        serde_json::from_str(HELLO_STARKNET_CASM).unwrap(),
+    )
+}
+
+fn sierra_compilation_limits(max_bytecode_size: usize) -> SierraCompilationLimits {
+    SierraCompilationLimits {
+        min_sierra_version: SierraVersion { major: 1, minor: 1, patch: 0 },
+        max_sierra_version: SierraVersion { major: 1, minor: 6, patch: 0 },
+        max_bytecode_size,
+    }
+}
 
 #[rstest]
 fn test_get_visited_segments() {
@@ -42,3 +77,67 @@ fn test_get_visited_segments() {
         TransactionExecutionError::InvalidSegmentStructure(907, 807)
     );
 }
+
+#[rstest]
+#[case::missing_version(vec![], None)]
+#[case::too_old(
+    sierra_program_header(1, 0, 0),
+    Some(SierraVersion { major: 1, minor: 0, patch: 0 })
+)]
+#[case::too_new(
+    sierra_program_header(2, 0, 0),
+    Some(SierraVersion { major: 2, minor: 0, patch: 0 })
+)]
+fn test_sierra_version_limits(
+    #[case] sierra_program: Vec<Felt>,
+    #[case] expected_unsupported_version: Option<SierraVersion>,
+) {
+    let limits = sierra_compilation_limits(100);
+    let sierra_class = SierraContractClass { sierra_program, ..Default::default() };
+
+    let error = ContractClassV1::try_from_sierra(&sierra_class, &limits).unwrap_err();
+    match expected_unsupported_version {
+        None => assert_matches!(error, ContractClassError::InvalidSierraVersion),
+        Some(expected_version) => assert_matches!(
+            error,
+            ContractClassError::UnsupportedSierraVersion { sierra_version, .. }
+            if sierra_version == expected_version
+        ),
+    }
+}
+
+#[test]
+fn test_casm_conversion_round_trip() {
+    let ContractClass::V1(contract_class) =
+        FeatureContract::TestContract(CairoVersion::Cairo1).get_class()
+    else {
+        panic!("Expected a Cairo 1 class.");
+    };
+
+    let casm_contract_class = CasmContractClass::try_from(&contract_class).unwrap();
+    assert_eq!(ContractClassV1::try_from(casm_contract_class).unwrap(), contract_class);
+}
+
+#[test]
+fn test_try_from_sierra() {
+    let (sierra_class, casm_contract_class) = hello_starknet_classes();
+    let limits = sierra_compilation_limits(casm_contract_class.bytecode.len());
+
+    let contract_class = ContractClassV1::try_from_sierra(&sierra_class, &limits).unwrap();
+    assert_eq!(
+        compute_compiled_class_hash(&contract_class).unwrap(),
+        CompiledClassHash(casm_contract_class.compiled_class_hash())
+    );
+    assert_eq!(contract_class, ContractClassV1::try_from(casm_contract_class).unwrap());
+}
+
+#[test]
+fn test_try_from_sierra_max_bytecode_size() {
+    let (sierra_class, casm_contract_class) = hello_starknet_classes();
+    let limits = sierra_compilation_limits(casm_contract_class.bytecode.len() - 1);
+
+    assert_matches!(
+        ContractClassV1::try_from_sierra(&sierra_class, &limits).unwrap_err(),
+        ContractClassError::SierraCompilationError(_)
+    );
+}
@@ -10,7 +10,7 @@ use starknet_api::hash::StarkHash;
 use super::execution_utils::SEGMENT_ARENA_BUILTIN_SIZE;
 use crate::abi::abi_utils::selector_from_name;
 use crate::abi::constants::{CONSTRUCTOR_ENTRY_POINT_NAME, DEFAULT_ENTRY_POINT_SELECTOR};
-use crate::execution::call_info::{CallExecution, CallInfo};
+use crate::execution::call_info::{CallExecution, CallInfo, TrackedResource};
 use crate::execution::contract_class::ContractClassV0;
 use crate::execution::deprecated_syscalls::hint_processor::DeprecatedSyscallHintProcessor;
 use crate::execution::entry_point::{
@@ -36,21 +36,37 @@ pub fn execute_entry_point_call(
     context: &mut EntryPointExecutionContext,
 ) -> EntryPointExecutionResult<CallInfo> {
     let VmExecutionContext { mut runner, mut syscall_handler, initial_syscall_ptr, entry_point_pc } =
-        initialize_execution_context(&call, contract_class, state, resources, context)?;
+        match initialize_execution_context(&call, contract_class, state, resources, context) {
+            Ok(vm_execution_context) => vm_execution_context,
+            Err(error) => {
+                // Nothing was executed; the failure is recorded with the call alone.
+                context.record_failed_call(CallInfo { call, ..Default::default() });
+                return Err(error.into());
+            }
+        };
+
+    // Fix the VM resources, in order to calculate the usage of this run at the end.
+    let previous_resources = syscall_handler.resources.clone();
 
-    let (implicit_args, args) = prepare_call_arguments(
+    let (implicit_args, args) = match prepare_call_arguments(
         &call,
         &mut runner,
         initial_syscall_ptr,
         &mut syscall_handler.read_only_segments,
-    )?;
+    ) {
+        Ok(call_arguments) => call_arguments,
+        Err(error) => {
+            record_failed_execution(&mut runner, syscall_handler, call, &previous_resources)?;
+            return Err(error.into());
+        }
+    };
     let n_total_args = args.len();
 
-    // Fix the VM resources, in order to calculate the usage of this run at the end.
-    let previous_resources = syscall_handler.resources.clone();
-
     // Execute.
-    run_entry_point(&mut runner, &mut syscall_handler, entry_point_pc, args)?;
+    if let Err(error) = run_entry_point(&mut runner, &mut syscall_handler, entry_point_pc, args) {
+        record_failed_execution(&mut runner, syscall_handler, call, &previous_resources)?;
+        return Err(error);
+    }
 
     Ok(finalize_execution(
         runner,
@@ -62,6 +78,39 @@ pub fn execute_entry_point_call(
     )?)
 }
 
+/// Records the partial call info of an execution that failed inside the VM, up to the failure
+/// point.
+/// Note: failures in finalizing the execution are internal errors, and are not recorded; neither
+/// is the call if its VM resources cannot be computed, in which case the error is returned.
+fn record_failed_execution(
+    runner: &mut CairoRunner,
+    syscall_handler: DeprecatedSyscallHintProcessor<'_>,
+    call: CallEntryPoint,
+    previous_resources: &ExecutionResources,
+) -> Result<(), PostExecutionError> {
+    // Computing the VM resources requires the segment sizes, which are not computed if the run
+    // failed.
+    runner.vm.segments.compute_effective_sizes();
+    let resources =
+        &(&*syscall_handler.resources - previous_resources) + &runner.get_execution_resources()?;
+    let call_info = CallInfo {
+        call,
+        execution: CallExecution {
+            events: syscall_handler.events,
+            l2_to_l1_messages: syscall_handler.l2_to_l1_messages,
+            ..Default::default()
+        },
+        resources: resources.filter_unused_builtins(),
+        tracked_resource: TrackedResource::CairoSteps,
+        inner_calls: syscall_handler.inner_calls,
+        storage_read_values: syscall_handler.read_values,
+        accessed_storage_keys: syscall_handler.accessed_keys,
+    };
+    syscall_handler.context.record_failed_call(call_info);
+
+    Ok(())
+}
+
 pub fn initialize_execution_context<'a>(
     call: &CallEntryPoint,
     contract_class: ContractClassV0,
@@ -256,6 +305,7 @@ pub fn finalize_execution(
             gas_consumed: 0,
         },
         resources: full_call_resources.filter_unused_builtins(),
+        tracked_resource: TrackedResource::CairoSteps,
         inner_calls: syscall_handler.inner_calls,
         storage_read_values: syscall_handler.read_values,
         accessed_storage_keys: syscall_handler.accessed_keys,
@@ -217,6 +217,18 @@ impl<'a> DeprecatedSyscallHintProcessor<'a> {
         Ok(())
     }
 
+    /// Returns an error if the syscall is run in view mode.
+    pub fn verify_not_in_view_mode(&self, syscall_name: &str) -> DeprecatedSyscallResult<()> {
+        if self.execution_mode() == ExecutionMode::View {
+            return Err(DeprecatedSyscallExecutionError::InvalidSyscallInExecutionMode {
+                syscall_name: syscall_name.to_string(),
+                execution_mode: self.execution_mode(),
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn verify_syscall_ptr(&self, actual_ptr: Relocatable) -> DeprecatedSyscallResult<()> {
         if actual_ptr != self.syscall_ptr {
             return Err(DeprecatedSyscallExecutionError::BadSyscallPointer {
@@ -15,6 +15,7 @@ use crate::abi::constants;
 use crate::context::{BlockContext, TransactionContext};
 use crate::execution::call_info::CallInfo;
 use crate::execution::common_hints::ExecutionMode;
+use crate::execution::contract_class::ContractClass;
 use crate::execution::errors::{
     ConstructorEntryPointExecutionError, EntryPointExecutionError, PreExecutionError,
 };
@@ -62,17 +63,50 @@ pub struct CallEntryPoint {
 }
 
 impl CallEntryPoint {
+    /// Executes the call. On failure, the (partial) call info of the call is recorded in the
+    /// context (see [EntryPointExecutionContext::take_failed_call_info]).
     pub fn execute(
         mut self,
         state: &mut dyn State,
         resources: &mut ExecutionResources,
         context: &mut EntryPointExecutionContext,
     ) -> EntryPointExecutionResult<CallInfo> {
-        let tx_context = &context.tx_context;
+        let depth = *context.current_recursion_depth.borrow() + 1;
         let mut decrement_when_dropped = RecursionDepthGuard::new(
             context.current_recursion_depth.clone(),
             context.versioned_constants().max_recursion_depth,
         );
+
+        // Failures that occur before the execution are recorded with the call alone; failures
+        // during the execution are recorded by it, with the partial execution info.
+        let contract_class =
+            match self.prepare_execution(state, context, &mut decrement_when_dropped) {
+                Ok(contract_class) => contract_class,
+                Err(error) => {
+                    context.record_failed_call_at(
+                        depth,
+                        CallInfo { call: self, ..Default::default() },
+                    );
+                    return Err(error);
+                }
+            };
+
+        let result = execute_entry_point_call(self, contract_class, state, resources, context);
+        if result.is_ok() {
+            context.discard_failed_calls_deeper_than(depth);
+        }
+
+        result
+    }
+
+    /// Performs the pre-execution checks, and returns the class to execute.
+    fn prepare_execution(
+        &mut self,
+        state: &mut dyn State,
+        context: &EntryPointExecutionContext,
+        decrement_when_dropped: &mut RecursionDepthGuard,
+    ) -> EntryPointExecutionResult<ContractClass> {
+        let tx_context = &context.tx_context;
         decrement_when_dropped.try_increment_and_check_depth()?;
 
         // Validate contract is deployed.
@@ -96,9 +130,8 @@ impl CallEntryPoint {
         }
         // Add class hash to the call, that will appear in the output (call info).
         self.class_hash = Some(class_hash);
-        let contract_class = state.get_compiled_contract_class(class_hash)?;
 
-        execute_entry_point_call(self, contract_class, state, resources, context)
+        Ok(state.get_compiled_contract_class(class_hash)?)
     }
 }
 
@@ -126,6 +159,10 @@ pub struct EntryPointExecutionContext {
 
     // The execution mode affects the behavior of the hint processor.
     pub execution_mode: ExecutionMode,
+    /// The (partial) call infos of failed calls that were not yet attached to a failed caller,
+    /// along with their recursion depths (which are non-decreasing); the last one is reported if
+    /// the failure reverts the transaction.
+    failed_calls: Vec<(usize, CallInfo)>,
 }
 
 impl EntryPointExecutionContext {
@@ -142,6 +179,7 @@ impl EntryPointExecutionContext {
             tx_context: tx_context.clone(),
             current_recursion_depth: Default::default(),
             execution_mode: mode,
+            failed_calls: Vec::new(),
         })
     }
 
@@ -159,6 +197,49 @@ impl EntryPointExecutionContext {
         Self::new(tx_context, ExecutionMode::Execute, limit_steps_by_resources)
     }
 
+    pub fn new_view(
+        tx_context: Arc<TransactionContext>,
+        limit_steps_by_resources: bool,
+    ) -> TransactionExecutionResult<Self> {
+        Self::new(tx_context, ExecutionMode::View, limit_steps_by_resources)
+    }
+
+    /// Records the (partial) call info of the call failing at the current recursion depth, and
+    /// marks it as failed. The failed inner calls recorded before it, if any, are appended to its
+    /// inner calls.
+    pub(crate) fn record_failed_call(&mut self, call_info: CallInfo) {
+        let depth = *self.current_recursion_depth.borrow();
+        self.record_failed_call_at(depth, call_info);
+    }
+
+    fn record_failed_call_at(&mut self, depth: usize, mut call_info: CallInfo) {
+        call_info.execution.failed = true;
+        let first_inner_index =
+            self.failed_calls.partition_point(|(failed_depth, _)| *failed_depth <= depth);
+        call_info.inner_calls.extend(
+            self.failed_calls
+                .drain(first_inner_index..)
+                .map(|(_, inner_call_info)| inner_call_info),
+        );
+        self.failed_calls.push((depth, call_info));
+    }
+
+    /// Discards the failed calls made within a call at the given depth that completed
+    /// successfully; they are not part of its call info.
+    fn discard_failed_calls_deeper_than(&mut self, depth: usize) {
+        let first_inner_index =
+            self.failed_calls.partition_point(|(failed_depth, _)| *failed_depth <= depth);
+        self.failed_calls.truncate(first_inner_index);
+    }
+
+    /// Takes the (partial) call info tree of the last failed call, up to the failure point.
+    /// The failing frame is the innermost frame marked as failed.
+    pub fn take_failed_call_info(&mut self) -> Option<CallInfo> {
+        let failed_call = self.failed_calls.pop().map(|(_, call_info)| call_info);
+        self.failed_calls.clear();
+        failed_call
+    }
+
     /// Returns the maximum number of cairo steps allowed, given the max fee, gas price and the
     /// execution mode.
     /// If fee is disabled, returns the global maximum.
@@ -176,7 +257,7 @@ impl EntryPointExecutionContext {
                 .validate_max_n_steps
                 .try_into()
                 .expect("Failed to convert validate_max_n_steps (u32) to usize."),
-            ExecutionMode::Execute => versioned_constants
+            ExecutionMode::Execute | ExecutionMode::View => versioned_constants
                 .invoke_tx_max_n_steps
                 .try_into()
                 .expect("Failed to convert invoke_tx_max_n_steps (u32) to usize."),
@@ -13,7 +13,7 @@ use num_traits::{ToPrimitive, Zero};
 use starknet_api::felt;
 use starknet_types_core::felt::Felt;
 
-use crate::execution::call_info::{CallExecution, CallInfo, Retdata};
+use crate::execution::call_info::{CallExecution, CallInfo, Retdata, TrackedResource};
 use crate::execution::contract_class::{ContractClassV1, EntryPointV1};
 use crate::execution::entry_point::{
     CallEntryPoint, EntryPointExecutionContext, EntryPointExecutionResult,
@@ -25,6 +25,7 @@ use crate::execution::execution_utils::{
 };
 use crate::execution::syscalls::hint_processor::SyscallHintProcessor;
 use crate::state::state_api::State;
+use crate::versioned_constants::VersionedConstants;
 
 // TODO(spapini): Try to refactor this file into a StarknetRunner struct.
 
@@ -64,31 +65,45 @@ pub fn execute_entry_point_call(
         program_extra_data_length,
     } = initialize_execution_context(call, &contract_class, state, resources, context)?;
 
-    let args = prepare_call_arguments(
+    // Fix the resources, in order to calculate the usage of this run at the end.
+    let previous_resources = syscall_handler.resources.clone();
+
+    let args = match prepare_call_arguments(
         &syscall_handler.call,
         &mut runner,
         initial_syscall_ptr,
         &mut syscall_handler.read_only_segments,
         &entry_point,
-    )?;
+    ) {
+        Ok(args) => args,
+        Err(error) => {
+            record_failed_execution(&mut runner, syscall_handler, &previous_resources)?;
+            return Err(error.into());
+        }
+    };
     let n_total_args = args.len();
 
-    // Fix the resources, in order to calculate the usage of this run at the end.
-    let previous_resources = syscall_handler.resources.clone();
-
     // Execute.
     let bytecode_length = contract_class.bytecode_length();
     let program_segment_size = bytecode_length + program_extra_data_length;
-    run_entry_point(&mut runner, &mut syscall_handler, entry_point, args, program_segment_size)?;
+    if let Err(error) =
+        run_entry_point(&mut runner, &mut syscall_handler, entry_point, args, program_segment_size)
+    {
+        record_failed_execution(&mut runner, syscall_handler, &previous_resources)?;
+        return Err(error);
+    }
 
     // Collect the set PC values that were visited during the entry point execution.
-    register_visited_pcs(
+    if let Err(error) = register_visited_pcs(
         &mut runner,
         syscall_handler.state,
         class_hash,
         program_segment_size,
         bytecode_length,
-    )?;
+    ) {
+        record_failed_execution(&mut runner, syscall_handler, &previous_resources)?;
+        return Err(error);
+    }
 
     let call_info = finalize_execution(
         runner,
@@ -98,14 +113,63 @@ pub fn execute_entry_point_call(
         program_extra_data_length,
     )?;
     if call_info.execution.failed {
-        return Err(EntryPointExecutionError::ExecutionFailed {
-            error_data: call_info.execution.retdata.0,
-        });
+        let error_data = call_info.execution.retdata.0.clone();
+        context.record_failed_call(call_info);
+        return Err(EntryPointExecutionError::ExecutionFailed { error_data });
     }
 
     Ok(call_info)
 }
 
+/// Records the partial call info of an execution that failed inside the VM, up to the failure
+/// point.
+/// Note: failures in finalizing the execution are internal errors, and are not recorded; neither
+/// is the call if its VM resources cannot be computed, in which case the error is returned.
+fn record_failed_execution(
+    runner: &mut CairoRunner,
+    syscall_handler: SyscallHintProcessor<'_>,
+    previous_resources: &ExecutionResources,
+) -> Result<(), PostExecutionError> {
+    // Computing the VM resources requires the segment sizes, which are not computed if the run
+    // failed.
+    runner.vm.segments.compute_effective_sizes();
+    let resources =
+        &(&*syscall_handler.resources - previous_resources) + &runner.get_execution_resources()?;
+    let tracked_resource = tracked_resource(syscall_handler.context.versioned_constants());
+    // The gas remaining at the failure point is unknown; if the call is charged by Sierra gas, all
+    // of its initial gas is considered consumed.
+    let gas_consumed = match tracked_resource {
+        TrackedResource::CairoSteps => 0,
+        TrackedResource::SierraGas => syscall_handler.call.initial_gas,
+    };
+    let call_info = CallInfo {
+        call: syscall_handler.call,
+        execution: CallExecution {
+            events: syscall_handler.events,
+            l2_to_l1_messages: syscall_handler.l2_to_l1_messages,
+            gas_consumed,
+            ..Default::default()
+        },
+        resources: resources.filter_unused_builtins(),
+        tracked_resource,
+        inner_calls: syscall_handler.inner_calls,
+        storage_read_values: syscall_handler.read_values,
+        accessed_storage_keys: syscall_handler.accessed_keys,
+    };
+    syscall_handler.context.record_failed_call(call_info);
+
+    Ok(())
+}
+
+/// Returns the resource by which the execution of a Cairo 1 call is charged.
+fn tracked_resource(versioned_constants: &VersionedConstants) -> TrackedResource {
+    if versioned_constants.enable_l2_gas {
+        TrackedResource::SierraGas
+    } else {
+        TrackedResource::CairoSteps
+    }
+}
+
 // Collects the set PC values that were visited during the entry point execution.
 fn register_visited_pcs(
     runner: &mut CairoRunner,
@@ -145,7 +209,53 @@ pub fn initialize_execution_context<'a>(
     resources: &'a mut ExecutionResources,
     context: &'a mut EntryPointExecutionContext,
 ) -> Result<VmExecutionContext<'a>, PreExecutionError> {
-    let entry_point = contract_class.get_entry_point(&call)?;
+    let InitializedRunner {
+        mut runner,
+        entry_point,
+        read_only_segments,
+        program_extra_data_length,
+    } = match initialize_runner(&call, contract_class) {
+        Ok(initialized_runner) => initialized_runner,
+        Err(error) => {
+            // Nothing was executed; the failure is recorded with the call alone.
+            context.record_failed_call(CallInfo { call, ..Default::default() });
+            return Err(error);
+        }
+    };
+
+    // Instantiate syscall handler.
+    let initial_syscall_ptr = runner.vm.add_memory_segment();
+    let syscall_handler = SyscallHintProcessor::new(
+        state,
+        resources,
+        context,
+        initial_syscall_ptr,
+        call,
+        &contract_class.hints,
+        read_only_segments,
+    );
+
+    Ok(VmExecutionContext {
+        runner,
+        syscall_handler,
+        initial_syscall_ptr,
+        entry_point,
+        program_extra_data_length,
+    })
+}
+
+struct InitializedRunner {
+    runner: CairoRunner,
+    entry_point: EntryPointV1,
+    read_only_segments: ReadOnlySegments,
+    program_extra_data_length: usize,
+}
+
+fn initialize_runner(
+    call: &CallEntryPoint,
+    contract_class: &ContractClassV1,
+) -> Result<InitializedRunner, PreExecutionError> {
+    let entry_point = contract_class.get_entry_point(call)?;
 
     // Instantiate Cairo runner.
     let proof_mode = false;
@@ -175,25 +285,7 @@ pub fn initialize_execution_context<'a>(
     let program_extra_data_length =
         prepare_program_extra_data(&mut runner, contract_class, &mut read_only_segments)?;
 
-    // Instantiate syscall handler.
-    let initial_syscall_ptr = runner.vm.add_memory_segment();
-    let syscall_handler = SyscallHintProcessor::new(
-        state,
-        resources,
-        context,
-        initial_syscall_ptr,
-        call,
-        &contract_class.hints,
-        read_only_segments,
-    );
-
-    Ok(VmExecutionContext {
-        runner,
-        syscall_handler,
-        initial_syscall_ptr,
-        entry_point,
-        program_extra_data_length,
-    })
+    Ok(InitializedRunner { runner, entry_point, read_only_segments, program_extra_data_length })
 }
 
 fn prepare_program_extra_data(
@@ -409,6 +501,7 @@ pub fn finalize_execution(
         .get_additional_os_syscall_resources(&syscall_handler.syscall_counter)?;
 
     let full_call_resources = &*syscall_handler.resources - &previous_resources;
+    let tracked_resource = tracked_resource(versioned_constants);
     Ok(CallInfo {
         call: syscall_handler.call,
         execution: CallExecution {
@@ -419,6 +512,7 @@ pub fn finalize_execution(
             gas_consumed: call_result.gas_consumed,
         },
         resources: full_call_resources.filter_unused_builtins(),
+        tracked_resource,
         inner_calls: syscall_handler.inner_calls,
         storage_read_values: syscall_handler.read_values,
         accessed_storage_keys: syscall_handler.accessed_keys,
@@ -1,21 +1,25 @@
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use cairo_vm::types::builtin_name::BuiltinName;
+use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use num_bigint::BigInt;
 use pretty_assertions::assert_eq;
+use rstest::rstest;
 use starknet_api::core::{EntryPointSelector, PatriciaKey};
 use starknet_api::transaction::Calldata;
 use starknet_api::{calldata, felt};
 
 use crate::abi::abi_utils::{get_storage_var_address, selector_from_name};
-use crate::context::ChainInfo;
+use crate::context::{BlockContext, ChainInfo, TransactionContext};
 use crate::execution::call_info::{CallExecution, CallInfo, Retdata};
-use crate::execution::entry_point::CallEntryPoint;
+use crate::execution::entry_point::{CallEntryPoint, EntryPointExecutionContext};
 use crate::state::cached_state::CachedState;
 use crate::test_utils::contracts::FeatureContract;
 use crate::test_utils::dict_state_reader::DictStateReader;
 use crate::test_utils::initial_test_state::test_state;
 use crate::test_utils::{trivial_external_entry_point_new, CairoVersion, BALANCE};
+use crate::transaction::objects::{DeprecatedTransactionInfo, TransactionInfo};
 use crate::versioned_constants::VersionedConstants;
 use crate::{retdata, storage_key};
 
@@ -537,3 +541,62 @@ fn test_cairo1_entry_point_segment_arena() {
         2
     );
 }
+
+#[rstest]
+#[case::cairo0(CairoVersion::Cairo0, "with_arg", calldata![felt!(1_u8)], calldata![felt!(25_u8)])]
+#[case::cairo1(
+    CairoVersion::Cairo1,
+    "assert_eq",
+    calldata![felt!(1_u8), felt!(2_u8)],
+    calldata![felt!(1_u8), felt!(1_u8)]
+)]
+fn test_failed_call_recording(
+    #[case] cairo_version: CairoVersion,
+    #[case] entry_point_name: &str,
+    #[case] failing_calldata: Calldata,
+    #[case] passing_calldata: Calldata,
+) {
+    let test_contract = FeatureContract::TestContract(cairo_version);
+    let chain_info = &ChainInfo::create_for_testing();
+    let mut state = test_state(chain_info, BALANCE, &[(test_contract, 1)]);
+    let tx_context = TransactionContext {
+        block_context: BlockContext::create_for_testing(),
+        tx_info: TransactionInfo::Deprecated(DeprecatedTransactionInfo::default()),
+    };
+    let mut context = EntryPointExecutionContext::new_invoke(Arc::new(tx_context), true).unwrap();
+    let call_with_calldata = |calldata: Calldata| CallEntryPoint {
+        entry_point_selector: selector_from_name(entry_point_name),
+        calldata,
+        ..trivial_external_entry_point_new(test_contract)
+    };
+    let mut execute = |call: CallEntryPoint| {
+        call.execute(&mut state, &mut ExecutionResources::default(), &mut context)
+    };
+
+    // A failed call, whose failure is handled by the caller, followed by a successful call; the
+    // partial call info of the failed call is kept, with the resources consumed up to the failure.
+    execute(call_with_calldata(failing_calldata.clone())).unwrap_err();
+    execute(call_with_calldata(passing_calldata)).unwrap();
+    let failed_call_info = context.take_failed_call_info().unwrap();
+    assert!(failed_call_info.execution.failed);
+    assert_eq!(failed_call_info.call.calldata, failing_calldata);
+    assert!(failed_call_info.resources.n_steps > 0);
+    assert!(context.take_failed_call_info().is_none());
+
+    // Failures before the execution are recorded with the call alone.
+    let missing_entry_point_call = CallEntryPoint {
+        entry_point_selector: selector_from_name("no_such_entry_point"),
+        ..trivial_external_entry_point_new(test_contract)
+    };
+    missing_entry_point_call
+        .clone()
+        .execute(&mut state, &mut ExecutionResources::default(), &mut context)
+        .unwrap_err();
+    let failed_call_info = context.take_failed_call_info().unwrap();
+    assert!(failed_call_info.execution.failed);
+    assert_eq!(
+        failed_call_info.call.entry_point_selector,
+        missing_entry_point_call.entry_point_selector
+    );
+    assert!(failed_call_info.inner_calls.is_empty());
+}
@@ -1,4 +1,6 @@
+use cairo_lang_starknet_classes::casm_contract_class::StarknetSierraCompilationError;
 use cairo_vm::types::errors::math_errors::MathError;
+use cairo_vm::types::errors::program_errors::ProgramError;
 use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
 use cairo_vm::vm::errors::memory_errors::MemoryError;
 use cairo_vm::vm::errors::runner_errors::RunnerError;
@@ -13,6 +15,7 @@ use thiserror::Error;
 use crate::execution::entry_point::ConstructorContext;
 use crate::execution::execution_utils::format_panic_data;
 use crate::state::errors::StateError;
+use crate::versioned_constants::SierraVersion;
 
 // TODO(AlonH, 21/12/2022): Implement Display for all types that appear in errors.
 
@@ -134,4 +137,23 @@ pub enum ContractClassError {
         contract_class_version: u8,
         sierra_program_length: usize,
     },
+    #[error("The bytecode segment lengths do not match the length of the bytecode.")]
+    InvalidBytecodeSegmentLengths,
+    #[error("Invalid compiled class: {0}.")]
+    InvalidCompiledClass(String),
+    #[error("The Sierra program does not start with a valid Sierra version.")]
+    InvalidSierraVersion,
+    #[error(transparent)]
+    ProgramError(#[from] ProgramError),
+    #[error(transparent)]
+    SierraCompilationError(#[from] StarknetSierraCompilationError),
+    #[error(
+        "Sierra version {sierra_version} is not supported. Supported versions: \
+         {min_sierra_version} to {max_sierra_version}."
+    )]
+    UnsupportedSierraVersion {
+        sierra_version: SierraVersion,
+        min_sierra_version: SierraVersion,
+        max_sierra_version: SierraVersion,
+    },
 }
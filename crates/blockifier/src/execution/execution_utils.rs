@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 
-use cairo_lang_runner::casm_run::format_next_item;
 use cairo_vm::serde::deserialize_program::{
     deserialize_array_of_bigint_hex, Attribute, HintParams, Identifier, ReferenceManager,
 };
@@ -27,6 +26,7 @@ use crate::execution::entry_point::{
     EntryPointExecutionContext, EntryPointExecutionResult,
 };
 use crate::execution::errors::PostExecutionError;
+use crate::execution::panic_data::DecodedPanicData;
 use crate::execution::{deprecated_entry_point_execution, entry_point_execution};
 use crate::state::errors::StateError;
 use crate::state::state_api::State;
@@ -261,12 +261,7 @@ pub fn max_fee_for_execution_info(tx_info: &TransactionInfo) -> Felt {
 }
 
 pub fn format_panic_data(felts: &[Felt]) -> String {
-    let mut felts = felts.iter().copied();
-    let mut items = Vec::new();
-    while let Some(item) = format_next_item(&mut felts) {
-        items.push(item.quote_if_string());
-    }
-    if let [item] = &items[..] { item.clone() } else { format!("({})", items.join(", ")) }
+    DecodedPanicData::new(felts).to_string()
 }
 
 /// Returns the VM resources required for running `poseidon_hash_many` in the Starknet OS.
@@ -0,0 +1,99 @@
+use std::fmt::{Display, Formatter};
+
+use cairo_lang_runner::casm_run::format_next_item;
+use cairo_lang_runner::short_string::as_cairo_short_string;
+use itertools::Itertools;
+use serde::Serialize;
+use starknet_types_core::felt::Felt;
+
+#[cfg(test)]
+#[path = "panic_data_test.rs"]
+pub mod test;
+
+/// The error appended to the panic data of a call, when an inner call it invoked failed.
+pub const ENTRYPOINT_FAILED_ERROR: &str = "ENTRYPOINT_FAILED";
+
+/// A single decoded item of Cairo panic data.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub enum PanicItem {
+    /// A felt that does not encode a string.
+    Felt(Felt),
+    /// A felt that encodes a short string (up to 31 ASCII characters).
+    ShortString { value: Felt, string: String },
+    /// A string encoded as a `ByteArray`: the magic felt, the number of full words, the full
+    /// words, the pending word and the pending word length.
+    ByteArray(String),
+    /// The marker of a failed inner call; the panic data preceding it is the inner call's.
+    EntryPointFailed,
+}
+
+impl Display for PanicItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Felt(value) => write!(f, "{:#x}", value.to_biguint()),
+            Self::ShortString { value, string } => {
+                write!(f, "{:#x} ('{string}')", value.to_biguint())
+            }
+            Self::ByteArray(string) => write!(f, "\"{string}\""),
+            Self::EntryPointFailed => write!(
+                f,
+                "{:#x} ('{ENTRYPOINT_FAILED_ERROR}')",
+                Felt::from_bytes_be_slice(ENTRYPOINT_FAILED_ERROR.as_bytes()).to_biguint()
+            ),
+        }
+    }
+}
+
+/// The panic data of a failed Cairo 1 execution, decoded into its items.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct DecodedPanicData {
+    pub items: Vec<PanicItem>,
+}
+
+impl DecodedPanicData {
+    pub fn new(panic_data: &[Felt]) -> Self {
+        let entry_point_failed = Felt::from_bytes_be_slice(ENTRYPOINT_FAILED_ERROR.as_bytes());
+
+        let mut items = Vec::new();
+        let mut felts = panic_data.iter().copied();
+        while let Some(first_felt) = felts.clone().next() {
+            let n_remaining_felts = felts.len();
+            let Some(formatted_item) = format_next_item(&mut felts) else { break };
+            // Only a `ByteArray` spans more than a single felt.
+            let item = if n_remaining_felts - felts.len() > 1 {
+                PanicItem::ByteArray(formatted_item.get())
+            } else if first_felt == entry_point_failed {
+                PanicItem::EntryPointFailed
+            } else {
+                match as_cairo_short_string(&first_felt) {
+                    Some(string) => PanicItem::ShortString { value: first_felt, string },
+                    None => PanicItem::Felt(first_felt),
+                }
+            };
+            items.push(item);
+        }
+
+        Self { items }
+    }
+
+    /// Returns the number of failed calls the error propagated through, i.e., the number of
+    /// `ENTRYPOINT_FAILED` markers.
+    pub fn n_failed_entry_points(&self) -> usize {
+        self.items.iter().filter(|item| **item == PanicItem::EntryPointFailed).count()
+    }
+
+    /// Returns the items of the error chain, without the `ENTRYPOINT_FAILED` markers; the
+    /// innermost error comes first.
+    pub fn error_items(&self) -> impl Iterator<Item = &PanicItem> {
+        self.items.iter().filter(|item| **item != PanicItem::EntryPointFailed)
+    }
+}
+
+impl Display for DecodedPanicData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.items[..] {
+            [item] => write!(f, "{item}"),
+            items => write!(f, "({})", items.iter().join(", ")),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use cairo_lang_utils::byte_array::{BYTES_IN_WORD, BYTE_ARRAY_MAGIC};
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::felt;
+use starknet_types_core::felt::Felt;
+
+use crate::execution::errors::EntryPointExecutionError;
+use crate::execution::panic_data::{DecodedPanicData, PanicItem, ENTRYPOINT_FAILED_ERROR};
+use crate::utils::u128_from_usize;
+
+fn short_string(string: &str) -> Felt {
+    Felt::from_bytes_be_slice(string.as_bytes())
+}
+
+fn byte_array(string: &str) -> Vec<Felt> {
+    let chunks: Vec<_> = string.as_bytes().chunks(BYTES_IN_WORD).collect();
+    let (full_words, pending_word): (&[&[u8]], &[u8]) = match chunks.split_last() {
+        Some((last, rest)) if last.len() < BYTES_IN_WORD => (rest, last),
+        _ => (&chunks[..], &[]),
+    };
+
+    let mut felts =
+        vec![Felt::from_hex(BYTE_ARRAY_MAGIC).unwrap(), felt!(u128_from_usize(full_words.len()))];
+    felts.extend(full_words.iter().map(|word| Felt::from_bytes_be_slice(word)));
+    felts.push(Felt::from_bytes_be_slice(pending_word));
+    felts.push(felt!(u128_from_usize(pending_word.len())));
+    felts
+}
+
+#[rstest]
+#[case::short_string(vec![short_string("fail")], "0x6661696c ('fail')")]
+#[case::not_a_string(vec![felt!(0x800_u16)], "0x800")]
+#[case::several_items(
+    vec![short_string("fail"), felt!(0x800_u16)],
+    "(0x6661696c ('fail'), 0x800)"
+)]
+#[case::byte_array(byte_array("Hello"), "\"Hello\"")]
+#[case::long_byte_array(
+    byte_array("This error message is longer than a single byte array word."),
+    "\"This error message is longer than a single byte array word.\""
+)]
+#[case::byte_array_then_short_string(
+    [byte_array("Hello"), vec![short_string("world")]].concat(),
+    "(\"Hello\", 0x776f726c64 ('world'))"
+)]
+#[case::malformed_byte_array(
+    vec![Felt::from_hex(BYTE_ARRAY_MAGIC).unwrap(), felt!(7_u8)],
+    "(0x46a6158a16a947e5916b2a2ca68501a45e93d7110e81aa2d6438b1c57c879a3, 0x7)"
+)]
+#[case::error_chain(
+    vec![short_string("fail"), short_string(ENTRYPOINT_FAILED_ERROR)],
+    "(0x6661696c ('fail'), 0x454e545259504f494e545f4641494c4544 ('ENTRYPOINT_FAILED'))"
+)]
+fn test_format_panic_data(#[case] panic_data: Vec<Felt>, #[case] expected_string: &str) {
+    assert_eq!(DecodedPanicData::new(&panic_data).to_string(), expected_string);
+}
+
+#[test]
+fn test_decode_error_chain() {
+    let panic_data = [
+        byte_array("Inner error"),
+        vec![short_string(ENTRYPOINT_FAILED_ERROR), short_string(ENTRYPOINT_FAILED_ERROR)],
+    ]
+    .concat();
+    let decoded_panic_data = DecodedPanicData::new(&panic_data);
+
+    assert_eq!(
+        decoded_panic_data.items,
+        vec![
+            PanicItem::ByteArray("Inner error".to_string()),
+            PanicItem::EntryPointFailed,
+            PanicItem::EntryPointFailed,
+        ]
+    );
+    assert_eq!(decoded_panic_data.n_failed_entry_points(), 2);
+    assert_eq!(
+        decoded_panic_data.error_items().collect::<Vec<_>>(),
+        vec![&PanicItem::ByteArray("Inner error".to_string())]
+    );
+}
+
+#[test]
+fn test_revert_string_of_error_chain() {
+    let error_data = [
+        byte_array("Inner error"),
+        vec![short_string(ENTRYPOINT_FAILED_ERROR), short_string(ENTRYPOINT_FAILED_ERROR)],
+    ]
+    .concat();
+    let entry_point_failed = "0x454e545259504f494e545f4641494c4544 ('ENTRYPOINT_FAILED')";
+
+    assert_eq!(
+        EntryPointExecutionError::ExecutionFailed { error_data }.to_string(),
+        format!(
+            "Execution failed. Failure reason: (\"Inner error\", {entry_point_failed}, \
+             {entry_point_failed})."
+        )
+    );
+}
@@ -1,13 +1,18 @@
+use std::fmt::{Display, Formatter};
+
 use cairo_vm::types::relocatable::Relocatable;
 use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
 use cairo_vm::vm::errors::hint_errors::HintError;
 use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
 use itertools::Itertools;
+use serde::Serialize;
 use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector};
+use starknet_types_core::felt::Felt;
 
 use super::deprecated_syscalls::hint_processor::DeprecatedSyscallExecutionError;
 use super::syscalls::hint_processor::SyscallExecutionError;
 use crate::execution::errors::{ConstructorEntryPointExecutionError, EntryPointExecutionError};
+use crate::execution::panic_data::DecodedPanicData;
 use crate::transaction::errors::TransactionExecutionError;
 
 #[cfg(test)]
@@ -17,7 +22,9 @@ pub mod test;
 pub const TRACE_LENGTH_CAP: usize = 15000;
 pub const TRACE_EXTRA_CHARS_SLACK: usize = 100;
 
-enum PreambleType {
+/// The type of the call of an entry point frame.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum PreambleType {
     CallContract,
     LibraryCall,
     Constructor,
@@ -33,12 +40,13 @@ impl PreambleType {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct EntryPointErrorFrame {
-    depth: usize,
-    preamble_type: PreambleType,
-    storage_address: ContractAddress,
-    class_hash: ClassHash,
-    selector: Option<EntryPointSelector>,
+    pub depth: usize,
+    pub preamble_type: PreambleType,
+    pub storage_address: ContractAddress,
+    pub class_hash: ClassHash,
+    pub selector: Option<EntryPointSelector>,
 }
 
 impl EntryPointErrorFrame {
@@ -64,9 +72,10 @@ impl From<&EntryPointErrorFrame> for String {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct VmExceptionFrame {
-    pc: Relocatable,
-    traceback: Option<String>,
+    pub pc: Relocatable,
+    pub traceback: Option<String>,
 }
 
 impl From<&VmExceptionFrame> for String {
@@ -81,9 +90,33 @@ impl From<&VmExceptionFrame> for String {
     }
 }
 
+/// The panic data of a failed Cairo 1 execution.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct PanicFrame {
+    pub error_data: Vec<Felt>,
+    pub decoded_error_data: DecodedPanicData,
+}
+
+impl PanicFrame {
+    pub fn new(error_data: &[Felt]) -> Self {
+        Self {
+            error_data: error_data.to_vec(),
+            decoded_error_data: DecodedPanicData::new(error_data),
+        }
+    }
+}
+
+impl From<&PanicFrame> for String {
+    fn from(value: &PanicFrame) -> Self {
+        format!("Execution failed. Failure reason: {}.\n", value.decoded_error_data)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub enum Frame {
     EntryPoint(EntryPointErrorFrame),
     Vm(VmExceptionFrame),
+    Panic(PanicFrame),
     StringFrame(String),
 }
 
@@ -92,6 +125,7 @@ impl From<&Frame> for String {
         match value {
             Frame::EntryPoint(entry_point_frame) => entry_point_frame.into(),
             Frame::Vm(vm_exception_frame) => vm_exception_frame.into(),
+            Frame::Panic(panic_frame) => panic_frame.into(),
             Frame::StringFrame(error) => error.clone(),
         }
     }
@@ -109,37 +143,89 @@ impl From<VmExceptionFrame> for Frame {
     }
 }
 
+impl From<PanicFrame> for Frame {
+    fn from(value: PanicFrame) -> Self {
+        Frame::Panic(value)
+    }
+}
+
 impl From<String> for Frame {
     fn from(value: String) -> Self {
         Frame::StringFrame(value)
     }
 }
 
-#[derive(Default)]
+/// The top-level description of the failure, preceding the frames of the error stack.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub enum ErrorStackHeader {
+    Constructor,
+    Execution,
+    Validation,
+    #[default]
+    None,
+}
+
+impl Display for ErrorStackHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Constructor => "Contract constructor execution has failed:\n",
+                Self::Execution => "Transaction execution has failed:\n",
+                Self::Validation => "Transaction validation has failed:\n",
+                Self::None => "",
+            }
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct ErrorStack {
+    header: ErrorStackHeader,
     stack: Vec<Frame>,
 }
 
-impl From<ErrorStack> for String {
-    fn from(value: ErrorStack) -> Self {
-        let error_stack_str = value.stack.iter().map(String::from).join("\n");
+impl Display for ErrorStack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let error_stack_str = self.stack.iter().map(String::from).join("\n");
 
         // When the trace string is too long, trim it in a way that keeps both the beginning and
         // end.
-        if error_stack_str.len() > TRACE_LENGTH_CAP + TRACE_EXTRA_CHARS_SLACK {
+        let error_stack_str = if error_stack_str.len() > TRACE_LENGTH_CAP + TRACE_EXTRA_CHARS_SLACK
+        {
             error_stack_str[..(TRACE_LENGTH_CAP / 2)].to_string()
                 + "\n\n...\n\n"
                 + &error_stack_str[(error_stack_str.len() - TRACE_LENGTH_CAP / 2)..]
         } else {
             error_stack_str
-        }
+        };
+        write!(f, "{}{}", self.header, error_stack_str)
+    }
+}
+
+impl From<ErrorStack> for String {
+    fn from(value: ErrorStack) -> Self {
+        value.to_string()
     }
 }
 
 impl ErrorStack {
+    pub fn new(header: ErrorStackHeader) -> Self {
+        Self { header, stack: Vec::new() }
+    }
+
     pub fn push(&mut self, frame: Frame) {
         self.stack.push(frame);
     }
+
+    pub fn header(&self) -> ErrorStackHeader {
+        self.header
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.stack
+    }
 }
 
 /// Extracts the error trace from a `TransactionExecutionError`. This is a top level function.
@@ -150,13 +236,21 @@ pub fn gen_transaction_execution_error_trace(error: &TransactionExecutionError)
             class_hash,
             storage_address,
             selector,
-        }
-        | TransactionExecutionError::ValidateTransactionError {
+        } => gen_error_trace_from_entry_point_error(
+            ErrorStackHeader::Execution,
+            error,
+            storage_address,
+            class_hash,
+            Some(selector),
+            PreambleType::CallContract,
+        ),
+        TransactionExecutionError::ValidateTransactionError {
             error,
             class_hash,
             storage_address,
             selector,
         } => gen_error_trace_from_entry_point_error(
+            ErrorStackHeader::Validation,
             error,
             storage_address,
             class_hash,
@@ -171,6 +265,7 @@ pub fn gen_transaction_execution_error_trace(error: &TransactionExecutionError)
                 constructor_selector,
             },
         ) => gen_error_trace_from_entry_point_error(
+            ErrorStackHeader::Constructor,
             error,
             storage_address,
             class_hash,
@@ -186,15 +281,34 @@ pub fn gen_transaction_execution_error_trace(error: &TransactionExecutionError)
     }
 }
 
+/// Extracts the error trace from an `EntryPointExecutionError` of a call that is not wrapped by a
+/// transaction (e.g., a view call). This is a top level function.
+pub fn gen_entry_point_execution_error_trace(
+    error: &EntryPointExecutionError,
+    storage_address: &ContractAddress,
+    class_hash: &ClassHash,
+    entry_point_selector: &EntryPointSelector,
+) -> ErrorStack {
+    gen_error_trace_from_entry_point_error(
+        ErrorStackHeader::None,
+        error,
+        storage_address,
+        class_hash,
+        Some(entry_point_selector),
+        PreambleType::CallContract,
+    )
+}
+
 /// Generate error stack from top-level entry point execution error.
 fn gen_error_trace_from_entry_point_error(
+    header: ErrorStackHeader,
     error: &EntryPointExecutionError,
     storage_address: &ContractAddress,
     class_hash: &ClassHash,
     entry_point_selector: Option<&EntryPointSelector>,
     preamble_type: PreambleType,
 ) -> ErrorStack {
-    let mut error_stack: ErrorStack = ErrorStack::default();
+    let mut error_stack = ErrorStack::new(header);
     let depth = 0;
     error_stack.push(
         EntryPointErrorFrame {
@@ -433,6 +547,9 @@ fn extract_entry_point_execution_error_into_stack_trace(
         EntryPointExecutionError::CairoRunError(cairo_run_error) => {
             extract_cairo_run_error_into_stack_trace(error_stack, depth, cairo_run_error)
         }
+        EntryPointExecutionError::ExecutionFailed { error_data } => {
+            error_stack.push(PanicFrame::new(error_data).into())
+        }
         _ => error_stack.push(format!("{}\n", entry_point_error).into()),
     }
 }
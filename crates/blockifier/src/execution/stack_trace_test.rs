@@ -1,3 +1,4 @@
+use assert_matches::assert_matches;
 use pretty_assertions::assert_eq;
 use regex::Regex;
 use rstest::rstest;
@@ -11,6 +12,7 @@ use starknet_api::{calldata, felt};
 use crate::abi::abi_utils::selector_from_name;
 use crate::abi::constants::CONSTRUCTOR_ENTRY_POINT_NAME;
 use crate::context::{BlockContext, ChainInfo};
+use crate::execution::stack_trace::{ErrorStackHeader, Frame, PanicFrame, PreambleType};
 use crate::invoke_tx_args;
 use crate::test_utils::contracts::FeatureContract;
 use crate::test_utils::initial_test_state::{fund_account, test_state};
@@ -20,6 +22,7 @@ use crate::transaction::constants::{
     DEPLOY_CONTRACT_FUNCTION_ENTRY_POINT_NAME, EXECUTE_ENTRY_POINT_NAME, FELT_TRUE,
     VALIDATE_DECLARE_ENTRY_POINT_NAME, VALIDATE_DEPLOY_ENTRY_POINT_NAME, VALIDATE_ENTRY_POINT_NAME,
 };
+use crate::transaction::objects::RevertError;
 use crate::transaction::test_utils::{
     account_invoke_tx, block_context, create_account_tx_for_validate_test_nonce_0,
     max_resource_bounds, run_invoke_tx, FaultyAccountTxCreatorArgs, INVALID,
@@ -135,6 +138,75 @@ Execution failed. Failure reason: 0x6661696c ('fail').
     assert_eq!(tx_execution_error.to_string(), expected_trace);
 }
 
+#[rstest]
+fn test_revert_error_frames(block_context: BlockContext) {
+    let chain_info = &block_context.chain_info;
+    let account = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1);
+    let mut state = test_state(chain_info, BALANCE, &[(account, 1), (test_contract, 2)]);
+    let account_address = account.get_instance_address(0);
+    let test_contract_address = test_contract.get_instance_address(0);
+    let test_contract_address_2 = test_contract.get_instance_address(1);
+
+    // Nest calls: __execute__ -> test_call_contract -> fail.
+    let calldata = create_calldata(
+        test_contract_address,
+        "test_call_contract",
+        &[*test_contract_address_2.0.key(), selector_from_name("fail").0, felt!(0_u8)],
+    );
+    let tx_execution_info = run_invoke_tx(
+        &mut state,
+        &block_context,
+        invoke_tx_args! {
+            sender_address: account_address,
+            calldata,
+            version: TransactionVersion::ONE,
+        },
+    )
+    .unwrap();
+    let revert_error = tx_execution_info.revert_error.unwrap();
+    let RevertError::Execution(error_stack) = &revert_error else {
+        panic!("Expected an execution revert error.");
+    };
+    assert_eq!(error_stack.header(), ErrorStackHeader::Execution);
+
+    // The entry point frames are kept as data.
+    let entry_point_frames: Vec<_> = error_stack
+        .frames()
+        .iter()
+        .filter_map(|frame| match frame {
+            Frame::EntryPoint(frame) => {
+                Some((frame.depth, frame.preamble_type, frame.storage_address))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        entry_point_frames,
+        vec![
+            (0, PreambleType::CallContract, account_address),
+            (1, PreambleType::CallContract, test_contract_address),
+            (2, PreambleType::CallContract, test_contract_address_2),
+        ]
+    );
+    let fail_felt = felt!("0x6661696c");
+    assert_matches!(
+        error_stack.frames().last(),
+        Some(Frame::Panic(PanicFrame { error_data, decoded_error_data }))
+            if *error_data == vec![fail_felt]
+                && decoded_error_data.to_string() == "0x6661696c ('fail')"
+    );
+
+    // The revert error is rendered in the trace string format, and is serializable.
+    let revert_error_str = revert_error.to_string();
+    assert!(
+        revert_error_str.starts_with("Transaction execution has failed:\n0: Error in the called")
+    );
+    assert!(revert_error_str.ends_with("Execution failed. Failure reason: 0x6661696c ('fail').\n"));
+    let serialized_revert_error = serde_json::to_value(&revert_error).unwrap();
+    assert_eq!(serialized_revert_error["Execution"]["header"], "Execution");
+}
+
 #[rstest]
 #[case(CairoVersion::Cairo0, "invoke_call_chain", "Couldn't compute operand op0. Unknown value for memory cell 1:37", (1081_u16, 1127_u16))]
 #[case(CairoVersion::Cairo0, "fail", "An ASSERT_EQ instruction failed: 1 != 0.", (1184_u16, 1135_u16))]
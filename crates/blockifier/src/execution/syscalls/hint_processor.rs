@@ -264,6 +264,18 @@ impl<'a> SyscallHintProcessor<'a> {
         self.execution_mode() == ExecutionMode::Validate
     }
 
+    /// Returns an error if the syscall is run in view mode.
+    pub fn verify_not_in_view_mode(&self, syscall_name: &str) -> SyscallResult<()> {
+        if self.execution_mode() == ExecutionMode::View {
+            return Err(SyscallExecutionError::InvalidSyscallInExecutionMode {
+                syscall_name: syscall_name.to_string(),
+                execution_mode: self.execution_mode(),
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn verify_syscall_ptr(&self, actual_ptr: Relocatable) -> SyscallResult<()> {
         if actual_ptr != self.syscall_ptr {
             return Err(SyscallExecutionError::BadSyscallPointer {
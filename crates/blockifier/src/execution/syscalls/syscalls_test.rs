@@ -338,6 +338,12 @@ fn verify_compiler_version(contract: FeatureContract, expected_version: &str) {
     false,
     true;
     "Execute execution mode: block info should be as usual. Transaction V3. Query.")]
+#[test_case(
+    ExecutionMode::View,
+    TransactionVersion::THREE,
+    false,
+    false;
+    "View execution mode: block info should be as usual. Transaction V3.")]
 fn test_get_execution_info(
     execution_mode: ExecutionMode,
     mut version: TransactionVersion,
@@ -359,7 +365,7 @@ fn test_get_execution_info(
             felt!(CURRENT_BLOCK_TIMESTAMP_FOR_VALIDATE),
             Felt::ZERO,
         ],
-        ExecutionMode::Execute => [
+        ExecutionMode::Execute | ExecutionMode::View => [
             felt!(CURRENT_BLOCK_NUMBER),    // Block number.
             felt!(CURRENT_BLOCK_TIMESTAMP), // Block timestamp.
             Felt::from_hex(TEST_SEQUENCER_ADDRESS).unwrap(),
@@ -500,14 +506,12 @@ fn test_get_execution_info(
         ..trivial_external_entry_point_with_address(test_contract_address)
     };
 
-    let result = match execution_mode {
-        ExecutionMode::Validate => {
-            entry_point_call.execute_directly_given_tx_info_in_validate_mode(state, tx_info, false)
-        }
-        ExecutionMode::Execute => {
-            entry_point_call.execute_directly_given_tx_info(state, tx_info, false)
-        }
-    };
+    let result = entry_point_call.execute_directly_given_tx_info_in_mode(
+        state,
+        tx_info,
+        execution_mode,
+        false,
+    );
 
     assert!(!result.unwrap().execution.failed);
 }
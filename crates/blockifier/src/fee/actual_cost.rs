@@ -3,7 +3,8 @@ use starknet_api::core::ContractAddress;
 use starknet_api::transaction::Fee;
 
 use crate::context::TransactionContext;
-use crate::execution::call_info::CallInfo;
+use crate::execution::call_info::{saturating_sub_resources, CallInfo};
+use crate::fee::fee_utils::get_tip_by_gas_vector;
 use crate::state::cached_state::StateChanges;
 use crate::transaction::account_transaction::AccountTransaction;
 use crate::transaction::objects::{
@@ -29,6 +30,7 @@ struct TransactionReceiptParameters<'a, T: Iterator<Item = &'a CallInfo> + Clone
     execution_resources: &'a ExecutionResources,
     tx_type: TransactionType,
     reverted_steps: usize,
+    reverted_call_info: Option<&'a CallInfo>,
 }
 
 // TODO(Gilad): Use everywhere instead of passing the `actual_{fee,resources}` tuple, which often
@@ -36,6 +38,8 @@ struct TransactionReceiptParameters<'a, T: Iterator<Item = &'a CallInfo> + Clone
 #[derive(Default, Debug, PartialEq)]
 pub struct TransactionReceipt {
     pub fee: Fee,
+    // The part of the fee paid as a tip.
+    pub tip: Fee,
     pub gas: GasVector,
     pub da_gas: GasVector,
     pub resources: TransactionResources,
@@ -57,8 +61,20 @@ impl TransactionReceipt {
             execution_resources,
             tx_type,
             reverted_steps,
+            reverted_call_info,
         } = tx_receipt_params;
 
+        // The calls charged by Sierra gas (as L2 gas) are not charged by their VM resources; this
+        // applies to the reverted execution as well.
+        let (sierra_gas, sierra_gas_vm_resources) =
+            CallInfo::summarize_sierra_gas_usage(call_infos.clone());
+        let (reverted_sierra_gas, reverted_sierra_gas_vm_resources) =
+            CallInfo::summarize_sierra_gas_usage(reverted_call_info.into_iter());
+        let reverted_steps =
+            reverted_steps.saturating_sub(reverted_sierra_gas_vm_resources.n_steps);
+        let mut execution_resources = execution_resources.clone();
+        saturating_sub_resources(&mut execution_resources, &sierra_gas_vm_resources);
+
         let starknet_resources = StarknetResources::new(
             calldata_length,
             signature_length,
@@ -68,7 +84,7 @@ impl TransactionReceipt {
             call_infos,
         );
 
-        let cairo_resources = (execution_resources
+        let cairo_resources = (&execution_resources
             + &tx_context.block_context.versioned_constants.get_additional_os_tx_resources(
                 tx_type,
                 &starknet_resources,
@@ -80,6 +96,7 @@ impl TransactionReceipt {
             starknet_resources,
             vm_resources: cairo_resources,
             n_reverted_steps: reverted_steps,
+            sierra_gas: sierra_gas + reverted_sierra_gas,
         };
 
         let gas = tx_resources.to_gas_vector(
@@ -88,16 +105,21 @@ impl TransactionReceipt {
         )?;
 
         // L1 handler transactions are not charged an L2 fee but it is compared to the L1 fee.
-        let fee = if tx_context.tx_info.enforce_fee()? || tx_type == TransactionType::L1Handler {
-            tx_context.tx_info.get_fee_by_gas_vector(&tx_context.block_context.block_info, gas)
+        let compute_fee =
+            tx_context.tx_info.enforce_fee()? || tx_type == TransactionType::L1Handler;
+        let (fee, tip) = if compute_fee {
+            let base_fee =
+                tx_context.tx_info.get_fee_by_gas_vector(&tx_context.block_context.block_info, gas);
+            let tip = get_tip_by_gas_vector(tx_context, &gas);
+            (Fee(base_fee.0.saturating_add(tip.0)), tip)
         } else {
-            Fee(0)
+            (Fee(0), Fee(0))
         };
         let da_gas = tx_resources
             .starknet_resources
             .get_state_changes_cost(tx_context.block_context.block_info.use_kzg_da);
 
-        Ok(Self { resources: tx_resources, gas, da_gas, fee })
+        Ok(Self { resources: tx_resources, gas, da_gas, fee, tip })
     }
 
     /// Computes actual cost of an L1 handler transaction.
@@ -120,10 +142,12 @@ impl TransactionReceipt {
             execution_resources,
             tx_type: TransactionType::L1Handler,
             reverted_steps: 0,
+            reverted_call_info: None,
         })
     }
 
-    /// Computes actual cost of an account transaction.
+    /// Computes actual cost of an account transaction. The steps and the (partial) call info of a
+    /// reverted execution, if any, are given separately.
     pub fn from_account_tx<'a>(
         account_tx: &'a AccountTransaction,
         tx_context: &'a TransactionContext,
@@ -131,6 +155,7 @@ impl TransactionReceipt {
         execution_resources: &'a ExecutionResources,
         call_infos: impl Iterator<Item = &'a CallInfo> + Clone,
         reverted_steps: usize,
+        reverted_call_info: Option<&'a CallInfo>,
     ) -> TransactionExecutionResult<Self> {
         Self::from_params(TransactionReceiptParameters {
             tx_context,
@@ -144,6 +169,7 @@ impl TransactionReceipt {
             execution_resources,
             tx_type: account_tx.tx_type(),
             reverted_steps,
+            reverted_call_info,
         })
     }
 }
@@ -1,21 +1,35 @@
+use serde::Serialize;
 use starknet_api::transaction::Fee;
 use starknet_types_core::felt::Felt;
 use thiserror::Error;
 
 use crate::context::TransactionContext;
 use crate::fee::actual_cost::TransactionReceipt;
-use crate::fee::fee_utils::{get_balance_and_if_covers_fee, get_fee_by_gas_vector};
-use crate::fee::gas_usage::compute_discounted_gas_from_gas_vector;
+use crate::fee::fee_utils::{
+    get_balance_and_if_covers_fee, get_fee_by_gas_vector, get_tip_by_gas_vector,
+};
+use crate::fee::gas_usage::{
+    compute_discounted_gas_from_gas_vector, compute_l1_data_gas_allowance,
+};
 use crate::state::state_api::StateReader;
 use crate::transaction::errors::TransactionExecutionError;
 use crate::transaction::objects::{
     FeeType, GasVector, TransactionExecutionResult, TransactionInfo,
 };
 
-#[derive(Clone, Copy, Debug, Error)]
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq, Serialize)]
 pub enum FeeCheckError {
     #[error("Insufficient max L1 gas: max amount: {max_amount}, actual used: {actual_amount}.")]
     MaxL1GasAmountExceeded { max_amount: u128, actual_amount: u128 },
+    /// The L1 data gas is paid out of the L1 gas bound; the max amount is the L1 data gas the
+    /// bound covers on top of the L1 gas used.
+    #[error(
+        "Insufficient max L1 gas for L1 data gas: max amount: {max_amount}, actual used: \
+         {actual_amount}."
+    )]
+    MaxL1DataGasAmountExceeded { max_amount: u128, actual_amount: u128 },
+    #[error("Insufficient max L2 gas: max amount: {max_amount}, actual used: {actual_amount}.")]
+    MaxL2GasAmountExceeded { max_amount: u128, actual_amount: u128 },
     #[error("Insufficient max fee: max fee: {}, actual fee: {}.", max_fee.0, actual_fee.0)]
     MaxFeeExceeded { max_fee: Fee, actual_fee: Fee },
     #[error(
@@ -26,14 +40,16 @@ pub enum FeeCheckError {
 }
 
 /// This struct holds the result of fee checks: recommended fee to charge (useful in post-execution
-/// revert flow) and an error if the check failed.
+/// revert flow), the part of it paid as a tip, and an error if the check failed.
 struct FeeCheckReport {
     recommended_fee: Fee,
+    recommended_tip: Fee,
     error: Option<FeeCheckError>,
 }
 
 pub trait FeeCheckReportFields {
     fn recommended_fee(&self) -> Fee;
+    fn recommended_tip(&self) -> Fee;
     fn error(&self) -> Option<FeeCheckError>;
 }
 
@@ -42,44 +58,58 @@ impl FeeCheckReportFields for FeeCheckReport {
         self.recommended_fee
     }
 
+    fn recommended_tip(&self) -> Fee {
+        self.recommended_tip
+    }
+
     fn error(&self) -> Option<FeeCheckError> {
         self.error
     }
 }
 
-// TODO(Aner, 23/1/24): Update this struct to check data gas bounds as well as other bounds.
 impl FeeCheckReport {
-    pub fn success_report(actual_fee: Fee) -> Self {
-        Self { recommended_fee: actual_fee, error: None }
+    pub fn success_report(tx_receipt: &TransactionReceipt) -> Self {
+        Self { recommended_fee: tx_receipt.fee, recommended_tip: tx_receipt.tip, error: None }
     }
 
     /// Given a fee error and the current context, constructs and returns a report.
     pub fn from_fee_check_error(
-        actual_fee: Fee,
+        tx_receipt: &TransactionReceipt,
         error: FeeCheckError,
         tx_context: &TransactionContext,
     ) -> TransactionExecutionResult<Self> {
-        let recommended_fee = match error {
+        let (recommended_fee, recommended_tip) = match error {
             // If the error is insufficient balance, the recommended fee is the actual fee.
             // This recommendation assumes (a) the pre-validation checks were applied and pass (i.e.
             // the sender initially could cover the resource bounds), and (b) the actual resources
             // are within the resource bounds set by the sender.
-            FeeCheckError::InsufficientFeeTokenBalance { .. } => actual_fee,
-            // If the error is resource overdraft, the recommended fee is the resource bounds.
+            FeeCheckError::InsufficientFeeTokenBalance { .. } => (tx_receipt.fee, tx_receipt.tip),
+            // If the error is resource overdraft, the recommended fee is the resource bounds (with
+            // the tip on the L2 gas bound).
             // If the transaction passed pre-validation checks (i.e. balance initially covered the
             // resource bounds), the sender should be able to pay this fee.
-            FeeCheckError::MaxFeeExceeded { .. } | FeeCheckError::MaxL1GasAmountExceeded { .. } => {
-                match &tx_context.tx_info {
-                    TransactionInfo::Current(info) => get_fee_by_gas_vector(
+            FeeCheckError::MaxFeeExceeded { .. }
+            | FeeCheckError::MaxL1GasAmountExceeded { .. }
+            | FeeCheckError::MaxL1DataGasAmountExceeded { .. }
+            | FeeCheckError::MaxL2GasAmountExceeded { .. } => match &tx_context.tx_info {
+                TransactionInfo::Current(info) => {
+                    let mut max_gas_vector =
+                        GasVector::from_l1_gas(info.l1_resource_bounds()?.max_amount.into());
+                    if tx_context.block_context.versioned_constants.enable_l2_gas {
+                        max_gas_vector.l2_gas = info.l2_resource_bounds().max_amount.into();
+                    }
+                    let base_fee = get_fee_by_gas_vector(
                         &tx_context.block_context.block_info,
-                        GasVector::from_l1_gas(info.l1_resource_bounds()?.max_amount.into()),
+                        max_gas_vector,
                         &FeeType::Strk,
-                    ),
-                    TransactionInfo::Deprecated(context) => context.max_fee,
+                    );
+                    let tip = get_tip_by_gas_vector(tx_context, &max_gas_vector);
+                    (Fee(base_fee.0.saturating_add(tip.0)), tip)
                 }
-            }
+                TransactionInfo::Deprecated(context) => (context.max_fee, Fee(0)),
+            },
         };
-        Ok(Self { recommended_fee, error: Some(error) })
+        Ok(Self { recommended_fee, recommended_tip, error: Some(error) })
     }
 
     /// If the actual cost exceeds the resource bounds on the transaction, returns a fee check
@@ -93,24 +123,38 @@ impl FeeCheckReport {
 
         // First, compare the actual resources used against the upper bound(s) defined by the
         // sender.
-        // TODO(Aner, 21/01/24) modify for 4844 (include check for blob_gas).
         match tx_info {
             TransactionInfo::Current(context) => {
                 // Check L1 gas limit.
                 let max_l1_gas = context.l1_resource_bounds()?.max_amount.into();
-
-                // TODO(Dori, 1/7/2024): When data gas limit is added (and enforced) in resource
-                //   bounds, check it here as well (separately, with a different error variant if
-                //   limit exceeded).
-                let total_discounted_gas_used =
-                    compute_discounted_gas_from_gas_vector(gas, tx_context);
-
-                if total_discounted_gas_used > max_l1_gas {
+                if gas.l1_gas > max_l1_gas {
                     return Err(FeeCheckError::MaxL1GasAmountExceeded {
                         max_amount: max_l1_gas,
-                        actual_amount: total_discounted_gas_used,
+                        actual_amount: gas.l1_gas,
                     })?;
                 }
+
+                // Check L1 data gas limit, which is the part of the L1 gas limit left after the L1
+                // gas used.
+                if compute_discounted_gas_from_gas_vector(gas, tx_context) > max_l1_gas {
+                    let max_l1_data_gas =
+                        compute_l1_data_gas_allowance(max_l1_gas, gas.l1_gas, tx_context);
+                    return Err(FeeCheckError::MaxL1DataGasAmountExceeded {
+                        max_amount: max_l1_data_gas,
+                        actual_amount: gas.l1_data_gas,
+                    })?;
+                }
+
+                // Check L2 gas limit.
+                if tx_context.block_context.versioned_constants.enable_l2_gas {
+                    let max_l2_gas = context.l2_resource_bounds().max_amount.into();
+                    if gas.l2_gas > max_l2_gas {
+                        return Err(FeeCheckError::MaxL2GasAmountExceeded {
+                            max_amount: max_l2_gas,
+                            actual_amount: gas.l2_gas,
+                        })?;
+                    }
+                }
             }
             TransactionInfo::Deprecated(context) => {
                 // Check max fee.
@@ -147,6 +191,10 @@ macro_rules! impl_report_fields {
                 self.0.recommended_fee()
             }
 
+            fn recommended_tip(&self) -> Fee {
+                self.0.recommended_tip()
+            }
+
             fn error(&self) -> Option<FeeCheckError> {
                 self.0.error()
             }
@@ -186,11 +234,9 @@ impl PostExecutionReport {
         tx_receipt: &TransactionReceipt,
         charge_fee: bool,
     ) -> TransactionExecutionResult<Self> {
-        let TransactionReceipt { fee, .. } = tx_receipt;
-
         // If fee is not enforced, no need to check post-execution.
         if !charge_fee || !tx_context.tx_info.enforce_fee()? {
-            return Ok(Self(FeeCheckReport::success_report(*fee)));
+            return Ok(Self(FeeCheckReport::success_report(tx_receipt)));
         }
 
         // First, compare the actual resources used against the upper bound(s) defined by the
@@ -210,7 +256,7 @@ impl PostExecutionReport {
                     // Found an error; set the recommended fee based on the error variant and
                     // current context, and return the report.
                     return Ok(Self(FeeCheckReport::from_fee_check_error(
-                        *fee,
+                        tx_receipt,
                         fee_check_error,
                         tx_context,
                     )?));
@@ -219,6 +265,6 @@ impl PostExecutionReport {
             }
         }
 
-        Ok(Self(FeeCheckReport::success_report(*fee)))
+        Ok(Self(FeeCheckReport::success_report(tx_receipt)))
     }
 }
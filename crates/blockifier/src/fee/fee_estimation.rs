@@ -0,0 +1,127 @@
+use num_rational::Ratio;
+use starknet_api::transaction::{Fee, Resource, ResourceBounds, ResourceBoundsMapping};
+
+use crate::blockifier::block::GasPrices;
+use crate::context::TransactionContext;
+use crate::fee::fee_utils::{get_fee_by_gas_vector, get_tip_by_gas_vector};
+use crate::fee::gas_usage::compute_discounted_gas_by_fee_type;
+use crate::transaction::objects::{
+    FeeType, GasVector, HasRelatedFeeType, TransactionExecutionInfo, TransactionExecutionResult,
+};
+
+#[cfg(test)]
+#[path = "fee_estimation_test.rs"]
+pub mod test;
+
+/// Configuration of a fee estimation.
+/// The margins are relative; for example, a margin of 1/2 recommends bounds 50% above the
+/// estimated values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeEstimationConfig {
+    pub skip_validate: bool,
+    pub gas_amount_margin: Ratio<u128>,
+    pub gas_price_margin: Ratio<u128>,
+}
+
+impl Default for FeeEstimationConfig {
+    fn default() -> Self {
+        Self {
+            skip_validate: false,
+            gas_amount_margin: Ratio::new(1, 2),
+            gas_price_margin: Ratio::new(1, 2),
+        }
+    }
+}
+
+/// The estimated resources and fee of a transaction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeEstimation {
+    pub l1_gas: u128,
+    pub l1_data_gas: u128,
+    // Zero if L2 gas is not enabled.
+    pub l2_gas: u128,
+    pub l1_gas_price: u128,
+    pub l1_data_gas_price: u128,
+    pub l2_gas_price: u128,
+    pub overall_fee: Fee,
+    pub fee_type: FeeType,
+    // Bounds for a V3 (STRK) transaction; `None` for transactions paying in ETH, which have no
+    // resource bounds. Since no L1 data gas bound is enforced, the L1 data gas is converted to
+    // (discounted) L1 gas.
+    pub recommended_resource_bounds: Option<ResourceBoundsMapping>,
+    pub revert_error: Option<String>,
+}
+
+impl FeeEstimation {
+    pub fn new(
+        tx_context: &TransactionContext,
+        tx_execution_info: &TransactionExecutionInfo,
+        config: &FeeEstimationConfig,
+    ) -> TransactionExecutionResult<Self> {
+        let block_info = &tx_context.block_context.block_info;
+        let gas_vector = tx_execution_info.transaction_receipt.gas;
+        let GasVector { l1_gas, l1_data_gas, l2_gas } = gas_vector;
+        // Note: the fee type is determined by the transaction version, regardless of the query
+        // bit. The receipt's fee cannot be used, since it is zero for transactions with no fee
+        // bounds.
+        let fee_type = tx_context.tx_info.fee_type();
+        let gas_prices = &block_info.gas_prices;
+        // The tip is paid on top of the fee, as in the actual cost of the transaction.
+        let base_fee = get_fee_by_gas_vector(block_info, gas_vector, &fee_type);
+        let tip = get_tip_by_gas_vector(tx_context, &gas_vector);
+        let recommended_resource_bounds = match fee_type {
+            FeeType::Strk => Some(recommended_resource_bounds(&gas_vector, gas_prices, config)?),
+            FeeType::Eth => None,
+        };
+
+        Ok(Self {
+            l1_gas,
+            l1_data_gas,
+            l2_gas,
+            l1_gas_price: gas_prices.get_gas_price_by_fee_type(&fee_type).into(),
+            l1_data_gas_price: gas_prices.get_data_gas_price_by_fee_type(&fee_type).into(),
+            l2_gas_price: gas_prices.get_l2_gas_price_by_fee_type(&fee_type).into(),
+            overall_fee: Fee(base_fee.0.saturating_add(tip.0)),
+            fee_type,
+            recommended_resource_bounds,
+            revert_error: tx_execution_info.revert_error.as_ref().map(ToString::to_string),
+        })
+    }
+}
+
+/// Returns the resource bounds that cover the given gas usage, with the configured margins, for a
+/// transaction paying in STRK.
+fn recommended_resource_bounds(
+    gas_vector: &GasVector,
+    gas_prices: &GasPrices,
+    config: &FeeEstimationConfig,
+) -> TransactionExecutionResult<ResourceBoundsMapping> {
+    let l1_gas_price = gas_prices.get_gas_price_by_fee_type(&FeeType::Strk);
+    let discounted_l1_gas =
+        compute_discounted_gas_by_fee_type(gas_vector, gas_prices, &FeeType::Strk);
+
+    let l1_bounds = ResourceBounds {
+        max_amount: u64::try_from(apply_margin(discounted_l1_gas, config.gas_amount_margin))?,
+        max_price_per_unit: apply_margin(l1_gas_price.into(), config.gas_price_margin),
+    };
+    // No L2 gas is needed if it is not metered.
+    let l2_bounds = match gas_vector.l2_gas {
+        0 => ResourceBounds { max_amount: 0, max_price_per_unit: 0 },
+        l2_gas => ResourceBounds {
+            max_amount: u64::try_from(apply_margin(l2_gas, config.gas_amount_margin))?,
+            max_price_per_unit: apply_margin(
+                gas_prices.get_l2_gas_price_by_fee_type(&FeeType::Strk).into(),
+                config.gas_price_margin,
+            ),
+        },
+    };
+
+    Ok(ResourceBoundsMapping::try_from(vec![
+        (Resource::L1Gas, l1_bounds),
+        (Resource::L2Gas, l2_bounds),
+    ])?)
+}
+
+fn apply_margin(value: u128, margin: Ratio<u128>) -> u128 {
+    (Ratio::from_integer(value) * (Ratio::from_integer(1) + margin)).ceil().to_integer()
+}
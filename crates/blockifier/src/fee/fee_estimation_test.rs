@@ -0,0 +1,148 @@
+use num_rational::Ratio;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::core::Nonce;
+use starknet_api::transaction::{
+    Fee, Resource, ResourceBounds, ResourceBoundsMapping, Tip, TransactionVersion,
+};
+use starknet_types_core::felt::Felt;
+
+use crate::context::BlockContext;
+use crate::fee::fee_estimation::FeeEstimationConfig;
+use crate::fee::fee_utils::get_fee_by_gas_vector;
+use crate::invoke_tx_args;
+use crate::state::state_api::StateReader;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::initial_test_state::test_state;
+use crate::test_utils::{
+    create_calldata, CairoVersion, BALANCE, DEFAULT_STRK_L2_GAS_PRICE, MAX_L1_GAS_AMOUNT,
+    MAX_L1_GAS_PRICE,
+};
+use crate::transaction::objects::{FeeType, GasVector};
+use crate::transaction::test_utils::{account_invoke_tx, block_context};
+use crate::transaction::transactions::ExecutableTransaction;
+
+#[rstest]
+#[case::v1(TransactionVersion::ONE, FeeType::Eth)]
+#[case::v3(TransactionVersion::THREE, FeeType::Strk)]
+fn test_estimate_fee(
+    mut block_context: BlockContext,
+    #[case] version: TransactionVersion,
+    #[case] expected_fee_type: FeeType,
+    #[values(false, true)] only_query: bool,
+    #[values(false, true)] enable_l2_gas: bool,
+) {
+    block_context.versioned_constants.enable_l2_gas = enable_l2_gas;
+    let account_contract = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1);
+    let mut state = test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(account_contract, 1), (test_contract, 1)],
+    );
+    let account_address = account_contract.get_instance_address(0);
+    let calldata = create_calldata(
+        test_contract.get_instance_address(0),
+        "test_storage_read_write",
+        &[Felt::from(1993_u16), Felt::from(93_u8)],
+    );
+
+    // Estimate a transaction with no fee bounds.
+    let tx = account_invoke_tx(invoke_tx_args! {
+        sender_address: account_address,
+        calldata: calldata.clone(),
+        version,
+        only_query,
+    });
+    let config = FeeEstimationConfig { gas_amount_margin: Ratio::new(1, 10), ..Default::default() };
+    let estimation = tx.estimate_fee(&mut state, &block_context, &config).unwrap();
+
+    // The estimation does not modify the state.
+    assert_eq!(state.get_nonce_at(account_address).unwrap(), Nonce(Felt::ZERO));
+
+    // L2 gas is only metered if enabled.
+    assert_eq!(estimation.l2_gas > 0, enable_l2_gas);
+    let gas_vector = GasVector {
+        l1_gas: estimation.l1_gas,
+        l1_data_gas: estimation.l1_data_gas,
+        l2_gas: estimation.l2_gas,
+    };
+    assert_eq!(estimation.fee_type, expected_fee_type);
+    assert_eq!(
+        estimation.overall_fee,
+        get_fee_by_gas_vector(&block_context.block_info, gas_vector, &expected_fee_type)
+    );
+    assert!(estimation.overall_fee > Fee(0));
+    assert_eq!(estimation.revert_error, None);
+
+    // Resource bounds are only recommended for transactions paying in STRK.
+    let Some(resource_bounds) = estimation.recommended_resource_bounds else {
+        assert_eq!(expected_fee_type, FeeType::Eth);
+        return;
+    };
+    assert_eq!(expected_fee_type, FeeType::Strk);
+
+    // The recommended bounds are sufficient to execute the transaction.
+    let tx = account_invoke_tx(invoke_tx_args! {
+        sender_address: account_address,
+        calldata,
+        version: TransactionVersion::THREE,
+        resource_bounds,
+    });
+    let tx_execution_info = tx.execute(&mut state, &block_context, true, true).unwrap();
+    assert!(!tx_execution_info.is_reverted());
+}
+
+#[rstest]
+fn test_estimate_fee_with_tip(mut block_context: BlockContext) {
+    block_context.versioned_constants.enable_l2_gas = true;
+    let account_contract = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1);
+    let mut state = test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(account_contract, 1), (test_contract, 1)],
+    );
+    let account_address = account_contract.get_instance_address(0);
+    let calldata = create_calldata(
+        test_contract.get_instance_address(0),
+        "test_storage_read_write",
+        &[Felt::from(1993_u16), Felt::from(93_u8)],
+    );
+    let tip = 5;
+    let resource_bounds = ResourceBoundsMapping::try_from(vec![
+        (
+            Resource::L1Gas,
+            ResourceBounds { max_amount: MAX_L1_GAS_AMOUNT, max_price_per_unit: MAX_L1_GAS_PRICE },
+        ),
+        (
+            Resource::L2Gas,
+            ResourceBounds {
+                max_amount: 10 * MAX_L1_GAS_AMOUNT,
+                max_price_per_unit: DEFAULT_STRK_L2_GAS_PRICE + u128::from(tip),
+            },
+        ),
+    ])
+    .unwrap();
+    let tx = account_invoke_tx(invoke_tx_args! {
+        sender_address: account_address,
+        calldata,
+        resource_bounds,
+        tip: Tip(tip),
+    });
+    let estimation = tx.estimate_fee(&mut state, &block_context, &Default::default()).unwrap();
+
+    // The tip is paid per unit of L2 gas, on top of the fee.
+    let gas_vector = GasVector {
+        l1_gas: estimation.l1_gas,
+        l1_data_gas: estimation.l1_data_gas,
+        l2_gas: estimation.l2_gas,
+    };
+    let base_fee = get_fee_by_gas_vector(&block_context.block_info, gas_vector, &FeeType::Strk);
+    assert!(estimation.l2_gas > 0);
+    assert_eq!(estimation.overall_fee, Fee(base_fee.0 + estimation.l2_gas * u128::from(tip)));
+
+    // The estimation matches the fee charged for the transaction.
+    let tx_execution_info = tx.execute(&mut state, &block_context, true, true).unwrap();
+    assert_eq!(tx_execution_info.transaction_receipt.fee, estimation.overall_fee);
+}
@@ -0,0 +1,102 @@
+use std::cmp::Ordering;
+use std::num::NonZeroU128;
+
+use num_bigint::BigUint;
+use num_rational::Ratio;
+use num_traits::{CheckedDiv, ToPrimitive, Zero};
+
+use crate::blockifier::block::GasPrices;
+use crate::bouncer::BouncerWeights;
+use crate::versioned_constants::FeeMarketConstants;
+
+#[cfg(test)]
+#[path = "fee_market_test.rs"]
+pub mod test;
+
+/// Computes the gas prices of the next block, given the gas prices of the previous block and its
+/// usage of each priced resource: L1 gas, L1 data gas (measured by the state diff size) and L2 gas.
+/// The ETH (wei) prices follow the update rule, and the STRK (fri) prices are derived from them by
+/// the given STRK per ETH rate.
+pub fn compute_next_gas_prices(
+    gas_prices: &GasPrices,
+    block_weights: &BouncerWeights,
+    block_max_capacity: &BouncerWeights,
+    constants: &FeeMarketConstants,
+    strk_per_eth_rate: Ratio<u128>,
+) -> GasPrices {
+    let eth_l1_gas_price = compute_next_gas_price(
+        gas_prices.eth_l1_gas_price,
+        block_weights.gas,
+        block_max_capacity.gas,
+        constants,
+    );
+    let eth_l1_data_gas_price = compute_next_gas_price(
+        gas_prices.eth_l1_data_gas_price,
+        block_weights.state_diff_size,
+        block_max_capacity.state_diff_size,
+        constants,
+    );
+    let eth_l2_gas_price = compute_next_gas_price(
+        gas_prices.eth_l2_gas_price,
+        block_weights.l2_gas,
+        block_max_capacity.l2_gas,
+        constants,
+    );
+
+    GasPrices {
+        eth_l1_gas_price,
+        strk_l1_gas_price: convert_eth_to_strk(eth_l1_gas_price, strk_per_eth_rate),
+        eth_l1_data_gas_price,
+        strk_l1_data_gas_price: convert_eth_to_strk(eth_l1_data_gas_price, strk_per_eth_rate),
+        eth_l2_gas_price,
+        strk_l2_gas_price: convert_eth_to_strk(eth_l2_gas_price, strk_per_eth_rate),
+    }
+}
+
+/// Computes the price of a resource in the next block, EIP-1559 style: the price moves towards
+/// balancing the usage of the resource around its target (the block capacity divided by the
+/// elasticity multiplier), by `price * |used - target| / target / max_price_change_denominator`.
+/// The result is at least the minimal gas price; a zero target leaves the price unchanged.
+pub fn compute_next_gas_price(
+    gas_price: NonZeroU128,
+    gas_used: usize,
+    block_capacity: usize,
+    constants: &FeeMarketConstants,
+) -> NonZeroU128 {
+    let price = BigUint::from(gas_price.get());
+    let denominator = BigUint::from(constants.max_price_change_denominator);
+    let gas_target = BigUint::from(block_capacity)
+        .checked_div(&BigUint::from(constants.elasticity_multiplier))
+        .unwrap_or_default();
+    if gas_target.is_zero() || denominator.is_zero() {
+        return gas_price;
+    }
+
+    let gas_used = BigUint::from(gas_used);
+    let next_price = match gas_used.cmp(&gas_target) {
+        Ordering::Equal => price,
+        Ordering::Greater => {
+            // An overused block always raises the price.
+            let delta = &price * (gas_used - &gas_target) / &gas_target / denominator;
+            price + delta.max(BigUint::from(1_u8))
+        }
+        Ordering::Less => {
+            let delta = &price * (&gas_target - gas_used) / &gas_target / denominator;
+            price - delta
+        }
+    };
+    to_gas_price(next_price, constants.min_gas_price)
+}
+
+/// Converts a price in wei to a price in fri, by the given STRK per ETH rate (rounding down, to a
+/// price of at least 1).
+pub fn convert_eth_to_strk(eth_price: NonZeroU128, strk_per_eth_rate: Ratio<u128>) -> NonZeroU128 {
+    let strk_price =
+        BigUint::from(eth_price.get()) * *strk_per_eth_rate.numer() / *strk_per_eth_rate.denom();
+    to_gas_price(strk_price, 1)
+}
+
+fn to_gas_price(price: BigUint, min_gas_price: u128) -> NonZeroU128 {
+    let price = price.to_u128().unwrap_or(u128::MAX).max(min_gas_price).max(1);
+    NonZeroU128::new(price).expect("The gas price is positive.")
+}
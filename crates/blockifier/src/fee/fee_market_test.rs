@@ -0,0 +1,112 @@
+use std::num::NonZeroU128;
+
+use num_rational::Ratio;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+
+use crate::blockifier::block::BlockInfo;
+use crate::bouncer::BouncerWeights;
+use crate::fee::fee_market::{
+    compute_next_gas_price, compute_next_gas_prices, convert_eth_to_strk,
+};
+use crate::versioned_constants::FeeMarketConstants;
+
+const BLOCK_CAPACITY: usize = 1000;
+
+fn gas_price(price: u128) -> NonZeroU128 {
+    NonZeroU128::new(price).unwrap()
+}
+
+#[rstest]
+#[case::at_target(500, 800)]
+#[case::full_block(1000, 900)]
+#[case::above_target(750, 850)]
+#[case::empty_block(0, 700)]
+#[case::below_target(250, 750)]
+fn test_next_gas_price(#[case] gas_used: usize, #[case] expected_price: u128) {
+    let constants = FeeMarketConstants::default();
+    assert_eq!(
+        compute_next_gas_price(gas_price(800), gas_used, BLOCK_CAPACITY, &constants),
+        gas_price(expected_price)
+    );
+}
+
+#[test]
+fn test_next_gas_price_bounds() {
+    let constants = FeeMarketConstants { min_gas_price: 750, ..Default::default() };
+
+    // The price does not drop below the minimal gas price, and always rises in an overused block.
+    assert_eq!(
+        compute_next_gas_price(gas_price(800), 0, BLOCK_CAPACITY, &constants),
+        gas_price(750)
+    );
+    assert_eq!(
+        compute_next_gas_price(gas_price(800), 501, BLOCK_CAPACITY, &constants),
+        gas_price(801)
+    );
+
+    // Without capacity for a resource, its price is unchanged.
+    assert_eq!(compute_next_gas_price(gas_price(800), 0, 1, &constants), gas_price(800));
+
+    // The price does not overflow.
+    assert_eq!(
+        compute_next_gas_price(gas_price(u128::MAX), BLOCK_CAPACITY, BLOCK_CAPACITY, &constants),
+        gas_price(u128::MAX)
+    );
+}
+
+#[test]
+fn test_convert_eth_to_strk() {
+    assert_eq!(convert_eth_to_strk(gas_price(10), Ratio::new(3, 2)), gas_price(15));
+    assert_eq!(convert_eth_to_strk(gas_price(10), Ratio::new(1, 3)), gas_price(3));
+    assert_eq!(convert_eth_to_strk(gas_price(10), Ratio::new(1, 100)), gas_price(1));
+    assert_eq!(convert_eth_to_strk(gas_price(u128::MAX), Ratio::new(2, 1)), gas_price(u128::MAX));
+}
+
+#[test]
+fn test_next_gas_prices() {
+    let gas_prices = BlockInfo::create_for_testing().gas_prices;
+    let block_max_capacity = BouncerWeights {
+        gas: BLOCK_CAPACITY,
+        l2_gas: BLOCK_CAPACITY,
+        state_diff_size: BLOCK_CAPACITY,
+        ..Default::default()
+    };
+    let block_weights = BouncerWeights {
+        gas: BLOCK_CAPACITY,
+        l2_gas: BLOCK_CAPACITY / 2,
+        state_diff_size: 0,
+        ..Default::default()
+    };
+    let strk_per_eth_rate = Ratio::new(2, 1);
+    let next_gas_prices = compute_next_gas_prices(
+        &gas_prices,
+        &block_weights,
+        &block_max_capacity,
+        &FeeMarketConstants::default(),
+        strk_per_eth_rate,
+    );
+
+    // Each price follows the usage of its resource.
+    let eth_l1_gas_price = gas_prices.eth_l1_gas_price.get();
+    let eth_l1_data_gas_price = gas_prices.eth_l1_data_gas_price.get();
+    assert_eq!(
+        next_gas_prices.eth_l1_gas_price,
+        gas_price(eth_l1_gas_price + eth_l1_gas_price / 8)
+    );
+    assert_eq!(
+        next_gas_prices.eth_l1_data_gas_price,
+        gas_price(eth_l1_data_gas_price - eth_l1_data_gas_price / 8)
+    );
+    assert_eq!(next_gas_prices.eth_l2_gas_price, gas_prices.eth_l2_gas_price);
+
+    // The STRK prices are derived from the ETH prices.
+    for (eth_price, strk_price) in [
+        (next_gas_prices.eth_l1_gas_price, next_gas_prices.strk_l1_gas_price),
+        (next_gas_prices.eth_l1_data_gas_price, next_gas_prices.strk_l1_data_gas_price),
+        (next_gas_prices.eth_l2_gas_price, next_gas_prices.strk_l2_gas_price),
+    ] {
+        assert_eq!(strk_price, convert_eth_to_strk(eth_price, strk_per_eth_rate));
+        assert_eq!(strk_price.get(), 2 * eth_price.get());
+    }
+}
@@ -4,22 +4,37 @@ use assert_matches::assert_matches;
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use rstest::rstest;
-use starknet_api::transaction::Fee;
+use starknet_api::felt;
+use starknet_api::transaction::{
+    Fee, Resource, ResourceBounds, ResourceBoundsMapping, Tip, TransactionVersion,
+};
 
 use crate::abi::constants::N_STEPS_RESOURCE;
 use crate::context::BlockContext;
 use crate::fee::actual_cost::TransactionReceipt;
 use crate::fee::fee_checks::{FeeCheckError, FeeCheckReportFields, PostExecutionReport};
-use crate::fee::fee_utils::calculate_l1_gas_by_vm_usage;
+use crate::fee::fee_utils::{
+    calculate_l1_gas_by_vm_usage, get_fee_by_gas_vector, get_tip_by_gas_vector,
+};
 use crate::invoke_tx_args;
 use crate::test_utils::contracts::FeatureContract;
 use crate::test_utils::initial_test_state::test_state;
-use crate::test_utils::{CairoVersion, BALANCE};
-use crate::transaction::objects::GasVector;
+use crate::test_utils::{
+    create_calldata, CairoVersion, BALANCE, DEFAULT_STRK_L2_GAS_PRICE, MAX_L1_GAS_AMOUNT,
+    MAX_L1_GAS_PRICE,
+};
+use crate::transaction::objects::{FeeType, GasVector};
 use crate::transaction::test_utils::{account_invoke_tx, l1_resource_bounds};
+use crate::transaction::transactions::ExecutableTransaction;
 use crate::utils::u128_from_usize;
 use crate::versioned_constants::VersionedConstants;
 
+#[derive(Debug)]
+enum OverdraftResource {
+    L1Gas,
+    L1DataGas,
+}
+
 fn get_vm_resource_usage() -> ExecutionResources {
     ExecutionResources {
         n_steps: 10000,
@@ -104,22 +119,24 @@ fn test_float_calculate_l1_gas_by_vm_usage() {
     );
 }
 
-/// Test the L1 gas limit bound, as applied to the case where both gas and data gas are consumed.
+/// Test the L1 gas limit bound, as applied to the case where both gas and data gas are consumed;
+/// the data gas is paid out of the L1 gas bound, and reported separately if it does not fit.
 #[rstest]
-#[case::no_dg_within_bounds(1000, 10, 10000, 0, 10000, false)]
-#[case::no_dg_overdraft(1000, 10, 10001, 0, 10000, true)]
-#[case::both_gases_within_bounds(1000, 10, 10000, 5000, 100000, false)]
-#[case::both_gases_overdraft(1000, 10, 10000, 5000, 10000, true)]
-#[case::expensive_dg_no_dg_within_bounds(10, 1000, 10, 0, 10, false)]
-#[case::expensive_dg_with_dg_overdraft(10, 1000, 10, 1, 109, true)]
-#[case::expensive_dg_with_dg_within_bounds(10, 1000, 10, 1, 110, false)]
+#[case::no_dg_within_bounds(1000, 10, 10000, 0, 10000, None)]
+#[case::no_dg_overdraft(1000, 10, 10001, 0, 10000, Some(OverdraftResource::L1Gas))]
+#[case::both_gases_within_bounds(1000, 10, 10000, 5000, 100000, None)]
+#[case::both_gases_overdraft(1000, 10, 10000, 5000, 10000, Some(OverdraftResource::L1DataGas))]
+#[case::gas_overdraft_with_dg(1000, 10, 10001, 5000, 10000, Some(OverdraftResource::L1Gas))]
+#[case::expensive_dg_no_dg_within_bounds(10, 1000, 10, 0, 10, None)]
+#[case::expensive_dg_with_dg_overdraft(10, 1000, 10, 1, 109, Some(OverdraftResource::L1DataGas))]
+#[case::expensive_dg_with_dg_within_bounds(10, 1000, 10, 1, 110, None)]
 fn test_discounted_gas_overdraft(
     #[case] gas_price: u128,
     #[case] data_gas_price: u128,
     #[case] l1_gas_used: usize,
     #[case] l1_data_gas_used: usize,
     #[case] gas_bound: u64,
-    #[case] expect_failure: bool,
+    #[case] expected_overdraft: Option<OverdraftResource>,
 ) {
     let mut block_context = BlockContext::create_for_account_testing();
     block_context.block_info.gas_prices.strk_l1_gas_price = gas_price.try_into().unwrap();
@@ -137,6 +154,7 @@ fn test_discounted_gas_overdraft(
         gas: GasVector {
             l1_gas: u128_from_usize(l1_gas_used),
             l1_data_gas: u128_from_usize(l1_data_gas_used),
+            l2_gas: 0,
         },
         ..Default::default()
     };
@@ -149,15 +167,153 @@ fn test_discounted_gas_overdraft(
     )
     .unwrap();
 
+    match expected_overdraft {
+        Some(OverdraftResource::L1Gas) => assert_matches!(
+            report.error().unwrap(),
+            FeeCheckError::MaxL1GasAmountExceeded { max_amount, actual_amount }
+            if max_amount == u128::from(gas_bound) && actual_amount == u128_from_usize(l1_gas_used)
+        ),
+        Some(OverdraftResource::L1DataGas) => {
+            // The L1 data gas covered by the L1 gas bound left after the L1 gas used.
+            let expected_max_amount =
+                (u128::from(gas_bound) - u128_from_usize(l1_gas_used)) * gas_price / data_gas_price;
+            assert_matches!(
+                report.error().unwrap(),
+                FeeCheckError::MaxL1DataGasAmountExceeded { max_amount, actual_amount }
+                if max_amount == expected_max_amount
+                    && actual_amount == u128_from_usize(l1_data_gas_used)
+            )
+        }
+        None => assert_matches!(report.error(), None),
+    }
+}
+
+/// Test the L2 gas limit bound, which is only enforced if L2 gas is enabled.
+#[rstest]
+#[case::within_bounds(1000, 1000, true, false)]
+#[case::overdraft(1001, 1000, true, true)]
+#[case::overdraft_not_enabled(1001, 1000, false, false)]
+fn test_l2_gas_overdraft(
+    #[case] l2_gas_used: u128,
+    #[case] l2_gas_bound: u64,
+    #[case] enable_l2_gas: bool,
+    #[case] expect_failure: bool,
+) {
+    let mut block_context = BlockContext::create_for_account_testing();
+    block_context.versioned_constants.enable_l2_gas = enable_l2_gas;
+
+    let account = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0);
+    let mut state = test_state(&block_context.chain_info, BALANCE, &[(account, 1)]);
+    let resource_bounds = ResourceBoundsMapping::try_from(vec![
+        (Resource::L1Gas, ResourceBounds { max_amount: 10000, max_price_per_unit: 100 }),
+        (Resource::L2Gas, ResourceBounds { max_amount: l2_gas_bound, max_price_per_unit: 100 }),
+    ])
+    .unwrap();
+    let tx = account_invoke_tx(invoke_tx_args! {
+        sender_address: account.get_instance_address(0),
+        resource_bounds,
+    });
+
+    let tx_receipt = TransactionReceipt {
+        fee: Fee(7),
+        gas: GasVector { l1_gas: 100, l1_data_gas: 0, l2_gas: l2_gas_used },
+        ..Default::default()
+    };
+    let charge_fee = true;
+    let report = PostExecutionReport::new(
+        &mut state,
+        &block_context.to_tx_context(&tx),
+        &tx_receipt,
+        charge_fee,
+    )
+    .unwrap();
+
     if expect_failure {
-        let error = report.error().unwrap();
-        let expected_actual_amount = u128_from_usize(l1_gas_used)
-            + (u128_from_usize(l1_data_gas_used) * data_gas_price) / gas_price;
         assert_matches!(
-            error, FeeCheckError::MaxL1GasAmountExceeded { max_amount, actual_amount }
-            if max_amount == u128::from(gas_bound) && actual_amount == expected_actual_amount
+            report.error().unwrap(),
+            FeeCheckError::MaxL2GasAmountExceeded { max_amount, actual_amount }
+            if max_amount == u128::from(l2_gas_bound) && actual_amount == l2_gas_used
         )
     } else {
         assert_matches!(report.error(), None);
     }
 }
+
+/// Test that the tip is paid per unit of L2 gas, up to the max price of the L2 gas bound.
+#[rstest]
+#[case::no_tip(0, 2 * DEFAULT_STRK_L2_GAS_PRICE, 0)]
+#[case::within_bounds(5, 2 * DEFAULT_STRK_L2_GAS_PRICE, 5)]
+#[case::capped_by_bounds(5, DEFAULT_STRK_L2_GAS_PRICE + 3, 3)]
+#[case::bounds_below_price(5, DEFAULT_STRK_L2_GAS_PRICE - 1, 0)]
+fn test_get_tip_by_gas_vector(
+    #[case] tip: u64,
+    #[case] max_l2_gas_price: u128,
+    #[case] expected_tip_per_l2_gas: u128,
+) {
+    let block_context = BlockContext::create_for_account_testing();
+    let gas_vector = GasVector { l1_gas: 100, l1_data_gas: 10, l2_gas: 1000 };
+    let resource_bounds = ResourceBoundsMapping::try_from(vec![
+        (Resource::L1Gas, ResourceBounds { max_amount: 10000, max_price_per_unit: 100 }),
+        (
+            Resource::L2Gas,
+            ResourceBounds { max_amount: 10000, max_price_per_unit: max_l2_gas_price },
+        ),
+    ])
+    .unwrap();
+
+    let tx = account_invoke_tx(invoke_tx_args! { resource_bounds, tip: Tip(tip) });
+    assert_eq!(
+        get_tip_by_gas_vector(&block_context.to_tx_context(&tx), &gas_vector),
+        Fee(gas_vector.l2_gas * expected_tip_per_l2_gas)
+    );
+
+    // Transactions of older versions pay no tip.
+    let tx = account_invoke_tx(invoke_tx_args! { version: TransactionVersion::ONE, tip: Tip(tip) });
+    assert_eq!(get_tip_by_gas_vector(&block_context.to_tx_context(&tx), &gas_vector), Fee(0));
+}
+
+/// Test that the tip is charged on top of the base fee, and reported separately.
+#[rstest]
+fn test_charge_tip(#[values(false, true)] enable_l2_gas: bool) {
+    let mut block_context = BlockContext::create_for_account_testing();
+    block_context.versioned_constants.enable_l2_gas = enable_l2_gas;
+    let account = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1);
+    let mut state =
+        test_state(&block_context.chain_info, BALANCE, &[(account, 1), (test_contract, 1)]);
+
+    let tip = 7;
+    let resource_bounds = ResourceBoundsMapping::try_from(vec![
+        (
+            Resource::L1Gas,
+            ResourceBounds { max_amount: MAX_L1_GAS_AMOUNT, max_price_per_unit: MAX_L1_GAS_PRICE },
+        ),
+        (
+            Resource::L2Gas,
+            ResourceBounds {
+                max_amount: 10_u64.pow(8),
+                max_price_per_unit: DEFAULT_STRK_L2_GAS_PRICE + u128::from(tip),
+            },
+        ),
+    ])
+    .unwrap();
+    let tx = account_invoke_tx(invoke_tx_args! {
+        sender_address: account.get_instance_address(0),
+        calldata: create_calldata(
+            test_contract.get_instance_address(0),
+            "test_storage_read_write",
+            &[felt!(1993_u16), felt!(93_u8)],
+        ),
+        resource_bounds,
+        tip: Tip(tip),
+    });
+    let tx_execution_info = tx.execute(&mut state, &block_context, true, true).unwrap();
+    assert!(!tx_execution_info.is_reverted());
+
+    // The tip is only paid for metered L2 gas.
+    let receipt = &tx_execution_info.transaction_receipt;
+    assert_eq!(receipt.tip, Fee(receipt.gas.l2_gas * u128::from(tip)));
+    assert_eq!(receipt.tip > Fee(0), enable_l2_gas);
+    let base_fee = get_fee_by_gas_vector(&block_context.block_info, receipt.gas, &FeeType::Strk);
+    assert_eq!(receipt.fee, Fee(base_fee.0 + receipt.tip.0));
+}
@@ -73,9 +73,24 @@ pub fn get_fee_by_gas_vector(
     gas_vector.saturated_cost(
         u128::from(block_info.gas_prices.get_gas_price_by_fee_type(fee_type)),
         u128::from(block_info.gas_prices.get_data_gas_price_by_fee_type(fee_type)),
+        u128::from(block_info.gas_prices.get_l2_gas_price_by_fee_type(fee_type)),
     )
 }
 
+/// Returns the tip paid for the given gas usage: the tip is paid per unit of L2 gas, on top of the
+/// L2 gas price, and is capped so that the price paid does not exceed the L2 gas bound's max price.
+/// Transactions of older versions pay no tip.
+pub fn get_tip_by_gas_vector(tx_context: &TransactionContext, gas_vector: &GasVector) -> Fee {
+    let TransactionInfo::Current(context) = &tx_context.tx_info else {
+        return Fee(0);
+    };
+    let l2_gas_price = tx_context.block_context.block_info.gas_prices.strk_l2_gas_price;
+    let max_tip =
+        context.l2_resource_bounds().max_price_per_unit.saturating_sub(l2_gas_price.into());
+    let tip_per_l2_gas = u128::from(context.tip.0).min(max_tip);
+    Fee(gas_vector.l2_gas.saturating_mul(tip_per_l2_gas))
+}
+
 /// Returns the current fee balance and a boolean indicating whether the balance covers the fee.
 pub fn get_balance_and_if_covers_fee(
     state: &mut dyn StateReader,
@@ -107,7 +122,12 @@ pub fn verify_can_pay_committed_bounds(
             let max_amount: u128 = l1_bounds.max_amount.into();
             // Sender will not be charged by `max_price_per_unit`, but this check should not depend
             // on the current gas price.
-            Fee(max_amount * l1_bounds.max_price_per_unit)
+            let mut committed_fee = max_amount * l1_bounds.max_price_per_unit;
+            if tx_context.block_context.versioned_constants.enable_l2_gas {
+                let l2_bounds = context.l2_resource_bounds();
+                committed_fee += u128::from(l2_bounds.max_amount) * l2_bounds.max_price_per_unit;
+            }
+            Fee(committed_fee)
         }
         TransactionInfo::Deprecated(context) => context.max_fee,
     };
@@ -2,11 +2,14 @@ use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 
 use super::fee_utils::calculate_l1_gas_by_vm_usage;
 use crate::abi::constants;
+use crate::blockifier::block::GasPrices;
 use crate::context::{BlockContext, TransactionContext};
 use crate::fee::eth_gas_constants;
 use crate::state::cached_state::StateChangesCount;
 use crate::transaction::account_transaction::AccountTransaction;
-use crate::transaction::objects::{GasVector, HasRelatedFeeType, TransactionPreValidationResult};
+use crate::transaction::objects::{
+    FeeType, GasVector, HasRelatedFeeType, TransactionPreValidationResult,
+};
 use crate::utils::{u128_div_ceil, u128_from_usize};
 
 #[cfg(test)]
@@ -72,7 +75,7 @@ pub fn get_da_gas_cost(state_changes_count: &StateChangesCount, use_kzg_da: bool
         (u128_from_usize(gas), 0)
     };
 
-    GasVector { l1_gas, l1_data_gas: blob_gas }
+    GasVector { l1_gas, l1_data_gas: blob_gas, l2_gas: 0 }
 }
 
 /// Returns the number of felts added to the output messages segment as a result of adding
@@ -193,11 +196,38 @@ pub fn estimate_minimal_gas_vector(
 pub fn compute_discounted_gas_from_gas_vector(
     gas_usage_vector: &GasVector,
     tx_context: &TransactionContext,
+) -> u128 {
+    compute_discounted_gas_by_fee_type(
+        gas_usage_vector,
+        &tx_context.block_context.block_info.gas_prices,
+        &tx_context.tx_info.fee_type(),
+    )
+}
+
+/// Same as [compute_discounted_gas_from_gas_vector], at the gas prices of the given fee type.
+pub fn compute_discounted_gas_by_fee_type(
+    gas_usage_vector: &GasVector,
+    gas_prices: &GasPrices,
+    fee_type: &FeeType,
+) -> u128 {
+    let GasVector { l1_gas: gas_usage, l1_data_gas: blob_gas_usage, .. } = gas_usage_vector;
+    let gas_price = gas_prices.get_gas_price_by_fee_type(fee_type);
+    let data_gas_price = gas_prices.get_data_gas_price_by_fee_type(fee_type);
+    gas_usage + u128_div_ceil(blob_gas_usage * u128::from(data_gas_price), gas_price)
+}
+
+/// Computes the amount of L1 data gas covered by the given L1 gas bound, on top of the given L1 gas
+/// usage. Since there is no separate L1 data gas bound, L1 data gas is paid out of the L1 gas
+/// bound, at the discount described in [compute_discounted_gas_from_gas_vector].
+pub fn compute_l1_data_gas_allowance(
+    max_l1_gas: u128,
+    l1_gas_usage: u128,
+    tx_context: &TransactionContext,
 ) -> u128 {
     let gas_prices = &tx_context.block_context.block_info.gas_prices;
-    let GasVector { l1_gas: gas_usage, l1_data_gas: blob_gas_usage } = gas_usage_vector;
     let fee_type = tx_context.tx_info.fee_type();
     let gas_price = gas_prices.get_gas_price_by_fee_type(&fee_type);
     let data_gas_price = gas_prices.get_data_gas_price_by_fee_type(&fee_type);
-    gas_usage + u128_div_ceil(blob_gas_usage * u128::from(data_gas_price), gas_price)
+    max_l1_gas.saturating_sub(l1_gas_usage).saturating_mul(gas_price.into())
+        / u128::from(data_gas_price)
 }
@@ -205,7 +205,7 @@ fn test_get_message_segment_length(
 fn test_compute_discounted_gas_from_gas_vector() {
     let tx_context =
         BlockContext::create_for_testing().to_tx_context(&account_invoke_tx(invoke_tx_args! {}));
-    let gas_usage = GasVector { l1_gas: 100, l1_data_gas: 2 };
+    let gas_usage = GasVector { l1_gas: 100, l1_data_gas: 2, l2_gas: 0 };
     let actual_result = compute_discounted_gas_from_gas_vector(&gas_usage, &tx_context);
 
     let result_div_ceil = gas_usage.l1_gas
@@ -17,6 +17,7 @@ pub mod concurrency;
 pub mod context;
 pub mod execution;
 pub mod fee;
+pub mod patricia_tree;
 pub mod state;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
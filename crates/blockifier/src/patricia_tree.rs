@@ -0,0 +1,354 @@
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::StarkHash;
+
+#[cfg(test)]
+#[path = "patricia_tree_test.rs"]
+pub mod test;
+
+/// A node on the path from the root of a tree to a leaf, given by the hashes of its children.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProofNode {
+    Binary { left: Felt, right: Felt },
+    Edge { child: Felt, path: Felt, length: u8 },
+}
+
+impl ProofNode {
+    pub fn hash<H: StarkHash>(&self) -> Felt {
+        match self {
+            Self::Binary { left, right } => H::hash(left, right),
+            Self::Edge { child, path, length } => H::hash(child, path) + Felt::from(*length),
+        }
+    }
+}
+
+/// An in-memory Patricia-Merkle tree of a fixed height, whose leaves are keyed by their index in
+/// the tree (which must be smaller than 2^height). Zero leaves are considered empty.
+/// The nodes of the tree are kept along with their hashes; an update only rehashes the nodes on
+/// the paths to the updated leaves.
+pub struct PatriciaTree<H: StarkHash> {
+    height: u8,
+    leaves: BTreeMap<Felt, Felt>,
+    root: Option<Box<Node>>,
+    _hash: PhantomData<H>,
+}
+
+impl<H: StarkHash> PatriciaTree<H> {
+    pub fn new(height: u8) -> Self {
+        Self { height, leaves: BTreeMap::new(), root: None, _hash: PhantomData }
+    }
+
+    pub fn get(&self, key: Felt) -> Felt {
+        self.leaves.get(&key).copied().unwrap_or_default()
+    }
+
+    /// Sets the leaf at the given key; setting a zero leaf removes it.
+    pub fn set(&mut self, key: Felt, value: Felt) {
+        self.update([(key, value)]);
+    }
+
+    /// Sets the given leaves (a zero leaf is removed), and rehashes the nodes on their paths, once
+    /// per node.
+    pub fn update(&mut self, leaves: impl IntoIterator<Item = (Felt, Felt)>) {
+        for (key, value) in leaves {
+            let previous_value = if value == Felt::ZERO {
+                self.leaves.remove(&key)
+            } else {
+                self.leaves.insert(key, value)
+            };
+            if previous_value.unwrap_or_default() != value {
+                self.root =
+                    update_subtree(self.root.take(), self.height, &key.to_bytes_be(), value);
+            }
+        }
+
+        if let Some(root) = &mut self.root {
+            root.update_hash::<H>(self.height);
+        }
+    }
+
+    pub fn n_leaves(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns the root of the tree; the root of an empty tree is zero.
+    pub fn root(&self) -> Felt {
+        self.root.as_ref().map_or(Felt::ZERO, |root| root.hash())
+    }
+
+    /// Returns the nodes on the path from the root to the leaf at the given key, starting at the
+    /// root. If the leaf is empty, the path ends at the node where it diverges from the tree.
+    pub fn proof(&self, key: Felt) -> Vec<ProofNode> {
+        let mut proof = Vec::new();
+        let key = key.to_bytes_be();
+        let (mut node, mut height) = (self.root.as_deref(), self.height);
+        while let Some(current_node) = node {
+            match &current_node.kind {
+                NodeKind::Leaf(_) => break,
+                NodeKind::Binary { left, right } => {
+                    proof.push(ProofNode::Binary { left: left.hash(), right: right.hash() });
+                    height -= 1;
+                    node = Some(if key_bit(&key, height) { right } else { left });
+                }
+                NodeKind::Edge { path, length, child } => {
+                    let bottom_height = height - length;
+                    proof.push(ProofNode::Edge {
+                        child: child.hash(),
+                        path: key_bits(path, bottom_height, height),
+                        length: *length,
+                    });
+                    if diverging_height(&key, path, bottom_height, height).is_some() {
+                        break;
+                    }
+                    height = bottom_height;
+                    node = Some(child);
+                }
+            }
+        }
+
+        proof
+    }
+}
+
+/// Computes the root of a Patricia-Merkle tree of the given height, holding the given leaves,
+/// keyed by their index in the tree (which must be smaller than 2^height).
+pub fn calculate_root<H: StarkHash>(
+    leaves: impl IntoIterator<Item = (Felt, Felt)>,
+    height: u8,
+) -> Felt {
+    let mut tree = PatriciaTree::<H>::new(height);
+    tree.update(leaves);
+    tree.root()
+}
+
+/// Verifies that the given proof, as returned by [PatriciaTree::proof], proves the given value of
+/// the leaf at the given key (a zero value proves that the leaf is empty), in the tree of the given
+/// root and height.
+pub fn verify_proof<H: StarkHash>(
+    root: Felt,
+    height: u8,
+    key: Felt,
+    value: Felt,
+    proof: &[ProofNode],
+) -> bool {
+    let key = key.to_bytes_be();
+    let (mut expected_hash, mut height) = (root, height);
+    for node in proof {
+        if height == 0 || node.hash::<H>() != expected_hash {
+            return false;
+        }
+
+        match node {
+            ProofNode::Binary { left, right } => {
+                height -= 1;
+                expected_hash = if key_bit(&key, height) { *right } else { *left };
+            }
+            ProofNode::Edge { child, path, length } => {
+                let Some(bottom_height) = height.checked_sub(*length) else {
+                    return false;
+                };
+                if key_bits(&key, bottom_height, height) != *path {
+                    // The leaf is not in the tree.
+                    return value == Felt::ZERO;
+                }
+                height = bottom_height;
+                expected_hash = *child;
+            }
+        }
+    }
+
+    match height {
+        0 => expected_hash == value,
+        // Only the empty tree has a partial path that does not diverge from the key.
+        _ => proof.is_empty() && root == Felt::ZERO && value == Felt::ZERO,
+    }
+}
+
+/// A node of a non-empty subtree, along with its hash (`None` if the node was updated since the
+/// hashes were last computed).
+struct Node {
+    kind: NodeKind,
+    hash: Option<Felt>,
+}
+
+enum NodeKind {
+    Leaf(Felt),
+    Binary {
+        left: Box<Node>,
+        right: Box<Node>,
+    },
+    /// An edge leading from the subtree root down to a leaf or a binary node. The bits of the path
+    /// are kept at their indices in the keys of the leaves below it; the other bits are zero.
+    Edge {
+        path: [u8; 32],
+        length: u8,
+        child: Box<Node>,
+    },
+}
+
+impl Node {
+    fn new(kind: NodeKind) -> Box<Self> {
+        Box::new(Self { kind, hash: None })
+    }
+
+    fn leaf(value: Felt) -> Box<Self> {
+        Box::new(Self { kind: NodeKind::Leaf(value), hash: Some(value) })
+    }
+
+    /// Returns an edge between the given heights, along the path given by the bits of the given
+    /// key at these heights, leading to the given node; edges leading to edges are merged.
+    fn edge(key: &[u8; 32], bottom_height: u8, height: u8, child: Box<Self>) -> Box<Self> {
+        if bottom_height == height {
+            return child;
+        }
+
+        let mut path = [0; 32];
+        for index in bottom_height..height {
+            if key_bit(key, index) {
+                set_key_bit(&mut path, index);
+            }
+        }
+        let Self { kind, hash } = *child;
+        match kind {
+            NodeKind::Edge { path: child_path, length: child_length, child } => {
+                for (byte, child_byte) in path.iter_mut().zip(child_path) {
+                    *byte |= child_byte;
+                }
+                Self::new(NodeKind::Edge {
+                    path,
+                    length: height - bottom_height + child_length,
+                    child,
+                })
+            }
+            kind => Self::new(NodeKind::Edge {
+                path,
+                length: height - bottom_height,
+                child: Box::new(Self { kind, hash }),
+            }),
+        }
+    }
+
+    /// Computes the hashes of the updated nodes of the subtree of the given height rooted at this
+    /// node, and returns the hash of this node.
+    fn update_hash<H: StarkHash>(&mut self, height: u8) -> Felt {
+        if let Some(hash) = self.hash {
+            return hash;
+        }
+
+        let hash = match &mut self.kind {
+            NodeKind::Leaf(value) => *value,
+            NodeKind::Binary { left, right } => ProofNode::Binary {
+                left: left.update_hash::<H>(height - 1),
+                right: right.update_hash::<H>(height - 1),
+            }
+            .hash::<H>(),
+            NodeKind::Edge { path, length, child } => {
+                let bottom_height = height - *length;
+                ProofNode::Edge {
+                    child: child.update_hash::<H>(bottom_height),
+                    path: key_bits(path, bottom_height, height),
+                    length: *length,
+                }
+                .hash::<H>()
+            }
+        };
+        self.hash = Some(hash);
+        hash
+    }
+
+    fn hash(&self) -> Felt {
+        self.hash.expect("The hashes of the nodes are computed upon each update.")
+    }
+}
+
+/// Sets the leaf at the given key in the subtree of the given height rooted at the given node (if
+/// any), and returns the new root of the subtree; the nodes on the path to the leaf are marked as
+/// updated.
+fn update_subtree(
+    node: Option<Box<Node>>,
+    height: u8,
+    key: &[u8; 32],
+    value: Felt,
+) -> Option<Box<Node>> {
+    let Some(node) = node else {
+        return (value != Felt::ZERO).then(|| Node::edge(key, 0, height, Node::leaf(value)));
+    };
+
+    let Node { kind, hash } = *node;
+    match kind {
+        NodeKind::Leaf(_) => (value != Felt::ZERO).then(|| Node::leaf(value)),
+        NodeKind::Binary { left, right } => {
+            let child_height = height - 1;
+            let (left, right) = match key_bit(key, child_height) {
+                false => (update_subtree(Some(left), child_height, key, value), Some(right)),
+                true => (Some(left), update_subtree(Some(right), child_height, key, value)),
+            };
+            match (left, right) {
+                (Some(left), Some(right)) => Some(Node::new(NodeKind::Binary { left, right })),
+                // The binary node is replaced by an edge to its remaining child.
+                (Some(child), None) => Some(Node::edge(&[0; 32], child_height, height, child)),
+                (None, Some(child)) => {
+                    let mut path = [0; 32];
+                    set_key_bit(&mut path, child_height);
+                    Some(Node::edge(&path, child_height, height, child))
+                }
+                (None, None) => unreachable!("Only one child of a binary node is updated."),
+            }
+        }
+        NodeKind::Edge { path, length, child } => {
+            let bottom_height = height - length;
+            match diverging_height(key, &path, bottom_height, height) {
+                None => {
+                    let child = update_subtree(Some(child), bottom_height, key, value)?;
+                    Some(Node::edge(&path, bottom_height, height, child))
+                }
+                // The leaf is not in the tree, and remains empty.
+                Some(_) if value == Felt::ZERO => {
+                    Some(Box::new(Node { kind: NodeKind::Edge { path, length, child }, hash }))
+                }
+                // Split the edge: a binary node at the diverging height, leading to the rest of
+                // the edge and to the new leaf.
+                Some(child_height) => {
+                    let edge_child = Node::edge(&path, bottom_height, child_height, child);
+                    let leaf_child = Node::edge(key, 0, child_height, Node::leaf(value));
+                    let (left, right) = match key_bit(key, child_height) {
+                        false => (leaf_child, edge_child),
+                        true => (edge_child, leaf_child),
+                    };
+                    let binary_node = Node::new(NodeKind::Binary { left, right });
+                    Some(Node::edge(&path, child_height + 1, height, binary_node))
+                }
+            }
+        }
+    }
+}
+
+/// Returns the highest index in the given range at which the bits of the given keys differ, if
+/// any.
+fn diverging_height(key: &[u8; 32], other_key: &[u8; 32], start: u8, end: u8) -> Option<u8> {
+    (start..end).rev().find(|&index| key_bit(key, index) != key_bit(other_key, index))
+}
+
+/// Returns the bit of the given (big-endian) key at the given index, counting from the least
+/// significant bit.
+fn key_bit(key: &[u8; 32], index: u8) -> bool {
+    let byte = key[31 - usize::from(index / 8)];
+    (byte >> (index % 8)) & 1 == 1
+}
+
+fn set_key_bit(key: &mut [u8; 32], index: u8) {
+    key[31 - usize::from(index / 8)] |= 1 << (index % 8);
+}
+
+/// Returns the bits of the given (big-endian) key in the given index range, as a number.
+fn key_bits(key: &[u8; 32], start: u8, end: u8) -> Felt {
+    let mut bits = [0; 32];
+    for index in start..end {
+        if key_bit(key, index) {
+            set_key_bit(&mut bits, index - start);
+        }
+    }
+    Felt::from_bytes_be(&bits)
+}
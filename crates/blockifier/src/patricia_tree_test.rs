@@ -0,0 +1,165 @@
+use pretty_assertions::assert_eq;
+use serde::Deserialize;
+use starknet_api::felt;
+use starknet_api::transaction::{TransactionHash, TransactionSignature};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+
+use crate::patricia_tree::{calculate_root, verify_proof, PatriciaTree};
+
+/// The transactions of mainnet block 183862, with their transaction commitment (the root of a
+/// Patricia-Merkle tree of height 64).
+const TRANSACTION_COMMITMENT_JSON: &str =
+    include_str!("../resources/test_data/transaction_commitment.json");
+
+#[derive(Deserialize)]
+struct TransactionCommitmentTestData {
+    transaction_commitment: Felt,
+    transactions: Vec<TransactionWithSignature>,
+}
+
+#[derive(Deserialize)]
+struct TransactionWithSignature {
+    transaction_hash: TransactionHash,
+    signature: TransactionSignature,
+}
+
+#[test]
+fn test_empty_tree() {
+    assert_eq!(calculate_root::<Pedersen>([], 64), Felt::ZERO);
+    assert_eq!(calculate_root::<Pedersen>([(felt!(3_u8), Felt::ZERO)], 64), Felt::ZERO);
+}
+
+#[test]
+fn test_single_leaf() {
+    let (key, value) = (felt!(5_u8), felt!(17_u8));
+
+    // The root is an edge node leading all the way to the leaf.
+    assert_eq!(
+        calculate_root::<Poseidon>([(key, value)], 64),
+        Poseidon::hash(&value, &key) + felt!(64_u8)
+    );
+}
+
+#[test]
+fn test_leaves_order_and_zero_leaves() {
+    let leaves =
+        [(felt!(0_u8), felt!(1_u8)), (felt!(6_u8), felt!(2_u8)), (felt!(7_u8), felt!(3_u8))];
+    let root = calculate_root::<Pedersen>(leaves, 3);
+
+    assert_eq!(calculate_root::<Pedersen>(leaves.into_iter().rev(), 3), root);
+    assert_eq!(
+        calculate_root::<Pedersen>(leaves.into_iter().chain([(felt!(2_u8), Felt::ZERO)]), 3),
+        root
+    );
+
+    // The tree: the root is binary; its left child is an edge of length 2 to leaf 0, and its
+    // right child is an edge of length 1 to the binary node of leaves 6 and 7.
+    let left = Pedersen::hash(&felt!(1_u8), &Felt::ZERO) + felt!(2_u8);
+    let right = Pedersen::hash(&Pedersen::hash(&felt!(2_u8), &felt!(3_u8)), &Felt::ONE) + Felt::ONE;
+    assert_eq!(root, Pedersen::hash(&left, &right));
+}
+
+#[test]
+fn test_proofs() {
+    let height = 8;
+    let leaves =
+        [(felt!(0_u8), felt!(1_u8)), (felt!(6_u8), felt!(2_u8)), (felt!(7_u8), felt!(3_u8))];
+    let mut tree = PatriciaTree::<Pedersen>::new(height);
+    for (key, value) in leaves {
+        tree.set(key, value);
+    }
+    let root = tree.root();
+
+    for (key, value) in leaves {
+        let proof = tree.proof(key);
+        assert!(verify_proof::<Pedersen>(root, height, key, value, &proof));
+        assert!(!verify_proof::<Pedersen>(root, height, key, value + Felt::ONE, &proof));
+        assert!(!verify_proof::<Pedersen>(root, height, key, Felt::ZERO, &proof));
+    }
+
+    // Non-membership proofs, diverging from the tree at the root and below it.
+    for absent_key in [felt!(4_u8), felt!(200_u8)] {
+        let proof = tree.proof(absent_key);
+        assert!(verify_proof::<Pedersen>(root, height, absent_key, Felt::ZERO, &proof));
+        assert!(!verify_proof::<Pedersen>(root, height, absent_key, Felt::ONE, &proof));
+    }
+
+    // The empty tree.
+    let empty_tree = PatriciaTree::<Pedersen>::new(height);
+    assert_eq!(empty_tree.proof(felt!(6_u8)), vec![]);
+    assert!(verify_proof::<Pedersen>(Felt::ZERO, height, felt!(6_u8), Felt::ZERO, &[]));
+    assert!(!verify_proof::<Pedersen>(root, height, felt!(6_u8), Felt::ZERO, &[]));
+}
+
+#[test]
+fn test_incremental_updates() {
+    let height = 16;
+    let leaves: Vec<(Felt, Felt)> =
+        (0..100_u16).map(|index| (felt!(index * 613 % 65521), felt!(index + 1))).collect();
+    let mut tree = PatriciaTree::<Pedersen>::new(height);
+    tree.update(leaves.iter().copied());
+    assert_eq!(tree.root(), calculate_root::<Pedersen>(leaves.iter().copied(), height));
+
+    // Update, remove and add leaves, one by one and in a batch.
+    let updates: Vec<(Felt, Felt)> = leaves
+        .iter()
+        .step_by(3)
+        .map(|&(key, value)| (key, value + Felt::ONE))
+        .chain(leaves.iter().skip(1).step_by(3).map(|&(key, _)| (key, Felt::ZERO)))
+        .chain((0..20_u16).map(|index| (felt!(index * 7 + 1), felt!(index + 1000))))
+        .collect();
+    let (first_updates, last_updates) = updates.split_at(updates.len() / 2);
+    for &(key, value) in first_updates {
+        tree.set(key, value);
+    }
+    tree.update(last_updates.iter().copied());
+
+    let final_leaves = leaves.iter().chain(&updates).copied();
+    let root = calculate_root::<Pedersen>(final_leaves.clone(), height);
+    assert_eq!(tree.root(), root);
+    for (key, _) in final_leaves {
+        let proof = tree.proof(key);
+        assert!(verify_proof::<Pedersen>(root, height, key, tree.get(key), &proof));
+    }
+
+    // Removing all leaves empties the tree.
+    tree.update(leaves.iter().chain(&updates).map(|&(key, _)| (key, Felt::ZERO)));
+    assert_eq!((tree.root(), tree.n_leaves()), (Felt::ZERO, 0));
+}
+
+#[test]
+fn test_transaction_commitment_known_answer() {
+    let test_data: TransactionCommitmentTestData =
+        serde_json::from_str(TRANSACTION_COMMITMENT_JSON).unwrap();
+    let leaves = test_data.transactions.iter().enumerate().map(|(index, transaction)| {
+        let signature_hash = Pedersen::hash_array(&transaction.signature.0);
+        (Felt::from(index), Pedersen::hash(&transaction.transaction_hash.0, &signature_hash))
+    });
+
+    assert_eq!(calculate_root::<Pedersen>(leaves, 64), test_data.transaction_commitment);
+}
+
+#[test]
+fn test_poseidon_known_answers() {
+    // The roots of the block commitment trees of Starknet, as computed by the Starknet sequencer.
+    let root = |values: &[u8]| {
+        calculate_root::<Poseidon>(
+            values.iter().enumerate().map(|(index, value)| (Felt::from(index), felt!(*value))),
+            64,
+        )
+    };
+
+    assert_eq!(
+        root(&[1]),
+        felt!("0x7752582c54a42fe0fa35c40f07293bb7d8efe90e21d8d2c06a7db52d7d9b7e1")
+    );
+    assert_eq!(
+        root(&[1, 2]),
+        felt!("0x1c1ba983ee0a0de87d87d67ea3cbee7023aa65f6b7bcf71259f122ea3af80bf")
+    );
+    assert_eq!(
+        root(&[1, 2, 3]),
+        felt!("0x3b5cc7f1292eb3847c3f902d048a7e5dc7702d1c191ccd17c2d33f797e6fc32")
+    );
+}
@@ -3,4 +3,9 @@ pub mod cached_state;
 pub mod error_format_test;
 pub mod errors;
 pub mod global_cache;
+pub mod override_state;
 pub mod state_api;
+pub mod state_commitment;
+pub mod state_dump;
+mod state_serde;
+pub mod witness;
@@ -1,17 +1,21 @@
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
 
 use derive_more::IntoIterator;
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
-use starknet_api::state::StorageKey;
+use starknet_api::state::{StorageKey, ThinStateDiff};
 use starknet_types_core::felt::Felt;
 
 use crate::abi::abi_utils::get_fee_token_var_address;
 use crate::context::TransactionContext;
 use crate::execution::contract_class::ContractClass;
 use crate::state::errors::StateError;
-use crate::state::state_api::{State, StateReader, StateResult, UpdatableState};
+use crate::state::state_api::{
+    verify_bulk_read_length, State, StateReader, StateResult, UpdatableState,
+};
 use crate::transaction::objects::TransactionExecutionInfo;
 use crate::utils::{strict_subtract_mappings, subtract_mappings};
 
@@ -34,6 +38,7 @@ pub struct CachedState<S: StateReader> {
     pub(crate) class_hash_to_class: RefCell<ContractClassMapping>,
     /// A map from class hash to the set of PC values that were visited in the class.
     pub visited_pcs: HashMap<ClassHash, HashSet<usize>>,
+    journal: UndoJournal,
 }
 
 impl<S: StateReader> CachedState<S> {
@@ -43,9 +48,126 @@ impl<S: StateReader> CachedState<S> {
             cache: RefCell::new(StateCache::default()),
             class_hash_to_class: RefCell::new(HashMap::default()),
             visited_pcs: HashMap::default(),
+            journal: UndoJournal::default(),
         }
     }
 
+    /// Opens a savepoint: the writes performed from now on may be undone by rolling back to it.
+    /// Savepoints may be nested, and are closed (by a rollback or a release) innermost first;
+    /// closing a savepoint also closes the savepoints opened after it.
+    pub fn savepoint(&mut self) -> Savepoint {
+        self.journal.open()
+    }
+
+    /// Returns whether the given savepoint is open; i.e., it was opened on this state, and was not
+    /// closed yet (directly, or along with an enclosing savepoint).
+    pub fn is_savepoint_open(&self, savepoint: &Savepoint) -> bool {
+        self.journal.is_open(savepoint)
+    }
+
+    /// Undoes the writes performed since the given savepoint was opened, and closes it.
+    /// Cached reads are kept, as the underlying state is not affected by the writes.
+    pub fn rollback_to_savepoint(&mut self, savepoint: Savepoint) {
+        let n_entries = self.journal.close(savepoint);
+        let writes = &mut self.cache.get_mut().writes;
+        let class_hash_to_class = self.class_hash_to_class.get_mut();
+        for entry in self.journal.entries.drain(n_entries..).rev() {
+            match entry {
+                JournalEntry::Storage(key, value) => restore(&mut writes.storage, key, value),
+                JournalEntry::Nonce(address, value) => restore(&mut writes.nonces, address, value),
+                JournalEntry::ClassHash(address, value) => {
+                    restore(&mut writes.class_hashes, address, value)
+                }
+                JournalEntry::CompiledClassHash(class_hash, value) => {
+                    restore(&mut writes.compiled_class_hashes, class_hash, value)
+                }
+                JournalEntry::DeclaredContract(class_hash, value) => {
+                    restore(&mut writes.declared_contracts, class_hash, value)
+                }
+                JournalEntry::ContractClass(class_hash, value) => {
+                    restore(class_hash_to_class, class_hash, value)
+                }
+                JournalEntry::VisitedPcs(class_hash, pcs) => {
+                    if let Some(class_visited_pcs) = self.visited_pcs.get_mut(&class_hash) {
+                        for pc in pcs {
+                            class_visited_pcs.remove(&pc);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Closes the given savepoint, keeping the writes performed since it was opened; they are
+    /// undone if an enclosing savepoint is rolled back.
+    pub fn release_savepoint(&mut self, savepoint: Savepoint) {
+        self.journal.close(savepoint);
+        if self.journal.savepoints.is_empty() {
+            self.journal.entries.clear();
+        }
+    }
+
+    /// Returns the state changes made since the given (open) savepoint was opened, with respect to
+    /// the state at the savepoint; e.g., restoring a cell to its value at the savepoint is not a
+    /// change, even if the cell was changed before the savepoint.
+    pub fn get_state_changes_since(&mut self, savepoint: &Savepoint) -> StateResult<StateChanges> {
+        self.update_initial_values_of_write_only_access()?;
+        let n_entries = self.journal.n_entries_at(savepoint);
+        let StateCache { initial_reads, writes } = &*self.cache.borrow();
+
+        // Iterate in reverse order, so that the value at the savepoint of each cell is taken from
+        // its earliest entry.
+        let mut current_values = StateMaps::default();
+        let mut values_at_savepoint = StateMaps::default();
+        for entry in self.journal.entries[n_entries..].iter().rev() {
+            match *entry {
+                JournalEntry::Storage(key, previous_value) => record_journaled_cell(
+                    key,
+                    previous_value,
+                    (&writes.storage, &initial_reads.storage),
+                    (&mut current_values.storage, &mut values_at_savepoint.storage),
+                ),
+                JournalEntry::Nonce(address, previous_value) => record_journaled_cell(
+                    address,
+                    previous_value,
+                    (&writes.nonces, &initial_reads.nonces),
+                    (&mut current_values.nonces, &mut values_at_savepoint.nonces),
+                ),
+                JournalEntry::ClassHash(address, previous_value) => record_journaled_cell(
+                    address,
+                    previous_value,
+                    (&writes.class_hashes, &initial_reads.class_hashes),
+                    (&mut current_values.class_hashes, &mut values_at_savepoint.class_hashes),
+                ),
+                JournalEntry::CompiledClassHash(class_hash, previous_value) => {
+                    record_journaled_cell(
+                        class_hash,
+                        previous_value,
+                        (&writes.compiled_class_hashes, &initial_reads.compiled_class_hashes),
+                        (
+                            &mut current_values.compiled_class_hashes,
+                            &mut values_at_savepoint.compiled_class_hashes,
+                        ),
+                    )
+                }
+                JournalEntry::DeclaredContract(class_hash, previous_value) => {
+                    record_journaled_cell(
+                        class_hash,
+                        previous_value,
+                        (&writes.declared_contracts, &initial_reads.declared_contracts),
+                        (
+                            &mut current_values.declared_contracts,
+                            &mut values_at_savepoint.declared_contracts,
+                        ),
+                    )
+                }
+                JournalEntry::ContractClass(..) | JournalEntry::VisitedPcs(..) => {}
+            }
+        }
+
+        Ok(current_values.diff(&values_at_savepoint).into())
+    }
+
     /// Returns the state diff resulting from the performed writes, with respect to the parent
     /// state.
     pub fn to_state_diff(&mut self) -> StateResult<StateMaps> {
@@ -68,9 +190,32 @@ impl<S: StateReader> CachedState<S> {
         for (&key, &value) in &write_updates.declared_contracts {
             assert_eq!(value, local_contract_cache_updates.contains_key(&key));
         }
-        let mut cache = self.cache.borrow_mut();
-        cache.writes.extend(write_updates);
-        self.class_hash_to_class.get_mut().extend(local_contract_cache_updates);
+        let writes = &mut self.cache.get_mut().writes;
+        for (&key, &value) in &write_updates.storage {
+            self.journal.record(JournalEntry::Storage(key, writes.storage.insert(key, value)));
+        }
+        for (&address, &nonce) in &write_updates.nonces {
+            self.journal.record(JournalEntry::Nonce(address, writes.nonces.insert(address, nonce)));
+        }
+        for (&address, &class_hash) in &write_updates.class_hashes {
+            let previous_class_hash = writes.class_hashes.insert(address, class_hash);
+            self.journal.record(JournalEntry::ClassHash(address, previous_class_hash));
+        }
+        for (&class_hash, &compiled_class_hash) in &write_updates.compiled_class_hashes {
+            let previous_compiled_class_hash =
+                writes.compiled_class_hashes.insert(class_hash, compiled_class_hash);
+            self.journal
+                .record(JournalEntry::CompiledClassHash(class_hash, previous_compiled_class_hash));
+        }
+        for (&class_hash, &is_declared) in &write_updates.declared_contracts {
+            let previous_is_declared = writes.declared_contracts.insert(class_hash, is_declared);
+            self.journal.record(JournalEntry::DeclaredContract(class_hash, previous_is_declared));
+        }
+        let class_hash_to_class = self.class_hash_to_class.get_mut();
+        for (class_hash, contract_class) in local_contract_cache_updates {
+            let previous_class = class_hash_to_class.insert(class_hash, contract_class);
+            self.journal.record(JournalEntry::ContractClass(class_hash, previous_class));
+        }
     }
 
     pub fn update_visited_pcs_cache(&mut self, visited_pcs: &HashMap<ClassHash, HashSet<usize>>) {
@@ -88,21 +233,26 @@ impl<S: StateReader> CachedState<S> {
     ///     contract is deployed before running any code.
     ///   * Compiled class hash: verify the class is not declared through
     ///     `get_compiled_contract_class`.
-    ///
-    /// TODO(Noa, 30/07/23): Consider adding DB getters in bulk (via a DB read transaction).
     fn update_initial_values_of_write_only_access(&mut self) -> StateResult<()> {
         let cache = &mut *self.cache.borrow_mut();
 
         // Eliminate storage writes that are identical to the initial value (no change).
-        for contract_storage_key in cache.writes.storage.keys() {
-            if !cache.initial_reads.storage.contains_key(contract_storage_key) {
-                // First access to this cell was write; cache initial value.
-                cache.initial_reads.storage.insert(
-                    *contract_storage_key,
-                    self.state.get_storage_at(contract_storage_key.0, contract_storage_key.1)?,
-                );
-            }
+        // First access to these cells was write; cache their initial values, read in bulk.
+        let write_only_keys: Vec<(ContractAddress, StorageKey)> = cache
+            .writes
+            .storage
+            .keys()
+            .filter(|contract_storage_key| {
+                !cache.initial_reads.storage.contains_key(contract_storage_key)
+            })
+            .copied()
+            .collect();
+        if write_only_keys.is_empty() {
+            return Ok(());
         }
+
+        let initial_values = self.state.get_storage_many(&write_only_keys)?;
+        cache.initial_reads.storage.extend(write_only_keys.into_iter().zip(initial_values));
         Ok(())
     }
 }
@@ -220,6 +370,71 @@ impl<S: StateReader> StateReader for CachedState<S> {
             .unwrap_or_else(|| panic!("Cannot retrieve '{class_hash:?}' from the cache."));
         Ok(*compiled_class_hash)
     }
+
+    // Note: the count does not reflect the cached writes.
+    fn get_n_initialized_storage_leaves(
+        &self,
+        contract_address: ContractAddress,
+    ) -> StateResult<Option<usize>> {
+        self.state.get_n_initialized_storage_leaves(contract_address)
+    }
+
+    fn get_storage_many(
+        &self,
+        storage_keys: &[(ContractAddress, StorageKey)],
+    ) -> StateResult<Vec<Felt>> {
+        let mut cache = self.cache.borrow_mut();
+
+        let missing_keys: Vec<(ContractAddress, StorageKey)> = storage_keys
+            .iter()
+            .filter(|&&(contract_address, key)| {
+                cache.get_storage_at(contract_address, key).is_none()
+            })
+            .copied()
+            .collect();
+        if !missing_keys.is_empty() {
+            let storage_values = self.state.get_storage_many(&missing_keys)?;
+            verify_bulk_read_length(missing_keys.len(), storage_values.len())?;
+            for ((contract_address, key), value) in missing_keys.into_iter().zip(storage_values) {
+                cache.set_storage_initial_value(contract_address, key, value);
+            }
+        }
+
+        Ok(storage_keys
+            .iter()
+            .map(|&(contract_address, key)| {
+                *cache.get_storage_at(contract_address, key).unwrap_or_else(|| {
+                    panic!("Cannot retrieve '{contract_address:?}' and '{key:?}' from the cache.")
+                })
+            })
+            .collect())
+    }
+
+    fn get_nonces_many(&self, contract_addresses: &[ContractAddress]) -> StateResult<Vec<Nonce>> {
+        let mut cache = self.cache.borrow_mut();
+
+        let missing_addresses: Vec<ContractAddress> = contract_addresses
+            .iter()
+            .filter(|&&contract_address| cache.get_nonce_at(contract_address).is_none())
+            .copied()
+            .collect();
+        if !missing_addresses.is_empty() {
+            let nonces = self.state.get_nonces_many(&missing_addresses)?;
+            verify_bulk_read_length(missing_addresses.len(), nonces.len())?;
+            for (contract_address, nonce) in missing_addresses.into_iter().zip(nonces) {
+                cache.set_nonce_initial_value(contract_address, nonce);
+            }
+        }
+
+        Ok(contract_addresses
+            .iter()
+            .map(|&contract_address| {
+                *cache.get_nonce_at(contract_address).unwrap_or_else(|| {
+                    panic!("Cannot retrieve '{contract_address:?}' from the cache.")
+                })
+            })
+            .collect())
+    }
 }
 
 impl<S: StateReader> State for CachedState<S> {
@@ -229,7 +444,8 @@ impl<S: StateReader> State for CachedState<S> {
         key: StorageKey,
         value: Felt,
     ) -> StateResult<()> {
-        self.cache.get_mut().set_storage_value(contract_address, key, value);
+        let previous_value = self.cache.get_mut().set_storage_value(contract_address, key, value);
+        self.journal.record(JournalEntry::Storage((contract_address, key), previous_value));
 
         Ok(())
     }
@@ -237,7 +453,8 @@ impl<S: StateReader> State for CachedState<S> {
     fn increment_nonce(&mut self, contract_address: ContractAddress) -> StateResult<()> {
         let current_nonce = self.get_nonce_at(contract_address)?;
         let next_nonce = Nonce(current_nonce.0 + Felt::ONE);
-        self.cache.get_mut().set_nonce_value(contract_address, next_nonce);
+        let previous_nonce = self.cache.get_mut().set_nonce_value(contract_address, next_nonce);
+        self.journal.record(JournalEntry::Nonce(contract_address, previous_nonce));
 
         Ok(())
     }
@@ -251,7 +468,9 @@ impl<S: StateReader> State for CachedState<S> {
             return Err(StateError::OutOfRangeContractAddress);
         }
 
-        self.cache.get_mut().set_class_hash_write(contract_address, class_hash);
+        let previous_class_hash =
+            self.cache.get_mut().set_class_hash_write(contract_address, class_hash);
+        self.journal.record(JournalEntry::ClassHash(contract_address, previous_class_hash));
         Ok(())
     }
 
@@ -260,9 +479,10 @@ impl<S: StateReader> State for CachedState<S> {
         class_hash: ClassHash,
         contract_class: ContractClass,
     ) -> StateResult<()> {
-        self.class_hash_to_class.get_mut().insert(class_hash, contract_class);
-        let mut cache = self.cache.borrow_mut();
-        cache.declare_contract(class_hash);
+        let previous_class = self.class_hash_to_class.get_mut().insert(class_hash, contract_class);
+        self.journal.record(JournalEntry::ContractClass(class_hash, previous_class));
+        let previous_is_declared = self.cache.get_mut().declare_contract(class_hash);
+        self.journal.record(JournalEntry::DeclaredContract(class_hash, previous_is_declared));
         Ok(())
     }
 
@@ -271,12 +491,18 @@ impl<S: StateReader> State for CachedState<S> {
         class_hash: ClassHash,
         compiled_class_hash: CompiledClassHash,
     ) -> StateResult<()> {
-        self.cache.get_mut().set_compiled_class_hash_write(class_hash, compiled_class_hash);
+        let previous_compiled_class_hash =
+            self.cache.get_mut().set_compiled_class_hash_write(class_hash, compiled_class_hash);
+        self.journal
+            .record(JournalEntry::CompiledClassHash(class_hash, previous_compiled_class_hash));
         Ok(())
     }
 
     fn add_visited_pcs(&mut self, class_hash: ClassHash, pcs: &HashSet<usize>) {
-        self.visited_pcs.entry(class_hash).or_default().extend(pcs);
+        let class_visited_pcs = self.visited_pcs.entry(class_hash).or_default();
+        let newly_visited_pcs =
+            pcs.iter().copied().filter(|pc| class_visited_pcs.insert(*pc)).collect();
+        self.journal.record(JournalEntry::VisitedPcs(class_hash, newly_visited_pcs));
     }
 }
 
@@ -288,6 +514,7 @@ impl Default for CachedState<crate::test_utils::dict_state_reader::DictStateRead
             cache: Default::default(),
             class_hash_to_class: Default::default(),
             visited_pcs: Default::default(),
+            journal: Default::default(),
         }
     }
 }
@@ -314,8 +541,7 @@ impl From<StorageView> for IndexMap<ContractAddress, IndexMap<StorageKey, Felt>>
     }
 }
 
-#[cfg_attr(any(feature = "testing", test), derive(Clone))]
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct StateMaps {
     pub nonces: HashMap<ContractAddress, Nonce>,
     pub class_hashes: HashMap<ContractAddress, ClassHash>,
@@ -356,7 +582,8 @@ impl StateMaps {
 /// Caches read and write requests.
 /// The tracked changes are needed for block state commitment.
 
-// Invariant: keys cannot be deleted from fields (only used internally by the cached state).
+// Invariant: keys cannot be deleted from fields (only used internally by the cached state), except
+// for writes undone by a rollback to a savepoint.
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct StateCache {
     // Reader's cached information; initial values, read before any write operation (per cell).
@@ -373,8 +600,8 @@ impl StateCache {
         self.writes.diff(&self.initial_reads)
     }
 
-    fn declare_contract(&mut self, class_hash: ClassHash) {
-        self.writes.declared_contracts.insert(class_hash, true);
+    fn declare_contract(&mut self, class_hash: ClassHash) -> Option<bool> {
+        self.writes.declared_contracts.insert(class_hash, true)
     }
 
     fn set_declared_contract_initial_values(&mut self, class_hash: ClassHash, is_declared: bool) {
@@ -411,17 +638,21 @@ impl StateCache {
         contract_address: ContractAddress,
         key: StorageKey,
         value: Felt,
-    ) {
+    ) -> Option<Felt> {
         let contract_storage_key = (contract_address, key);
-        self.writes.storage.insert(contract_storage_key, value);
+        self.writes.storage.insert(contract_storage_key, value)
     }
 
     fn set_nonce_initial_value(&mut self, contract_address: ContractAddress, nonce: Nonce) {
         self.initial_reads.nonces.insert(contract_address, nonce);
     }
 
-    fn set_nonce_value(&mut self, contract_address: ContractAddress, nonce: Nonce) {
-        self.writes.nonces.insert(contract_address, nonce);
+    fn set_nonce_value(
+        &mut self,
+        contract_address: ContractAddress,
+        nonce: Nonce,
+    ) -> Option<Nonce> {
+        self.writes.nonces.insert(contract_address, nonce)
     }
 
     fn get_class_hash_at(&self, contract_address: ContractAddress) -> Option<&ClassHash> {
@@ -439,8 +670,12 @@ impl StateCache {
         self.initial_reads.class_hashes.insert(contract_address, class_hash);
     }
 
-    fn set_class_hash_write(&mut self, contract_address: ContractAddress, class_hash: ClassHash) {
-        self.writes.class_hashes.insert(contract_address, class_hash);
+    fn set_class_hash_write(
+        &mut self,
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+    ) -> Option<ClassHash> {
+        self.writes.class_hashes.insert(contract_address, class_hash)
     }
 
     fn get_compiled_class_hash(&self, class_hash: ClassHash) -> Option<&CompiledClassHash> {
@@ -462,11 +697,97 @@ impl StateCache {
         &mut self,
         class_hash: ClassHash,
         compiled_class_hash: CompiledClassHash,
-    ) {
-        self.writes.compiled_class_hashes.insert(class_hash, compiled_class_hash);
+    ) -> Option<CompiledClassHash> {
+        self.writes.compiled_class_hashes.insert(class_hash, compiled_class_hash)
     }
 }
 
+/// A savepoint of a `CachedState`, to roll back to or release; see `CachedState::savepoint`.
+#[derive(Debug)]
+#[must_use = "A savepoint must be rolled back to or released."]
+pub struct Savepoint {
+    depth: usize,
+    // Tells the savepoint apart from the savepoints opened at the same depth before or after it.
+    id: usize,
+}
+
+/// The previous value of a cell written by a `CachedState` (`None` if it was not written before),
+/// or the PCs newly marked as visited; used to undo the write.
+#[derive(Debug)]
+enum JournalEntry {
+    Storage(StorageEntry, Option<Felt>),
+    Nonce(ContractAddress, Option<Nonce>),
+    ClassHash(ContractAddress, Option<ClassHash>),
+    CompiledClassHash(ClassHash, Option<CompiledClassHash>),
+    DeclaredContract(ClassHash, Option<bool>),
+    ContractClass(ClassHash, Option<ContractClass>),
+    VisitedPcs(ClassHash, Vec<usize>),
+}
+
+/// Records the writes performed on a `CachedState` while a savepoint is open.
+#[derive(Debug, Default)]
+struct UndoJournal {
+    entries: Vec<JournalEntry>,
+    // The ID of each open savepoint and the number of entries at its opening, outermost first.
+    savepoints: Vec<(usize, usize)>,
+    n_opened_savepoints: usize,
+}
+
+impl UndoJournal {
+    fn open(&mut self) -> Savepoint {
+        let savepoint = Savepoint { depth: self.savepoints.len(), id: self.n_opened_savepoints };
+        self.savepoints.push((savepoint.id, self.entries.len()));
+        self.n_opened_savepoints += 1;
+        savepoint
+    }
+
+    fn is_open(&self, savepoint: &Savepoint) -> bool {
+        self.savepoints.get(savepoint.depth).is_some_and(|&(id, _)| id == savepoint.id)
+    }
+
+    fn record(&mut self, entry: JournalEntry) {
+        if !self.savepoints.is_empty() {
+            self.entries.push(entry);
+        }
+    }
+
+    /// Returns the number of entries at the opening of the given (open) savepoint.
+    fn n_entries_at(&self, savepoint: &Savepoint) -> usize {
+        assert!(self.is_open(savepoint), "Savepoint {} is already closed.", savepoint.id);
+        let (_, n_entries) = self.savepoints[savepoint.depth];
+        n_entries
+    }
+
+    /// Closes the given savepoint, along with the savepoints opened after it, and returns the
+    /// number of entries at its opening.
+    fn close(&mut self, savepoint: Savepoint) -> usize {
+        let n_entries = self.n_entries_at(&savepoint);
+        self.savepoints.truncate(savepoint.depth);
+        n_entries
+    }
+}
+
+fn restore<K: Eq + Hash, V>(map: &mut HashMap<K, V>, key: K, previous_value: Option<V>) {
+    match previous_value {
+        Some(value) => map.insert(key, value),
+        None => map.remove(&key),
+    };
+}
+
+/// Records the current value of a cell written since a savepoint, and its value at the savepoint:
+/// its previous write (given by a journal entry), if any, and its initial value otherwise.
+fn record_journaled_cell<K: Copy + Eq + Hash, V: Copy>(
+    key: K,
+    previous_value: Option<V>,
+    (writes, initial_reads): (&HashMap<K, V>, &HashMap<K, V>),
+    (current_values, values_at_savepoint): (&mut HashMap<K, V>, &mut HashMap<K, V>),
+) {
+    if let Some(&current_value) = writes.get(&key) {
+        current_values.insert(key, current_value);
+    }
+    restore(values_at_savepoint, key, previous_value.or_else(|| initial_reads.get(&key).copied()));
+}
+
 /// Wraps a mutable reference to a `State` object, exposing its API.
 /// Used to pass ownership to a `CachedState`.
 pub struct MutRefState<'a, S: StateReader + ?Sized>(&'a mut S);
@@ -502,6 +823,24 @@ impl<'a, S: StateReader + ?Sized> StateReader for MutRefState<'a, S> {
     fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
         self.0.get_compiled_class_hash(class_hash)
     }
+
+    fn get_n_initialized_storage_leaves(
+        &self,
+        contract_address: ContractAddress,
+    ) -> StateResult<Option<usize>> {
+        self.0.get_n_initialized_storage_leaves(contract_address)
+    }
+
+    fn get_storage_many(
+        &self,
+        storage_keys: &[(ContractAddress, StorageKey)],
+    ) -> StateResult<Vec<Felt>> {
+        self.0.get_storage_many(storage_keys)
+    }
+
+    fn get_nonces_many(&self, contract_addresses: &[ContractAddress]) -> StateResult<Vec<Nonce>> {
+        self.0.get_nonces_many(contract_addresses)
+    }
 }
 
 pub type TransactionalState<'a, U> = CachedState<MutRefState<'a, U>>;
@@ -523,6 +862,10 @@ impl<'a, S: StateReader> TransactionalState<'a, S> {
 impl<'a, U: UpdatableState> TransactionalState<'a, U> {
     /// Commits changes in the child (wrapping) state to its parent.
     pub fn commit(self) {
+        debug_assert!(
+            self.journal.savepoints.is_empty() && self.journal.entries.is_empty(),
+            "Savepoints must be closed before committing."
+        );
         let state = self.state.0;
         let child_cache = self.cache.into_inner();
         state.apply_writes(
@@ -567,13 +910,13 @@ impl From<StateMaps> for CommitmentStateDiff {
 #[cfg_attr(any(feature = "testing", test), derive(Clone))]
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct StateChangesKeys {
-    nonce_keys: HashSet<ContractAddress>,
-    class_hash_keys: HashSet<ContractAddress>,
-    storage_keys: HashSet<StorageEntry>,
-    compiled_class_hash_keys: HashSet<ClassHash>,
+    pub(crate) nonce_keys: HashSet<ContractAddress>,
+    pub(crate) class_hash_keys: HashSet<ContractAddress>,
+    pub(crate) storage_keys: HashSet<StorageEntry>,
+    pub(crate) compiled_class_hash_keys: HashSet<ClassHash>,
     // Note: this field may not be consistent with the above keys; specifically, it may be
     // strictlly contained in them. For example, as a result of a `difference` operation.
-    modified_contracts: HashSet<ContractAddress>,
+    pub(crate) modified_contracts: HashSet<ContractAddress>,
 }
 
 impl StateChangesKeys {
@@ -610,6 +953,22 @@ impl StateChangesKeys {
         self.modified_contracts.extend(&other.modified_contracts);
     }
 
+    // Removes the keys of `other` from each set member; undoes extending `self` by the residual
+    // contribution of `other`.
+    pub fn remove(&mut self, other: &Self) {
+        fn remove_keys<K: Eq + Hash>(keys: &mut HashSet<K>, removed_keys: &HashSet<K>) {
+            for key in removed_keys {
+                keys.remove(key);
+            }
+        }
+
+        remove_keys(&mut self.nonce_keys, &other.nonce_keys);
+        remove_keys(&mut self.class_hash_keys, &other.class_hash_keys);
+        remove_keys(&mut self.storage_keys, &other.storage_keys);
+        remove_keys(&mut self.compiled_class_hash_keys, &other.compiled_class_hash_keys);
+        remove_keys(&mut self.modified_contracts, &other.modified_contracts);
+    }
+
     pub fn update_sequencer_key_in_storage(
         &mut self,
         tx_context: &TransactionContext,
@@ -644,7 +1003,7 @@ impl StateChangesKeys {
 
 /// Holds the state changes.
 #[cfg_attr(any(feature = "testing", test), derive(Clone))]
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct StateChanges(pub StateMaps);
 
 impl StateChanges {
@@ -728,3 +1087,131 @@ pub struct StateChangesCount {
     pub n_compiled_class_hash_updates: usize,
     pub n_modified_contracts: usize,
 }
+
+pub(crate) type SortedStorage = BTreeMap<ContractAddress, BTreeMap<StorageKey, Felt>>;
+
+pub(crate) fn sort_map<'a, K: Copy + Ord + 'a, V: Copy + 'a>(
+    map: impl IntoIterator<Item = (&'a K, &'a V)>,
+) -> BTreeMap<K, V> {
+    map.into_iter().map(|(&key, &value)| (key, value)).collect()
+}
+
+pub(crate) fn sort_storage<'a>(
+    storage: impl IntoIterator<Item = (&'a StorageEntry, &'a Felt)>,
+) -> SortedStorage {
+    let mut sorted_storage = SortedStorage::new();
+    for (&(address, key), &value) in storage {
+        sorted_storage.entry(address).or_default().insert(key, value);
+    }
+    sorted_storage
+}
+
+impl StateMaps {
+    /// Converts the diff into a `ThinStateDiff`, given the initial class hashes of the touched
+    /// contracts; a class hash write to an address with no prior class is a deployment, otherwise
+    /// it is a class replacement. All entries are ordered by their keys.
+    pub fn to_thin_state_diff(&self, initial_class_hashes: &StateMaps) -> ThinStateDiff {
+        let compiled_class_hashes = sort_map(&self.compiled_class_hashes);
+        let deprecated_declared_classes = sort_map(&self.declared_contracts)
+            .into_iter()
+            .filter(|(class_hash, is_declared)| {
+                *is_declared && !compiled_class_hashes.contains_key(class_hash)
+            })
+            .map(|(class_hash, _)| class_hash)
+            .collect();
+        to_thin_state_diff(
+            sort_map(&self.class_hashes),
+            sort_storage(&self.storage),
+            compiled_class_hashes,
+            deprecated_declared_classes,
+            sort_map(&self.nonces),
+            initial_class_hashes,
+        )
+    }
+}
+
+/// Converts a `ThinStateDiff` into a diff; deployed contracts and replaced classes are both class
+/// hash writes, and all declared classes are marked as declared.
+impl From<ThinStateDiff> for StateMaps {
+    fn from(diff: ThinStateDiff) -> Self {
+        let ThinStateDiff {
+            deployed_contracts,
+            storage_diffs,
+            declared_classes,
+            deprecated_declared_classes,
+            nonces,
+            replaced_classes,
+        } = diff;
+
+        let declared_contracts = declared_classes
+            .keys()
+            .chain(&deprecated_declared_classes)
+            .map(|&class_hash| (class_hash, true))
+            .collect();
+        Self {
+            nonces: nonces.into_iter().collect(),
+            class_hashes: deployed_contracts.into_iter().chain(replaced_classes).collect(),
+            storage: storage_diffs
+                .into_iter()
+                .flat_map(|(address, contract_storage)| {
+                    contract_storage.into_iter().map(move |(key, value)| ((address, key), value))
+                })
+                .collect(),
+            compiled_class_hashes: declared_classes.into_iter().collect(),
+            declared_contracts,
+        }
+    }
+}
+
+impl CommitmentStateDiff {
+    /// Converts the diff into a `ThinStateDiff`, given the initial class hashes of the touched
+    /// contracts (see `StateMaps::to_thin_state_diff`) and the declared Cairo 0 classes, which are
+    /// not part of the diff. All entries are ordered by their keys.
+    pub fn to_thin_state_diff(
+        &self,
+        initial_class_hashes: &StateMaps,
+        deprecated_declared_classes: &[ClassHash],
+    ) -> ThinStateDiff {
+        let mut deprecated_declared_classes = deprecated_declared_classes.to_vec();
+        deprecated_declared_classes.sort();
+        to_thin_state_diff(
+            sort_map(&self.address_to_class_hash),
+            self.storage_updates
+                .iter()
+                .map(|(&address, contract_storage)| (address, sort_map(contract_storage)))
+                .collect(),
+            sort_map(&self.class_hash_to_compiled_class_hash),
+            deprecated_declared_classes,
+            sort_map(&self.address_to_nonce),
+            initial_class_hashes,
+        )
+    }
+}
+
+fn to_thin_state_diff(
+    class_hashes: BTreeMap<ContractAddress, ClassHash>,
+    storage: SortedStorage,
+    compiled_class_hashes: BTreeMap<ClassHash, CompiledClassHash>,
+    deprecated_declared_classes: Vec<ClassHash>,
+    nonces: BTreeMap<ContractAddress, Nonce>,
+    initial_class_hashes: &StateMaps,
+) -> ThinStateDiff {
+    let (replaced_classes, deployed_contracts) =
+        class_hashes.into_iter().partition(|(address, _)| {
+            matches!(
+                initial_class_hashes.class_hashes.get(address),
+                Some(initial_class_hash) if *initial_class_hash != ClassHash::default()
+            )
+        });
+    ThinStateDiff {
+        deployed_contracts,
+        storage_diffs: storage
+            .into_iter()
+            .map(|(address, contract_storage)| (address, contract_storage.into_iter().collect()))
+            .collect(),
+        declared_classes: compiled_class_hashes.into_iter().collect(),
+        deprecated_declared_classes,
+        nonces: nonces.into_iter().collect(),
+        replaced_classes,
+    }
+}
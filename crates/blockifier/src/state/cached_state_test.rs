@@ -1,10 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use assert_matches::assert_matches;
 use indexmap::indexmap;
 use pretty_assertions::assert_eq;
 use rstest::rstest;
 use starknet_api::core::PatriciaKey;
+use starknet_api::state::ThinStateDiff;
 use starknet_api::{class_hash, contract_address, felt, patricia_key};
 
 use crate::context::{BlockContext, ChainInfo};
@@ -70,6 +71,108 @@ fn get_and_set_storage_value() {
     assert_eq!(state.get_storage_at(contract_address1, key1).unwrap(), modified_storage_value1);
 }
 
+#[test]
+fn get_storage_and_nonces_many() {
+    let contract_address0 = contract_address!("0x100");
+    let contract_address1 = contract_address!("0x200");
+    let (key0, key1, key2) =
+        (storage_key!(0x10_u16), storage_key!(0x20_u16), storage_key!(0x30_u16));
+    let mut state = CachedState::from(DictStateReader {
+        storage_view: HashMap::from([
+            ((contract_address0, key0), felt!("0x1")),
+            ((contract_address1, key1), felt!("0x5")),
+        ]),
+        address_to_nonce: HashMap::from([(contract_address1, nonce!(3_u8))]),
+        ..Default::default()
+    });
+    state.set_storage_at(contract_address0, key0, felt!("0xA")).unwrap();
+    state.increment_nonce(contract_address1).unwrap();
+
+    // Values are read from the cache if present, and from the underlying state otherwise.
+    let storage_keys =
+        [(contract_address0, key0), (contract_address1, key1), (contract_address0, key2)];
+    assert_eq!(
+        state.get_storage_many(&storage_keys).unwrap(),
+        vec![felt!("0xA"), felt!("0x5"), Felt::ZERO]
+    );
+    assert_eq!(
+        state.get_nonces_many(&[contract_address0, contract_address1]).unwrap(),
+        vec![nonce!(0_u8), nonce!(4_u8)]
+    );
+
+    // The values read from the underlying state are cached as initial values.
+    let cache = state.cache.borrow();
+    let initial_reads = &cache.initial_reads;
+    assert_eq!(initial_reads.storage.get(&(contract_address1, key1)), Some(&felt!("0x5")));
+    assert_eq!(initial_reads.storage.get(&(contract_address0, key2)), Some(&Felt::ZERO));
+    assert_eq!(initial_reads.nonces.get(&contract_address0), Some(&nonce!(0_u8)));
+}
+
+/// A state reader whose bulk reads drop the last requested value.
+struct TruncatingStateReader(DictStateReader);
+
+impl StateReader for TruncatingStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.0.get_storage_at(contract_address, key)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.0.get_nonce_at(contract_address)
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.0.get_class_hash_at(contract_address)
+    }
+
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        self.0.get_compiled_contract_class(class_hash)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.0.get_compiled_class_hash(class_hash)
+    }
+
+    fn get_storage_many(
+        &self,
+        storage_keys: &[(ContractAddress, StorageKey)],
+    ) -> StateResult<Vec<Felt>> {
+        let mut values = self.0.get_storage_many(storage_keys)?;
+        values.pop();
+        Ok(values)
+    }
+
+    fn get_nonces_many(&self, contract_addresses: &[ContractAddress]) -> StateResult<Vec<Nonce>> {
+        let mut nonces = self.0.get_nonces_many(contract_addresses)?;
+        nonces.pop();
+        Ok(nonces)
+    }
+}
+
+#[test]
+fn get_storage_and_nonces_many_length_mismatch() {
+    let state = CachedState::new(TruncatingStateReader(DictStateReader::default()));
+    let contract_address = contract_address!(CONTRACT_ADDRESS);
+
+    assert_matches!(
+        state.get_storage_many(&[(contract_address, storage_key!(0x10_u16))]).unwrap_err(),
+        StateError::StateReadError(_)
+    );
+    assert_matches!(
+        state.get_nonces_many(&[contract_address]).unwrap_err(),
+        StateError::StateReadError(_)
+    );
+    // Reads of the balance are bulk reads as well.
+    let mut state_reader = TruncatingStateReader(DictStateReader::default());
+    assert_matches!(
+        state_reader.get_fee_token_balance(contract_address, contract_address).unwrap_err(),
+        StateError::StateReadError(_)
+    );
+}
+
 #[test]
 fn cast_between_storage_mapping_types() {
     let empty_map: IndexMap<ContractAddress, IndexMap<StorageKey, Felt>> = IndexMap::default();
@@ -413,6 +516,125 @@ fn test_state_changes_merge(
     );
 }
 
+#[test]
+fn test_nested_savepoints() {
+    let contract_address = contract_address!("0x100");
+    let (key0, key1) = (storage_key!(0x10_u16), storage_key!(0x20_u16));
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let class_hash = test_contract.get_class_hash();
+    let mut state = CachedState::from(DictStateReader {
+        storage_view: HashMap::from([((contract_address, key0), felt!("0x1"))]),
+        ..Default::default()
+    });
+    state.set_storage_at(contract_address, key0, felt!("0x2")).unwrap();
+
+    let outer_savepoint = state.savepoint();
+    state.set_storage_at(contract_address, key0, felt!("0x3")).unwrap();
+    state.increment_nonce(contract_address).unwrap();
+
+    // Roll back the inner savepoint.
+    let inner_savepoint = state.savepoint();
+    state.set_storage_at(contract_address, key1, felt!("0x4")).unwrap();
+    state.set_class_hash_at(contract_address, class_hash).unwrap();
+    state.set_contract_class(class_hash, test_contract.get_class()).unwrap();
+    state.add_visited_pcs(class_hash, &HashSet::from([7]));
+    state.rollback_to_savepoint(inner_savepoint);
+    assert_eq!(state.get_storage_at(contract_address, key0).unwrap(), felt!("0x3"));
+    assert_eq!(state.get_storage_at(contract_address, key1).unwrap(), Felt::ZERO);
+    assert_eq!(state.get_class_hash_at(contract_address).unwrap(), ClassHash::default());
+    assert_matches!(
+        state.get_compiled_contract_class(class_hash).unwrap_err(),
+        StateError::UndeclaredClassHash(undeclared_class_hash) if
+        undeclared_class_hash == class_hash
+    );
+    assert!(state.visited_pcs[&class_hash].is_empty());
+
+    // Release a savepoint; its writes are undone with the enclosing savepoint.
+    let inner_savepoint = state.savepoint();
+    state.set_storage_at(contract_address, key1, felt!("0x5")).unwrap();
+    state.release_savepoint(inner_savepoint);
+    assert_eq!(state.get_storage_at(contract_address, key1).unwrap(), felt!("0x5"));
+    state.rollback_to_savepoint(outer_savepoint);
+    assert_eq!(state.get_storage_at(contract_address, key0).unwrap(), felt!("0x2"));
+    assert_eq!(state.get_storage_at(contract_address, key1).unwrap(), Felt::ZERO);
+    assert_eq!(state.get_nonce_at(contract_address).unwrap(), nonce!(0_u8));
+    assert_eq!(
+        state.to_state_diff().unwrap(),
+        StateMaps {
+            storage: HashMap::from([((contract_address, key0), felt!("0x2"))]),
+            ..Default::default()
+        }
+    );
+
+    // Writes outside savepoints are not journaled.
+    state.set_storage_at(contract_address, key1, felt!("0x6")).unwrap();
+    assert!(state.journal.entries.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Savepoint 1 is already closed.")]
+fn test_closed_savepoint() {
+    let mut state = CachedState::from(DictStateReader::default());
+    let outer_savepoint = state.savepoint();
+    let inner_savepoint = state.savepoint();
+
+    // Rolling back to a savepoint closes the savepoints opened after it.
+    state.rollback_to_savepoint(outer_savepoint);
+    assert!(!state.is_savepoint_open(&inner_savepoint));
+
+    // A closed savepoint is not confused with a savepoint opened at the same depth after it.
+    let _outer_savepoint = state.savepoint();
+    let new_inner_savepoint = state.savepoint();
+    assert!(state.is_savepoint_open(&new_inner_savepoint));
+    assert!(!state.is_savepoint_open(&inner_savepoint));
+    state.rollback_to_savepoint(inner_savepoint);
+}
+
+#[test]
+fn test_state_changes_since_savepoint() {
+    let contract_address = contract_address!(CONTRACT_ADDRESS);
+    let (key0, key1, key2) =
+        (storage_key!(0x10_u16), storage_key!(0x20_u16), storage_key!(0x30_u16));
+    let mut state = CachedState::from(DictStateReader {
+        storage_view: HashMap::from([
+            ((contract_address, key0), felt!("0x1")),
+            ((contract_address, key1), felt!("0x1")),
+        ]),
+        ..Default::default()
+    });
+
+    // Validation changes a cell, and execution restores it to its initial value.
+    state.set_storage_at(contract_address, key0, felt!("0x2")).unwrap();
+    let validate_state_changes = state.get_actual_state_changes().unwrap();
+    let execution_savepoint = state.savepoint();
+    state.set_storage_at(contract_address, key0, felt!("0x1")).unwrap();
+    // Writes of the value at the savepoint are not changes.
+    state.set_storage_at(contract_address, key1, felt!("0x1")).unwrap();
+    state.set_storage_at(contract_address, key2, felt!("0x3")).unwrap();
+    state.set_storage_at(contract_address, key2, Felt::ZERO).unwrap();
+    state.increment_nonce(contract_address).unwrap();
+
+    let execution_state_changes = state.get_state_changes_since(&execution_savepoint).unwrap();
+    assert_eq!(
+        execution_state_changes,
+        StateChanges(StateMaps {
+            storage: HashMap::from([((contract_address, key0), felt!("0x1"))]),
+            nonces: HashMap::from([(contract_address, nonce!(1_u8))]),
+            ..Default::default()
+        })
+    );
+
+    // Both the validation and the execution changes are charged for, although the overall state
+    // diff does not include the restored cell.
+    let merged_state_changes =
+        StateChanges::merge(vec![validate_state_changes, execution_state_changes]);
+    assert!(merged_state_changes.0.storage.contains_key(&(contract_address, key0)));
+    assert!(
+        !state.get_actual_state_changes().unwrap().0.storage.contains_key(&(contract_address, key0))
+    );
+    state.release_savepoint(execution_savepoint);
+}
+
 #[test]
 fn test_contract_cache_is_used() {
     // Initialize the global cache with a single class, and initialize an empty state with this
@@ -603,3 +825,118 @@ fn test_state_maps() {
 
     assert_eq!(maps, empty);
 }
+
+fn state_maps_for_serialization() -> StateMaps {
+    let (contract_address0, contract_address1) =
+        (contract_address!("0x100"), contract_address!("0x200"));
+    let (class_hash0, class_hash1, class_hash2) =
+        (class_hash!("0x300"), class_hash!("0x301"), class_hash!("0x302"));
+    StateMaps {
+        nonces: HashMap::from([
+            (contract_address1, nonce!(1_u8)),
+            (contract_address0, nonce!(2_u8)),
+        ]),
+        class_hashes: HashMap::from([
+            (contract_address0, class_hash1),
+            (contract_address1, class_hash0),
+        ]),
+        storage: HashMap::from([
+            ((contract_address1, storage_key!(0x20_u16)), felt!("0x7")),
+            ((contract_address0, storage_key!(0x30_u16)), felt!("0x8")),
+            ((contract_address1, storage_key!(0x10_u16)), felt!("0x9")),
+        ]),
+        compiled_class_hashes: HashMap::from([(class_hash1, compiled_class_hash!(3_u8))]),
+        declared_contracts: HashMap::from([
+            (class_hash0, true),
+            (class_hash1, true),
+            (class_hash2, false),
+        ]),
+    }
+}
+
+#[test]
+fn test_state_diff_serialization() {
+    let state_maps = state_maps_for_serialization();
+
+    // The serialization is canonical: equal diffs (with differently ordered hash maps) are
+    // serialized identically.
+    let serialized_state_maps = serde_json::to_string(&state_maps).unwrap();
+    let rebuilt_state_maps = StateMaps {
+        storage: state_maps.storage.iter().map(|(&k, &v)| (k, v)).collect(),
+        ..state_maps.clone()
+    };
+    assert_eq!(serde_json::to_string(&rebuilt_state_maps).unwrap(), serialized_state_maps);
+    assert_eq!(serde_json::from_str::<StateMaps>(&serialized_state_maps).unwrap(), state_maps);
+
+    let state_changes = StateChanges(state_maps.clone());
+    let serialized_state_changes = serde_json::to_string(&state_changes).unwrap();
+    assert_eq!(serialized_state_changes, serialized_state_maps);
+    assert_eq!(
+        serde_json::from_str::<StateChanges>(&serialized_state_changes).unwrap(),
+        state_changes
+    );
+
+    let keys = state_changes.into_keys();
+    let serialized_keys = serde_json::to_string(&keys).unwrap();
+    assert_eq!(serde_json::from_str::<StateChangesKeys>(&serialized_keys).unwrap(), keys);
+
+    let commitment_state_diff = CommitmentStateDiff::from(state_maps);
+    let serialized_commitment_state_diff = serde_json::to_string(&commitment_state_diff).unwrap();
+    assert_eq!(
+        serde_json::from_str::<CommitmentStateDiff>(&serialized_commitment_state_diff).unwrap(),
+        commitment_state_diff
+    );
+}
+
+#[test]
+fn test_thin_state_diff_conversion() {
+    let state_maps = state_maps_for_serialization();
+    let (contract_address0, contract_address1) =
+        (contract_address!("0x100"), contract_address!("0x200"));
+    let (class_hash0, class_hash1) = (class_hash!("0x300"), class_hash!("0x301"));
+    let initial_class_hashes = StateMaps {
+        class_hashes: HashMap::from([
+            (contract_address0, class_hash0),
+            (contract_address1, ClassHash::default()),
+        ]),
+        ..Default::default()
+    };
+
+    let thin_state_diff = state_maps.to_thin_state_diff(&initial_class_hashes);
+    let storage_diffs = indexmap! {
+        contract_address0 => indexmap! {storage_key!(0x30_u16) => felt!("0x8")},
+        contract_address1 => indexmap! {
+            storage_key!(0x10_u16) => felt!("0x9"),
+            storage_key!(0x20_u16) => felt!("0x7"),
+        },
+    };
+    let nonces = indexmap! {contract_address0 => nonce!(2_u8), contract_address1 => nonce!(1_u8)};
+    assert_eq!(
+        thin_state_diff,
+        ThinStateDiff {
+            deployed_contracts: indexmap! {contract_address1 => class_hash0},
+            storage_diffs: storage_diffs.clone(),
+            declared_classes: indexmap! {class_hash1 => compiled_class_hash!(3_u8)},
+            deprecated_declared_classes: vec![class_hash0],
+            nonces: nonces.clone(),
+            replaced_classes: indexmap! {contract_address0 => class_hash1},
+        }
+    );
+    // Entries are ordered by their keys.
+    assert!(thin_state_diff.nonces.keys().eq(nonces.keys()));
+    assert!(thin_state_diff.storage_diffs[&contract_address1]
+        .keys()
+        .eq(storage_diffs[&contract_address1].keys()));
+
+    // A commitment state diff yields the same diff, given its declared Cairo 0 classes.
+    assert_eq!(
+        CommitmentStateDiff::from(state_maps.clone())
+            .to_thin_state_diff(&initial_class_hashes, &[class_hash0]),
+        thin_state_diff
+    );
+
+    // Converting back drops the undeclared classes only.
+    let mut declared_state_maps = state_maps;
+    declared_state_maps.declared_contracts.retain(|_, is_declared| *is_declared);
+    assert_eq!(StateMaps::from(thin_state_diff), declared_state_maps);
+}
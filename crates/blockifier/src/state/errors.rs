@@ -1,3 +1,5 @@
+use std::io;
+
 use cairo_vm::types::errors::program_errors::ProgramError;
 use num_bigint::{BigUint, TryFromBigIntError};
 use starknet_api::core::{ClassHash, ContractAddress};
@@ -5,6 +7,8 @@ use starknet_api::StarknetApiError;
 use thiserror::Error;
 
 use crate::abi::constants;
+use crate::execution::errors::ContractClassError;
+use crate::state::witness::WitnessKey;
 
 #[derive(Debug, Error)]
 pub enum StateError {
@@ -15,6 +19,8 @@ pub enum StateError {
         constants::STORED_BLOCK_HASH_BUFFER
     )]
     OldBlockHashNotProvided,
+    #[error("The block witness is missing the entry {0:?}.")]
+    MissingWitnessEntry(WitnessKey),
     #[error("Cannot deploy contract at address 0.")]
     OutOfRangeContractAddress,
     #[error(transparent)]
@@ -29,3 +35,21 @@ pub enum StateError {
     #[error("Failed to read from state: {0}.")]
     StateReadError(String),
 }
+
+#[derive(Debug, Error)]
+pub enum StateDumpError {
+    #[error(transparent)]
+    CborError(#[from] serde_cbor::Error),
+    #[error(transparent)]
+    ContractClassError(#[from] ContractClassError),
+    #[error("Invalid binary state dump: {0}.")]
+    InvalidBinaryDump(String),
+    #[error(transparent)]
+    IoError(#[from] io::Error),
+    #[error(transparent)]
+    ParseError(#[from] serde_json::Error),
+    #[error(transparent)]
+    ProgramError(#[from] ProgramError),
+    #[error(transparent)]
+    StarknetApiError(#[from] StarknetApiError),
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+use crate::abi::abi_utils::get_fee_token_var_address;
+use crate::abi::sierra_types::next_storage_key;
+use crate::execution::class_hash::compute_compiled_class_hash;
+use crate::execution::contract_class::{ContractClass, ContractClassResult};
+use crate::state::cached_state::StorageEntry;
+use crate::state::state_api::{verify_bulk_read_length, StateReader, StateResult};
+
+#[cfg(test)]
+#[path = "override_state_test.rs"]
+pub mod test;
+
+/// Values that take precedence over the ones of the underlying state.
+#[derive(Clone, Debug, Default)]
+pub struct StateOverrides {
+    pub storage: HashMap<StorageEntry, Felt>,
+    pub nonces: HashMap<ContractAddress, Nonce>,
+    pub class_hashes: HashMap<ContractAddress, ClassHash>,
+    pub classes: HashMap<ClassHash, ContractClass>,
+    pub compiled_class_hashes: HashMap<ClassHash, CompiledClassHash>,
+}
+
+impl StateOverrides {
+    /// Overrides the balance of the given address, in the given fee token, with the given
+    /// (low, high) pair.
+    pub fn set_fee_token_balance(
+        &mut self,
+        contract_address: ContractAddress,
+        fee_token_address: ContractAddress,
+        balance: (Felt, Felt),
+    ) -> StateResult<()> {
+        let low_key = get_fee_token_var_address(contract_address);
+        let high_key = next_storage_key(&low_key)?;
+        let (low, high) = balance;
+        self.storage.insert((fee_token_address, low_key), low);
+        self.storage.insert((fee_token_address, high_key), high);
+
+        Ok(())
+    }
+
+    /// Overrides the class of the contract at the given address (e.g., to simulate an upgrade).
+    /// The compiled class hash of a Cairo 1 class is overridden as well.
+    pub fn set_class(
+        &mut self,
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+        contract_class: ContractClass,
+    ) -> ContractClassResult<()> {
+        if let ContractClass::V1(class_v1) = &contract_class {
+            self.compiled_class_hashes.insert(class_hash, compute_compiled_class_hash(class_v1)?);
+        }
+        self.class_hashes.insert(contract_address, class_hash);
+        self.classes.insert(class_hash, contract_class);
+
+        Ok(())
+    }
+}
+
+/// A state reader that serves the given overrides on top of an underlying state reader.
+/// Used for what-if simulations, without modifying the underlying state.
+pub struct OverrideStateReader<S: StateReader> {
+    pub state: S,
+    pub overrides: StateOverrides,
+}
+
+impl<S: StateReader> OverrideStateReader<S> {
+    pub fn new(state: S, overrides: StateOverrides) -> Self {
+        Self { state, overrides }
+    }
+}
+
+impl<S: StateReader> StateReader for OverrideStateReader<S> {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        match self.overrides.storage.get(&(contract_address, key)) {
+            Some(value) => Ok(*value),
+            None => self.state.get_storage_at(contract_address, key),
+        }
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        match self.overrides.nonces.get(&contract_address) {
+            Some(nonce) => Ok(*nonce),
+            None => self.state.get_nonce_at(contract_address),
+        }
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        match self.overrides.class_hashes.get(&contract_address) {
+            Some(class_hash) => Ok(*class_hash),
+            None => self.state.get_class_hash_at(contract_address),
+        }
+    }
+
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        match self.overrides.classes.get(&class_hash) {
+            Some(contract_class) => Ok(contract_class.clone()),
+            None => self.state.get_compiled_contract_class(class_hash),
+        }
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        match self.overrides.compiled_class_hashes.get(&class_hash) {
+            Some(compiled_class_hash) => Ok(*compiled_class_hash),
+            None => self.state.get_compiled_class_hash(class_hash),
+        }
+    }
+
+    /// The overridden storage cells are counted by their overridden values.
+    fn get_n_initialized_storage_leaves(
+        &self,
+        contract_address: ContractAddress,
+    ) -> StateResult<Option<usize>> {
+        let Some(mut n_leaves) = self.state.get_n_initialized_storage_leaves(contract_address)?
+        else {
+            return Ok(None);
+        };
+        let contract_overrides =
+            self.overrides.storage.iter().filter(|((address, _), _)| *address == contract_address);
+        for ((_, key), value) in contract_overrides {
+            let is_initialized = self.state.get_storage_at(contract_address, *key)? != Felt::ZERO;
+            match (is_initialized, *value != Felt::ZERO) {
+                (false, true) => n_leaves += 1,
+                (true, false) => n_leaves -= 1,
+                _ => {}
+            }
+        }
+
+        Ok(Some(n_leaves))
+    }
+
+    fn get_storage_many(
+        &self,
+        storage_keys: &[(ContractAddress, StorageKey)],
+    ) -> StateResult<Vec<Felt>> {
+        let missing_keys: Vec<StorageEntry> = storage_keys
+            .iter()
+            .filter(|storage_key| !self.overrides.storage.contains_key(storage_key))
+            .copied()
+            .collect();
+        let values = self.state.get_storage_many(&missing_keys)?;
+        verify_bulk_read_length(missing_keys.len(), values.len())?;
+        let mut values = values.into_iter();
+
+        Ok(storage_keys
+            .iter()
+            .map(|storage_key| match self.overrides.storage.get(storage_key) {
+                Some(value) => *value,
+                None => values.next().expect("A value must be read for each missing key."),
+            })
+            .collect())
+    }
+
+    fn get_nonces_many(&self, contract_addresses: &[ContractAddress]) -> StateResult<Vec<Nonce>> {
+        let missing_addresses: Vec<ContractAddress> = contract_addresses
+            .iter()
+            .filter(|contract_address| !self.overrides.nonces.contains_key(contract_address))
+            .copied()
+            .collect();
+        let nonces = self.state.get_nonces_many(&missing_addresses)?;
+        verify_bulk_read_length(missing_addresses.len(), nonces.len())?;
+        let mut nonces = nonces.into_iter();
+
+        Ok(contract_addresses
+            .iter()
+            .map(|contract_address| match self.overrides.nonces.get(contract_address) {
+                Some(nonce) => *nonce,
+                None => nonces.next().expect("A nonce must be read for each missing address."),
+            })
+            .collect())
+    }
+}
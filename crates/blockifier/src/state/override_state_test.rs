@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::core::{ContractAddress, PatriciaKey};
+use starknet_api::transaction::{Fee, TransactionVersion};
+use starknet_api::{contract_address, felt, patricia_key};
+use starknet_types_core::felt::Felt;
+
+use crate::blockifier::config::TransactionExecutorConfig;
+use crate::blockifier::stateful_validator::StatefulValidator;
+use crate::blockifier::transaction_executor::TransactionExecutor;
+use crate::context::BlockContext;
+use crate::execution::class_hash::compute_compiled_class_hash;
+use crate::execution::contract_class::ContractClass;
+use crate::state::cached_state::CachedState;
+use crate::state::override_state::{OverrideStateReader, StateOverrides};
+use crate::state::state_api::StateReader;
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::dict_state_reader::DictStateReader;
+use crate::test_utils::initial_test_state::test_state;
+use crate::test_utils::{create_calldata, CairoVersion, BALANCE};
+use crate::transaction::errors::{
+    TransactionExecutionError, TransactionFeeError, TransactionPreValidationError,
+};
+use crate::transaction::objects::FeeType;
+use crate::transaction::test_utils::{
+    account_invoke_tx, block_context, create_account_tx_for_validate_test_nonce_0,
+    FaultyAccountTxCreatorArgs, VALID,
+};
+use crate::transaction::transaction_execution::Transaction;
+use crate::transaction::transaction_types::TransactionType;
+use crate::transaction::transactions::ExecutableTransaction;
+use crate::{invoke_tx_args, nonce, storage_key};
+
+#[rstest]
+fn test_override_state_reader(block_context: BlockContext) {
+    let account_contract = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let chain_info = &block_context.chain_info;
+    let base_state = test_state(chain_info, BALANCE, &[(account_contract, 1), (test_contract, 1)]);
+    let account_address = account_contract.get_instance_address(0);
+    let test_contract_address = test_contract.get_instance_address(0);
+    let eth_fee_token_address = chain_info.fee_token_address(&FeeType::Eth);
+
+    let mut overrides = StateOverrides::default();
+    overrides.nonces.insert(account_address, nonce!(5_u8));
+    overrides.storage.insert((test_contract_address, storage_key!(1993_u16)), felt!(93_u8));
+    overrides
+        .set_fee_token_balance(account_address, eth_fee_token_address, (felt!(7_u8), Felt::ZERO))
+        .unwrap();
+    let mut state = CachedState::new(OverrideStateReader::new(base_state, overrides));
+
+    // Overridden values.
+    assert_eq!(state.get_nonce_at(account_address).unwrap(), nonce!(5_u8));
+    assert_eq!(
+        state.get_storage_at(test_contract_address, storage_key!(1993_u16)).unwrap(),
+        felt!(93_u8)
+    );
+    assert_eq!(
+        state.get_fee_token_balance(account_address, eth_fee_token_address).unwrap(),
+        (felt!(7_u8), Felt::ZERO)
+    );
+    // Values of the underlying state.
+    assert_eq!(
+        state.get_class_hash_at(test_contract_address).unwrap(),
+        test_contract.get_class_hash()
+    );
+
+    // Transactions see the overrides.
+    let result = account_invoke_tx(invoke_tx_args! {
+        sender_address: account_address,
+        calldata: create_calldata(test_contract_address, "return_result", &[felt!(2_u8)]),
+        max_fee: Fee(BALANCE),
+        version: TransactionVersion::ONE,
+        nonce: nonce!(5_u8),
+    })
+    .execute(&mut state, &block_context, true, true);
+    assert_matches!(
+        result.unwrap_err(),
+        TransactionExecutionError::TransactionPreValidationError(
+            TransactionPreValidationError::TransactionFeeError(
+                TransactionFeeError::MaxFeeExceedsBalance { .. }
+            )
+        )
+    );
+}
+
+#[test]
+fn test_override_storage_leaves_count() {
+    let (contract_address, other_contract_address) =
+        (contract_address!("0x100"), contract_address!("0x200"));
+    let base_state = DictStateReader {
+        storage_view: HashMap::from([
+            ((contract_address, storage_key!(1_u8)), felt!(1_u8)),
+            ((contract_address, storage_key!(2_u8)), felt!(2_u8)),
+        ]),
+        ..Default::default()
+    };
+    let mut overrides = StateOverrides::default();
+    // An initialized cell overridden to another non-zero value, an initialized cell overridden to
+    // zero, and two uninitialized cells overridden to non-zero values.
+    overrides.storage.insert((contract_address, storage_key!(1_u8)), felt!(7_u8));
+    overrides.storage.insert((contract_address, storage_key!(2_u8)), Felt::ZERO);
+    overrides.storage.insert((contract_address, storage_key!(3_u8)), felt!(3_u8));
+    overrides.storage.insert((contract_address, storage_key!(4_u8)), felt!(4_u8));
+    overrides.storage.insert((other_contract_address, storage_key!(1_u8)), felt!(5_u8));
+    let state = OverrideStateReader::new(base_state, overrides);
+
+    assert_eq!(state.get_n_initialized_storage_leaves(contract_address).unwrap(), Some(3));
+    assert_eq!(state.get_n_initialized_storage_leaves(other_contract_address).unwrap(), Some(1));
+}
+
+#[rstest]
+fn test_override_class(block_context: BlockContext) {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let upgraded_contract = FeatureContract::TestContract(CairoVersion::Cairo1);
+    let base_state = test_state(&block_context.chain_info, BALANCE, &[(test_contract, 1)]);
+    let test_contract_address = test_contract.get_instance_address(0);
+    let (upgraded_class_hash, upgraded_class) =
+        (upgraded_contract.get_class_hash(), upgraded_contract.get_class());
+    let ContractClass::V1(upgraded_class_v1) = &upgraded_class else {
+        panic!("Expected a Cairo 1 class.");
+    };
+    let expected_compiled_class_hash = compute_compiled_class_hash(upgraded_class_v1).unwrap();
+
+    let mut overrides = StateOverrides::default();
+    overrides
+        .set_class(test_contract_address, upgraded_class_hash, upgraded_class.clone())
+        .unwrap();
+    let state = OverrideStateReader::new(base_state, overrides);
+
+    assert_eq!(state.get_class_hash_at(test_contract_address).unwrap(), upgraded_class_hash);
+    assert_eq!(state.get_compiled_contract_class(upgraded_class_hash).unwrap(), upgraded_class);
+    assert_eq!(
+        state.get_compiled_class_hash(upgraded_class_hash).unwrap(),
+        expected_compiled_class_hash
+    );
+}
+
+#[rstest]
+fn test_override_state_in_transaction_executor(block_context: BlockContext) {
+    let account_contract = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let chain_info = &block_context.chain_info;
+    // The account has no funds in the underlying state.
+    let base_state = test_state(chain_info, 0, &[(account_contract, 1), (test_contract, 1)]);
+    let account_address = account_contract.get_instance_address(0);
+    let eth_fee_token_address = chain_info.fee_token_address(&FeeType::Eth);
+
+    let mut overrides = StateOverrides::default();
+    overrides
+        .set_fee_token_balance(account_address, eth_fee_token_address, (felt!(BALANCE), Felt::ZERO))
+        .unwrap();
+    let state = CachedState::new(OverrideStateReader::new(base_state.state, overrides));
+    let mut tx_executor =
+        TransactionExecutor::new(state, block_context, TransactionExecutorConfig::default());
+
+    let tx = account_invoke_tx(invoke_tx_args! {
+        sender_address: account_address,
+        calldata: create_calldata(
+            test_contract.get_instance_address(0),
+            "return_result",
+            &[felt!(2_u8)],
+        ),
+        max_fee: Fee(BALANCE),
+        version: TransactionVersion::ONE,
+    });
+    let tx_execution_info = tx_executor.execute(&Transaction::AccountTransaction(tx)).unwrap();
+    assert!(!tx_execution_info.is_reverted());
+
+    // The fee is charged from the overridden balance; the underlying state is not modified.
+    let block_state = tx_executor.block_state.as_mut().unwrap();
+    let (balance, _) =
+        block_state.get_fee_token_balance(account_address, eth_fee_token_address).unwrap();
+    assert_eq!(balance, felt!(BALANCE - tx_execution_info.transaction_receipt.fee.0));
+    assert_eq!(
+        block_state
+            .state
+            .state
+            .get_fee_token_balance(account_address, eth_fee_token_address)
+            .unwrap(),
+        (Felt::ZERO, Felt::ZERO)
+    );
+}
+
+#[rstest]
+fn test_override_state_in_stateful_validator(block_context: BlockContext) {
+    let faulty_account = FeatureContract::FaultyAccount(CairoVersion::Cairo1);
+    let chain_info = &block_context.chain_info;
+    // The account has no funds in the underlying state.
+    let base_state = test_state(chain_info, 0, &[(faulty_account, 1)]);
+    let account_address = faulty_account.get_instance_address(0);
+    let tx_args = FaultyAccountTxCreatorArgs {
+        scenario: VALID,
+        tx_type: TransactionType::InvokeFunction,
+        tx_version: TransactionVersion::ONE,
+        sender_address: account_address,
+        class_hash: faulty_account.get_class_hash(),
+        max_fee: Fee(BALANCE),
+        ..Default::default()
+    };
+
+    let mut overrides = StateOverrides::default();
+    overrides
+        .set_fee_token_balance(
+            account_address,
+            chain_info.fee_token_address(&FeeType::Eth),
+            (felt!(BALANCE), Felt::ZERO),
+        )
+        .unwrap();
+    let state = CachedState::new(OverrideStateReader::new(base_state.state, overrides));
+    let mut stateful_validator = StatefulValidator::create(state, block_context);
+
+    let tx = create_account_tx_for_validate_test_nonce_0(tx_args);
+    let result = stateful_validator.perform_validations(tx, false);
+    assert!(result.is_ok(), "Validation failed: {:?}", result.unwrap_err());
+}
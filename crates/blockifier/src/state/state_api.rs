@@ -46,6 +46,39 @@ pub trait StateReader {
     /// Returns the compiled class hash of the given class hash.
     fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash>;
 
+    /// Returns the number of initialized (non-zero) storage cells of the given contract instance,
+    /// i.e., the number of leaves in its storage tree, if known.
+    /// Default: unknown.
+    fn get_n_initialized_storage_leaves(
+        &self,
+        _contract_address: ContractAddress,
+    ) -> StateResult<Option<usize>> {
+        Ok(None)
+    }
+
+    /// Returns the storage values under the given keys, in the given order. Implementations may
+    /// read the values in bulk, saving a round trip to the underlying storage per key.
+    /// Default: reads the values one by one.
+    fn get_storage_many(
+        &self,
+        storage_keys: &[(ContractAddress, StorageKey)],
+    ) -> StateResult<Vec<Felt>> {
+        storage_keys
+            .iter()
+            .map(|&(contract_address, key)| self.get_storage_at(contract_address, key))
+            .collect()
+    }
+
+    /// Returns the nonces of the given contract instances, in the given order. Implementations may
+    /// read the nonces in bulk.
+    /// Default: reads the nonces one by one.
+    fn get_nonces_many(&self, contract_addresses: &[ContractAddress]) -> StateResult<Vec<Nonce>> {
+        contract_addresses
+            .iter()
+            .map(|&contract_address| self.get_nonce_at(contract_address))
+            .collect()
+    }
+
     /// Returns the storage value representing the balance (in fee token) at the given address.
     // TODO(Dori, 1/7/2023): When a standard representation for large integers is set, change the
     //    return type to that.
@@ -58,10 +91,11 @@ pub trait StateReader {
     ) -> Result<(Felt, Felt), StateError> {
         let low_key = get_fee_token_var_address(contract_address);
         let high_key = next_storage_key(&low_key)?;
-        let low = self.get_storage_at(fee_token_address, low_key)?;
-        let high = self.get_storage_at(fee_token_address, high_key)?;
+        let balance =
+            self.get_storage_many(&[(fee_token_address, low_key), (fee_token_address, high_key)])?;
+        verify_bulk_read_length(2, balance.len())?;
 
-        Ok((low, high))
+        Ok((balance[0], balance[1]))
     }
 }
 
@@ -119,3 +153,15 @@ pub trait UpdatableState: StateReader {
         visited_pcs: &HashMap<ClassHash, HashSet<usize>>,
     );
 }
+
+/// Verifies that a bulk read (e.g., `get_storage_many`) returned exactly one value per requested
+/// item.
+pub fn verify_bulk_read_length(n_requested: usize, n_read: usize) -> StateResult<()> {
+    if n_requested != n_read {
+        return Err(StateError::StateReadError(format!(
+            "Bulk read of {n_requested} items returned {n_read} values"
+        )));
+    }
+
+    Ok(())
+}
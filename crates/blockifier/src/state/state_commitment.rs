@@ -0,0 +1,277 @@
+use std::collections::{HashMap, HashSet};
+
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+
+use crate::patricia_tree::{verify_proof, PatriciaTree, ProofNode};
+use crate::state::cached_state::CommitmentStateDiff;
+
+#[cfg(test)]
+#[path = "state_commitment_test.rs"]
+pub mod test;
+
+/// The height of the contracts tree, the classes tree and the contract storage trees.
+pub const STATE_TREE_HEIGHT: u8 = 251;
+const CONTRACT_CLASS_LEAF_V0: &[u8] = b"CONTRACT_CLASS_LEAF_V0";
+const CONTRACT_STATE_HASH_VERSION: Felt = Felt::ZERO;
+const STARKNET_STATE_V0: &[u8] = b"STARKNET_STATE_V0";
+
+/// The state of a contract, as committed to by its leaf in the contracts tree.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ContractLeaf {
+    pub class_hash: ClassHash,
+    pub nonce: Nonce,
+    pub storage_root: Felt,
+}
+
+impl ContractLeaf {
+    /// Returns the hash of the leaf; the leaf of a contract with an empty state is empty (zero).
+    pub fn hash(&self) -> Felt {
+        if *self == Self::default() {
+            return Felt::ZERO;
+        }
+
+        let class_and_storage_hash = Pedersen::hash(&self.class_hash.0, &self.storage_root);
+        Pedersen::hash(
+            &Pedersen::hash(&class_and_storage_hash, &self.nonce.0),
+            &CONTRACT_STATE_HASH_VERSION,
+        )
+    }
+}
+
+/// A proof of the state of a contract (its class hash, nonce and storage root), against the root
+/// of the contracts tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractProof {
+    pub contract_leaf: ContractLeaf,
+    pub contract_proof: Vec<ProofNode>,
+}
+
+impl ContractProof {
+    pub fn verify(&self, contract_address: ContractAddress, contracts_tree_root: Felt) -> bool {
+        verify_proof::<Pedersen>(
+            contracts_tree_root,
+            STATE_TREE_HEIGHT,
+            *contract_address.0.key(),
+            self.contract_leaf.hash(),
+            &self.contract_proof,
+        )
+    }
+}
+
+/// A proof of a storage value (zero, if unset), against the root of the contracts tree: a proof of
+/// the contract storage root, and a proof of the value against it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageProof {
+    pub contract_proof: ContractProof,
+    pub value: Felt,
+    pub storage_proof: Vec<ProofNode>,
+}
+
+impl StorageProof {
+    pub fn verify(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+        contracts_tree_root: Felt,
+    ) -> bool {
+        self.contract_proof.verify(contract_address, contracts_tree_root)
+            && verify_proof::<Pedersen>(
+                self.contract_proof.contract_leaf.storage_root,
+                STATE_TREE_HEIGHT,
+                *key.0.key(),
+                self.value,
+                &self.storage_proof,
+            )
+    }
+}
+
+/// A proof of the compiled class hash of a class (zero, if undeclared), against the root of the
+/// classes tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClassProof {
+    pub compiled_class_hash: CompiledClassHash,
+    pub class_proof: Vec<ProofNode>,
+}
+
+impl ClassProof {
+    pub fn verify(&self, class_hash: ClassHash, classes_tree_root: Felt) -> bool {
+        verify_proof::<Poseidon>(
+            classes_tree_root,
+            STATE_TREE_HEIGHT,
+            class_hash.0,
+            class_leaf_hash(self.compiled_class_hash),
+            &self.class_proof,
+        )
+    }
+}
+
+struct ContractState {
+    class_hash: ClassHash,
+    nonce: Nonce,
+    storage: PatriciaTree<Pedersen>,
+    storage_root: Felt,
+}
+
+impl Default for ContractState {
+    fn default() -> Self {
+        Self {
+            class_hash: ClassHash::default(),
+            nonce: Nonce::default(),
+            storage: PatriciaTree::new(STATE_TREE_HEIGHT),
+            storage_root: Felt::ZERO,
+        }
+    }
+}
+
+/// Maintains the global state commitment in memory: the contracts tree, whose leaves commit to the
+/// state of each contract (including its storage tree), and the classes tree, whose leaves commit
+/// to the compiled class hash of each Cairo 1 class.
+pub struct StateCommitment {
+    contracts: HashMap<ContractAddress, ContractState>,
+    compiled_class_hashes: HashMap<ClassHash, CompiledClassHash>,
+    contracts_tree: PatriciaTree<Pedersen>,
+    classes_tree: PatriciaTree<Poseidon>,
+    contracts_tree_root: Felt,
+    classes_tree_root: Felt,
+}
+
+impl Default for StateCommitment {
+    fn default() -> Self {
+        Self {
+            contracts: HashMap::new(),
+            compiled_class_hashes: HashMap::new(),
+            contracts_tree: PatriciaTree::new(STATE_TREE_HEIGHT),
+            classes_tree: PatriciaTree::new(STATE_TREE_HEIGHT),
+            contracts_tree_root: Felt::ZERO,
+            classes_tree_root: Felt::ZERO,
+        }
+    }
+}
+
+impl StateCommitment {
+    /// Applies the given state diff (e.g., of a block) and returns the new global state root.
+    /// Only the paths to the updated leaves are rehashed.
+    pub fn apply_state_diff(&mut self, state_diff: &CommitmentStateDiff) -> Felt {
+        let mut class_leaves = Vec::new();
+        for (class_hash, compiled_class_hash) in &state_diff.class_hash_to_compiled_class_hash {
+            self.compiled_class_hashes.insert(*class_hash, *compiled_class_hash);
+            class_leaves.push((class_hash.0, class_leaf_hash(*compiled_class_hash)));
+        }
+        self.classes_tree.update(class_leaves);
+
+        for (contract_address, class_hash) in &state_diff.address_to_class_hash {
+            self.contracts.entry(*contract_address).or_default().class_hash = *class_hash;
+        }
+        for (contract_address, nonce) in &state_diff.address_to_nonce {
+            self.contracts.entry(*contract_address).or_default().nonce = *nonce;
+        }
+        for (contract_address, storage_updates) in &state_diff.storage_updates {
+            let contract = self.contracts.entry(*contract_address).or_default();
+            let storage_leaves = storage_updates.iter().map(|(key, value)| (*key.0.key(), *value));
+            contract.storage.update(storage_leaves);
+            contract.storage_root = contract.storage.root();
+        }
+
+        let updated_contracts: HashSet<&ContractAddress> = state_diff
+            .address_to_class_hash
+            .keys()
+            .chain(state_diff.address_to_nonce.keys())
+            .chain(state_diff.storage_updates.keys())
+            .collect();
+        let contract_leaves: Vec<(Felt, Felt)> = updated_contracts
+            .into_iter()
+            .map(|contract_address| {
+                (*contract_address.0.key(), self.contract_leaf(*contract_address).hash())
+            })
+            .collect();
+        self.contracts_tree.update(contract_leaves);
+
+        self.contracts_tree_root = self.contracts_tree.root();
+        self.classes_tree_root = self.classes_tree.root();
+        self.global_root()
+    }
+
+    /// Returns the global state root, which commits to both the contracts and the classes trees.
+    pub fn global_root(&self) -> Felt {
+        calculate_global_root(self.contracts_tree_root, self.classes_tree_root)
+    }
+
+    pub fn contracts_tree_root(&self) -> Felt {
+        self.contracts_tree_root
+    }
+
+    pub fn classes_tree_root(&self) -> Felt {
+        self.classes_tree_root
+    }
+
+    /// Returns a proof of the class hash and nonce of the given contract.
+    pub fn contract_proof(&self, contract_address: ContractAddress) -> ContractProof {
+        ContractProof {
+            contract_leaf: self.contract_leaf(contract_address),
+            contract_proof: self.contracts_tree.proof(*contract_address.0.key()),
+        }
+    }
+
+    /// Returns a proof of the value at the given storage key of the given contract.
+    pub fn storage_proof(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StorageProof {
+        let (value, storage_proof) = match self.contracts.get(&contract_address) {
+            Some(contract) => {
+                (contract.storage.get(*key.0.key()), contract.storage.proof(*key.0.key()))
+            }
+            None => (Felt::ZERO, Vec::new()),
+        };
+        StorageProof { contract_proof: self.contract_proof(contract_address), value, storage_proof }
+    }
+
+    /// Returns a proof of the compiled class hash of the given class.
+    pub fn class_proof(&self, class_hash: ClassHash) -> ClassProof {
+        ClassProof {
+            compiled_class_hash: self
+                .compiled_class_hashes
+                .get(&class_hash)
+                .copied()
+                .unwrap_or_default(),
+            class_proof: self.classes_tree.proof(class_hash.0),
+        }
+    }
+
+    fn contract_leaf(&self, contract_address: ContractAddress) -> ContractLeaf {
+        self.contracts
+            .get(&contract_address)
+            .map(|contract| ContractLeaf {
+                class_hash: contract.class_hash,
+                nonce: contract.nonce,
+                storage_root: contract.storage_root,
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Computes the global state root from the roots of the contracts and classes trees; as long as no
+/// Cairo 1 class is declared, it is the root of the contracts tree.
+pub fn calculate_global_root(contracts_tree_root: Felt, classes_tree_root: Felt) -> Felt {
+    if classes_tree_root == Felt::ZERO {
+        return contracts_tree_root;
+    }
+
+    Poseidon::hash_array(&[
+        Felt::from_bytes_be_slice(STARKNET_STATE_V0),
+        contracts_tree_root,
+        classes_tree_root,
+    ])
+}
+
+fn class_leaf_hash(compiled_class_hash: CompiledClassHash) -> Felt {
+    if compiled_class_hash.0 == Felt::ZERO {
+        return Felt::ZERO;
+    }
+
+    Poseidon::hash(&Felt::from_bytes_be_slice(CONTRACT_CLASS_LEAF_V0), &compiled_class_hash.0)
+}
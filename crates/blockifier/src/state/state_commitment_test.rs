@@ -0,0 +1,157 @@
+use indexmap::{indexmap, IndexMap};
+use pretty_assertions::assert_eq;
+use starknet_api::core::{ClassHash, ContractAddress, PatriciaKey};
+use starknet_api::{class_hash, contract_address, felt, patricia_key};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+
+use crate::patricia_tree::calculate_root;
+use crate::state::cached_state::CommitmentStateDiff;
+use crate::state::state_commitment::{
+    calculate_global_root, ContractLeaf, StateCommitment, STATE_TREE_HEIGHT,
+};
+use crate::{compiled_class_hash, nonce, storage_key};
+
+fn state_diff_for_testing() -> CommitmentStateDiff {
+    let (contract_address0, contract_address1) =
+        (contract_address!("0x100"), contract_address!("0x200"));
+    CommitmentStateDiff {
+        address_to_class_hash: indexmap! {
+            contract_address0 => class_hash!("0x10"),
+            contract_address1 => class_hash!("0x20"),
+        },
+        address_to_nonce: indexmap! {contract_address0 => nonce!(1_u8)},
+        storage_updates: indexmap! {
+            contract_address1 => indexmap! {storage_key!(1_u8) => felt!(7_u8)},
+        },
+        class_hash_to_compiled_class_hash: indexmap! {
+            class_hash!("0x20") => compiled_class_hash!(3_u8),
+        },
+    }
+}
+
+#[test]
+fn test_global_root() {
+    let mut state_commitment = StateCommitment::default();
+    assert_eq!(state_commitment.global_root(), Felt::ZERO);
+
+    let global_root = state_commitment.apply_state_diff(&state_diff_for_testing());
+    assert_eq!(global_root, state_commitment.global_root());
+    assert_eq!(
+        global_root,
+        Poseidon::hash_array(&[
+            Felt::from_bytes_be_slice(b"STARKNET_STATE_V0"),
+            state_commitment.contracts_tree_root(),
+            state_commitment.classes_tree_root(),
+        ])
+    );
+
+    // The contracts tree commits to the state of each contract.
+    let storage_root = calculate_root::<Pedersen>([(felt!(1_u8), felt!(7_u8))], STATE_TREE_HEIGHT);
+    let contract_leaves = [
+        (
+            felt!(0x100_u16),
+            ContractLeaf {
+                class_hash: class_hash!("0x10"),
+                nonce: nonce!(1_u8),
+                storage_root: Felt::ZERO,
+            },
+        ),
+        (
+            felt!(0x200_u16),
+            ContractLeaf { class_hash: class_hash!("0x20"), nonce: nonce!(0_u8), storage_root },
+        ),
+    ];
+    assert_eq!(
+        state_commitment.contracts_tree_root(),
+        calculate_root::<Pedersen>(
+            contract_leaves.map(|(address, leaf)| (address, leaf.hash())),
+            STATE_TREE_HEIGHT,
+        )
+    );
+
+    // Without Cairo 1 classes, the global root is the contracts tree root.
+    let state_diff = CommitmentStateDiff {
+        class_hash_to_compiled_class_hash: IndexMap::new(),
+        ..state_diff_for_testing()
+    };
+    let mut state_commitment = StateCommitment::default();
+    assert_eq!(
+        state_commitment.apply_state_diff(&state_diff),
+        state_commitment.contracts_tree_root()
+    );
+    assert_eq!(calculate_global_root(Felt::ONE, Felt::ZERO), Felt::ONE);
+}
+
+#[test]
+fn test_incremental_updates() {
+    let mut state_commitment = StateCommitment::default();
+    let state_diff = state_diff_for_testing();
+    state_commitment.apply_state_diff(&CommitmentStateDiff {
+        address_to_class_hash: state_diff.address_to_class_hash.clone(),
+        address_to_nonce: IndexMap::new(),
+        storage_updates: indexmap! {
+            contract_address!("0x200") => indexmap! {
+                storage_key!(1_u8) => felt!(5_u8),
+                storage_key!(2_u8) => felt!(6_u8),
+            },
+        },
+        class_hash_to_compiled_class_hash: IndexMap::new(),
+    });
+    let global_root = state_commitment.apply_state_diff(&CommitmentStateDiff {
+        storage_updates: indexmap! {
+            contract_address!("0x200") => indexmap! {
+                storage_key!(1_u8) => felt!(7_u8),
+                storage_key!(2_u8) => Felt::ZERO,
+            },
+        },
+        ..state_diff.clone()
+    });
+
+    assert_eq!(global_root, StateCommitment::default().apply_state_diff(&state_diff));
+}
+
+#[test]
+fn test_proofs() {
+    let mut state_commitment = StateCommitment::default();
+    state_commitment.apply_state_diff(&state_diff_for_testing());
+    let contracts_tree_root = state_commitment.contracts_tree_root();
+    let classes_tree_root = state_commitment.classes_tree_root();
+    let (contract_address0, contract_address1) =
+        (contract_address!("0x100"), contract_address!("0x200"));
+
+    // Contract proofs, for deployed and undeployed contracts.
+    let contract_proof = state_commitment.contract_proof(contract_address0);
+    assert_eq!(contract_proof.contract_leaf.class_hash, class_hash!("0x10"));
+    assert_eq!(contract_proof.contract_leaf.nonce, nonce!(1_u8));
+    assert!(contract_proof.verify(contract_address0, contracts_tree_root));
+    assert!(!contract_proof.verify(contract_address1, contracts_tree_root));
+    let undeployed_contract_proof = state_commitment.contract_proof(contract_address!("0x300"));
+    assert_eq!(undeployed_contract_proof.contract_leaf, ContractLeaf::default());
+    assert!(undeployed_contract_proof.verify(contract_address!("0x300"), contracts_tree_root));
+
+    // Storage proofs, for set and unset keys.
+    let storage_proof = state_commitment.storage_proof(contract_address1, storage_key!(1_u8));
+    assert_eq!(storage_proof.value, felt!(7_u8));
+    assert!(storage_proof.verify(contract_address1, storage_key!(1_u8), contracts_tree_root));
+    let mut tampered_storage_proof = storage_proof.clone();
+    tampered_storage_proof.value = felt!(8_u8);
+    assert!(!tampered_storage_proof.verify(
+        contract_address1,
+        storage_key!(1_u8),
+        contracts_tree_root
+    ));
+    for (contract_address, key) in [(contract_address1, 2_u8), (contract_address0, 1_u8)] {
+        let storage_proof = state_commitment.storage_proof(contract_address, storage_key!(key));
+        assert_eq!(storage_proof.value, Felt::ZERO);
+        assert!(storage_proof.verify(contract_address, storage_key!(key), contracts_tree_root));
+    }
+
+    // Class proofs, for declared and undeclared classes.
+    let class_proof = state_commitment.class_proof(class_hash!("0x20"));
+    assert_eq!(class_proof.compiled_class_hash, compiled_class_hash!(3_u8));
+    assert!(class_proof.verify(class_hash!("0x20"), classes_tree_root));
+    let undeclared_class_proof = state_commitment.class_proof(class_hash!("0x10"));
+    assert_eq!(undeclared_class_proof.compiled_class_hash, compiled_class_hash!(0_u8));
+    assert!(undeclared_class_proof.verify(class_hash!("0x10"), classes_tree_root));
+}
@@ -0,0 +1,482 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_vm::types::program::Program;
+use serde::de::Error as DeserializationError;
+use serde::ser::Error as SerializationError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use starknet_api::core::{
+    ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, Nonce, PatriciaKey,
+};
+use starknet_api::deprecated_contract_class::{EntryPoint, EntryPointOffset, EntryPointType};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+use crate::execution::contract_class::{
+    ContractClass, ContractClassV0, ContractClassV0Inner, ContractClassV1,
+};
+use crate::state::cached_state::CachedState;
+use crate::state::errors::{StateDumpError, StateError};
+use crate::state::state_api::{StateReader, StateResult};
+
+#[cfg(test)]
+#[path = "state_dump_test.rs"]
+pub mod test;
+
+pub type StateDumpResult<T> = Result<T, StateDumpError>;
+
+const BINARY_FORMAT_HEADER: &[u8; 8] = b"SNDUMP\x00\x01";
+const FELT_SIZE: usize = 32;
+const LENGTH_SIZE: usize = 8;
+
+/// The encoding of a state dump.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StateDumpFormat {
+    Json,
+    /// A compact encoding: felts are encoded as 32 big-endian bytes and lengths as 8 little-endian
+    /// bytes. Contract classes are encoded in CBOR.
+    Binary,
+}
+
+/// A portable snapshot of a state. Entries are ordered by their keys, so that the encoding of a
+/// dump is deterministic.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateDump {
+    pub storage: BTreeMap<ContractAddress, BTreeMap<StorageKey, Felt>>,
+    pub nonces: BTreeMap<ContractAddress, Nonce>,
+    pub class_hashes: BTreeMap<ContractAddress, ClassHash>,
+    pub compiled_class_hashes: BTreeMap<ClassHash, CompiledClassHash>,
+    #[serde(serialize_with = "serialize_classes", deserialize_with = "deserialize_classes")]
+    pub classes: BTreeMap<ClassHash, ContractClass>,
+}
+
+impl StateDump {
+    /// Dumps the values read or written through the given cached state (written values taking
+    /// precedence), and the classes it loaded.
+    pub fn from_cached_state<S: StateReader>(state: &CachedState<S>) -> Self {
+        let cache = state.cache.borrow();
+        let mut state_maps = cache.initial_reads.clone();
+        state_maps.extend(&cache.writes);
+
+        let mut dump = Self::default();
+        for ((address, key), value) in state_maps.storage {
+            dump.storage.entry(address).or_default().insert(key, value);
+        }
+        dump.nonces.extend(state_maps.nonces);
+        dump.class_hashes.extend(state_maps.class_hashes);
+        dump.compiled_class_hashes.extend(state_maps.compiled_class_hashes);
+        dump.classes.extend(
+            state
+                .class_hash_to_class
+                .borrow()
+                .iter()
+                .map(|(class_hash, class)| (*class_hash, class.clone())),
+        );
+
+        dump
+    }
+
+    /// Dumps the entire state of the given cached state over a dump: the underlying dump, extended
+    /// by the dump of the cached state (see `from_cached_state`).
+    pub fn from_cached_file_state(state: &CachedState<FileStateReader>) -> Self {
+        let mut dump = state.state.dump().clone();
+        dump.extend(Self::from_cached_state(state));
+        dump
+    }
+
+    /// Adds the entries of the given dump, overriding existing ones.
+    pub fn extend(&mut self, other: Self) {
+        for (address, contract_storage) in other.storage {
+            self.storage.entry(address).or_default().extend(contract_storage);
+        }
+        self.nonces.extend(other.nonces);
+        self.class_hashes.extend(other.class_hashes);
+        self.compiled_class_hashes.extend(other.compiled_class_hashes);
+        self.classes.extend(other.classes);
+    }
+
+    pub fn encode(&self, format: StateDumpFormat) -> StateDumpResult<Vec<u8>> {
+        match format {
+            StateDumpFormat::Json => Ok(serde_json::to_vec(self)?),
+            StateDumpFormat::Binary => self.encode_binary(),
+        }
+    }
+
+    pub fn decode(bytes: &[u8], format: StateDumpFormat) -> StateDumpResult<Self> {
+        match format {
+            StateDumpFormat::Json => Ok(serde_json::from_slice(bytes)?),
+            StateDumpFormat::Binary => Self::decode_binary(bytes),
+        }
+    }
+
+    pub fn write_to_file(&self, path: &Path, format: StateDumpFormat) -> StateDumpResult<()> {
+        Ok(fs::write(path, self.encode(format)?)?)
+    }
+
+    pub fn read_from_file(path: &Path, format: StateDumpFormat) -> StateDumpResult<Self> {
+        Self::decode(&fs::read(path)?, format)
+    }
+
+    fn encode_binary(&self) -> StateDumpResult<Vec<u8>> {
+        let mut writer = BinaryWriter(BINARY_FORMAT_HEADER.to_vec());
+        writer.write_len(self.storage.len());
+        for (address, contract_storage) in &self.storage {
+            writer.write_felt(address);
+            writer.write_map(contract_storage);
+        }
+        writer.write_map(&self.nonces);
+        writer.write_map(&self.class_hashes);
+        writer.write_map(&self.compiled_class_hashes);
+        writer.write_len(self.classes.len());
+        for (class_hash, class) in &self.classes {
+            writer.write_felt(class_hash);
+            writer.write_class(class)?;
+        }
+
+        Ok(writer.0)
+    }
+
+    fn decode_binary(bytes: &[u8]) -> StateDumpResult<Self> {
+        let mut reader = BinaryReader(bytes);
+        if reader.read(BINARY_FORMAT_HEADER.len())? != BINARY_FORMAT_HEADER {
+            return Err(StateDumpError::InvalidBinaryDump("unknown header".to_string()));
+        }
+        let storage = (0..reader.read_len()?)
+            .map(|_| -> StateDumpResult<_> { Ok((reader.read_felt()?, reader.read_map()?)) })
+            .collect::<StateDumpResult<_>>()?;
+        let nonces = reader.read_map()?;
+        let class_hashes = reader.read_map()?;
+        let compiled_class_hashes = reader.read_map()?;
+        let classes = (0..reader.read_len()?)
+            .map(|_| -> StateDumpResult<_> { Ok((reader.read_felt()?, reader.read_class()?)) })
+            .collect::<StateDumpResult<_>>()?;
+        if !reader.0.is_empty() {
+            return Err(StateDumpError::InvalidBinaryDump("trailing bytes".to_string()));
+        }
+
+        Ok(Self { storage, nonces, class_hashes, compiled_class_hashes, classes })
+    }
+}
+
+/// The portable form of a contract class: a Cairo 0 class by its program (in the Cairo VM program
+/// serialization) and entry points, and a Cairo 1 class by its CASM.
+#[derive(Deserialize, Serialize)]
+enum SerializableContractClass {
+    V0 {
+        program: serde_json::Value,
+        entry_points_by_type: BTreeMap<EntryPointType, Vec<SerializableEntryPoint>>,
+    },
+    V1(CasmContractClass),
+}
+
+/// A Cairo 0 entry point, whose selector is serialized as a hex string in all formats; in
+/// non-human-readable formats, a felt is serialized as bytes, but is only deserialized from a
+/// string.
+#[derive(Deserialize, Serialize)]
+struct SerializableEntryPoint {
+    #[serde(serialize_with = "serialize_selector")]
+    selector: EntryPointSelector,
+    offset: EntryPointOffset,
+}
+
+impl From<&EntryPoint> for SerializableEntryPoint {
+    fn from(entry_point: &EntryPoint) -> Self {
+        Self { selector: entry_point.selector, offset: entry_point.offset }
+    }
+}
+
+impl From<SerializableEntryPoint> for EntryPoint {
+    fn from(entry_point: SerializableEntryPoint) -> Self {
+        Self { selector: entry_point.selector, offset: entry_point.offset }
+    }
+}
+
+fn serialize_selector<S: Serializer>(
+    selector: &EntryPointSelector,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("{:#x}", selector.0))
+}
+
+impl TryFrom<&ContractClass> for SerializableContractClass {
+    type Error = StateDumpError;
+
+    fn try_from(class: &ContractClass) -> StateDumpResult<Self> {
+        Ok(match class {
+            ContractClass::V0(class) => Self::V0 {
+                program: serde_json::from_slice(&class.program.serialize()?)?,
+                entry_points_by_type: class
+                    .entry_points_by_type
+                    .iter()
+                    .map(|(entry_point_type, entry_points)| {
+                        (*entry_point_type, entry_points.iter().map(Into::into).collect())
+                    })
+                    .collect(),
+            },
+            ContractClass::V1(class) => Self::V1(CasmContractClass::try_from(class)?),
+        })
+    }
+}
+
+impl TryFrom<SerializableContractClass> for ContractClass {
+    type Error = StateDumpError;
+
+    fn try_from(class: SerializableContractClass) -> StateDumpResult<Self> {
+        Ok(match class {
+            SerializableContractClass::V0 { program, entry_points_by_type } => {
+                ContractClassV0(Arc::new(ContractClassV0Inner {
+                    program: Program::deserialize(&serde_json::to_vec(&program)?, None)?,
+                    entry_points_by_type: entry_points_by_type
+                        .into_iter()
+                        .map(|(entry_point_type, entry_points)| {
+                            (entry_point_type, entry_points.into_iter().map(Into::into).collect())
+                        })
+                        .collect(),
+                }))
+                .into()
+            }
+            SerializableContractClass::V1(casm_contract_class) => {
+                ContractClassV1::try_from(casm_contract_class)?.into()
+            }
+        })
+    }
+}
+
+pub(crate) fn serialize_classes<S: Serializer>(
+    classes: &BTreeMap<ClassHash, ContractClass>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    classes
+        .iter()
+        .map(|(class_hash, class)| -> StateDumpResult<_> {
+            Ok((*class_hash, SerializableContractClass::try_from(class)?))
+        })
+        .collect::<StateDumpResult<BTreeMap<_, _>>>()
+        .map_err(S::Error::custom)?
+        .serialize(serializer)
+}
+
+pub(crate) fn deserialize_classes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<ClassHash, ContractClass>, D::Error> {
+    BTreeMap::<ClassHash, SerializableContractClass>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(class_hash, class)| -> StateDumpResult<_> {
+            Ok((class_hash, ContractClass::try_from(class)?))
+        })
+        .collect::<StateDumpResult<_>>()
+        .map_err(D::Error::custom)
+}
+
+/// A value encoded as a single felt in the binary format.
+trait FeltEncoded: Sized {
+    fn to_felt(&self) -> Felt;
+    fn from_felt(felt: Felt) -> StateDumpResult<Self>;
+}
+
+impl FeltEncoded for Felt {
+    fn to_felt(&self) -> Felt {
+        *self
+    }
+
+    fn from_felt(felt: Felt) -> StateDumpResult<Self> {
+        Ok(felt)
+    }
+}
+
+impl FeltEncoded for ContractAddress {
+    fn to_felt(&self) -> Felt {
+        *self.0.key()
+    }
+
+    fn from_felt(felt: Felt) -> StateDumpResult<Self> {
+        Ok(Self(PatriciaKey::try_from(felt)?))
+    }
+}
+
+impl FeltEncoded for StorageKey {
+    fn to_felt(&self) -> Felt {
+        *self.0.key()
+    }
+
+    fn from_felt(felt: Felt) -> StateDumpResult<Self> {
+        Ok(Self(PatriciaKey::try_from(felt)?))
+    }
+}
+
+impl FeltEncoded for Nonce {
+    fn to_felt(&self) -> Felt {
+        self.0
+    }
+
+    fn from_felt(felt: Felt) -> StateDumpResult<Self> {
+        Ok(Self(felt))
+    }
+}
+
+impl FeltEncoded for ClassHash {
+    fn to_felt(&self) -> Felt {
+        self.0
+    }
+
+    fn from_felt(felt: Felt) -> StateDumpResult<Self> {
+        Ok(Self(felt))
+    }
+}
+
+impl FeltEncoded for CompiledClassHash {
+    fn to_felt(&self) -> Felt {
+        self.0
+    }
+
+    fn from_felt(felt: Felt) -> StateDumpResult<Self> {
+        Ok(Self(felt))
+    }
+}
+
+struct BinaryWriter(Vec<u8>);
+
+impl BinaryWriter {
+    fn write_len(&mut self, len: usize) {
+        let len = u64::try_from(len).expect("A length should fit in 64 bits.");
+        self.0.extend(len.to_le_bytes());
+    }
+
+    fn write_felt(&mut self, value: &impl FeltEncoded) {
+        self.0.extend(value.to_felt().to_bytes_be());
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_len(bytes.len());
+        self.0.extend(bytes);
+    }
+
+    fn write_map<K: FeltEncoded, V: FeltEncoded>(&mut self, map: &BTreeMap<K, V>) {
+        self.write_len(map.len());
+        for (key, value) in map {
+            self.write_felt(key);
+            self.write_felt(value);
+        }
+    }
+
+    fn write_class(&mut self, class: &ContractClass) -> StateDumpResult<()> {
+        self.write_bytes(&serde_cbor::to_vec(&SerializableContractClass::try_from(class)?)?);
+        Ok(())
+    }
+}
+
+struct BinaryReader<'a>(&'a [u8]);
+
+impl<'a> BinaryReader<'a> {
+    fn read(&mut self, n_bytes: usize) -> StateDumpResult<&'a [u8]> {
+        if self.0.len() < n_bytes {
+            return Err(StateDumpError::InvalidBinaryDump("unexpected end of input".to_string()));
+        }
+        let (bytes, rest) = self.0.split_at(n_bytes);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn read_len(&mut self) -> StateDumpResult<usize> {
+        let bytes: [u8; LENGTH_SIZE] =
+            self.read(LENGTH_SIZE)?.try_into().expect("The length bytes should have been read.");
+        let len = u64::from_le_bytes(bytes);
+        usize::try_from(len)
+            .map_err(|_| StateDumpError::InvalidBinaryDump(format!("length {len} is too large")))
+    }
+
+    fn read_felt<T: FeltEncoded>(&mut self) -> StateDumpResult<T> {
+        let bytes: [u8; FELT_SIZE] =
+            self.read(FELT_SIZE)?.try_into().expect("The felt bytes should have been read.");
+        T::from_felt(Felt::from_bytes_be(&bytes))
+    }
+
+    fn read_bytes(&mut self) -> StateDumpResult<&'a [u8]> {
+        let len = self.read_len()?;
+        self.read(len)
+    }
+
+    fn read_map<K: FeltEncoded + Ord, V: FeltEncoded>(
+        &mut self,
+    ) -> StateDumpResult<BTreeMap<K, V>> {
+        (0..self.read_len()?)
+            .map(|_| -> StateDumpResult<_> { Ok((self.read_felt()?, self.read_felt()?)) })
+            .collect()
+    }
+
+    fn read_class(&mut self) -> StateDumpResult<ContractClass> {
+        let class: SerializableContractClass = serde_cbor::from_slice(self.read_bytes()?)?;
+        ContractClass::try_from(class)
+    }
+}
+
+/// A state reader serving a state dump (e.g., loaded from a file); entries missing from the dump
+/// have their default values, and classes missing from it are undeclared.
+#[derive(Clone, Debug, Default)]
+pub struct FileStateReader {
+    dump: StateDump,
+}
+
+impl FileStateReader {
+    pub fn new(dump: StateDump) -> Self {
+        Self { dump }
+    }
+
+    pub fn from_file(path: &Path, format: StateDumpFormat) -> StateDumpResult<Self> {
+        Ok(Self::new(StateDump::read_from_file(path, format)?))
+    }
+
+    pub fn dump(&self) -> &StateDump {
+        &self.dump
+    }
+}
+
+impl StateReader for FileStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        let value = self
+            .dump
+            .storage
+            .get(&contract_address)
+            .and_then(|contract_storage| contract_storage.get(&key))
+            .copied()
+            .unwrap_or_default();
+        Ok(value)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        Ok(self.dump.nonces.get(&contract_address).copied().unwrap_or_default())
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        Ok(self.dump.class_hashes.get(&contract_address).copied().unwrap_or_default())
+    }
+
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        self.dump
+            .classes
+            .get(&class_hash)
+            .cloned()
+            .ok_or(StateError::UndeclaredClassHash(class_hash))
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        Ok(self.dump.compiled_class_hashes.get(&class_hash).copied().unwrap_or_default())
+    }
+
+    fn get_n_initialized_storage_leaves(
+        &self,
+        contract_address: ContractAddress,
+    ) -> StateResult<Option<usize>> {
+        let n_leaves = self.dump.storage.get(&contract_address).map_or(0, |contract_storage| {
+            contract_storage.values().filter(|value| **value != Felt::ZERO).count()
+        });
+        Ok(Some(n_leaves))
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::{BTreeMap, HashMap};
+
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use starknet_api::core::{ClassHash, ContractAddress, PatriciaKey};
+use starknet_api::{class_hash, contract_address, felt, patricia_key};
+use starknet_types_core::felt::Felt;
+
+use crate::state::cached_state::CachedState;
+use crate::state::errors::{StateDumpError, StateError};
+use crate::state::state_api::{State, StateReader};
+use crate::state::state_dump::{FileStateReader, StateDump, StateDumpFormat};
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::dict_state_reader::DictStateReader;
+use crate::test_utils::CairoVersion;
+use crate::{compiled_class_hash, nonce, storage_key};
+
+#[rstest]
+fn test_dump_and_load_state(
+    #[values(StateDumpFormat::Json, StateDumpFormat::Binary)] format: StateDumpFormat,
+) {
+    let cairo0_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let cairo1_contract = FeatureContract::TestContract(CairoVersion::Cairo1);
+    let (cairo0_class_hash, cairo1_class_hash) =
+        (cairo0_contract.get_class_hash(), cairo1_contract.get_class_hash());
+    let (contract_address0, contract_address1) =
+        (contract_address!("0x100"), contract_address!("0x200"));
+    let (key0, key1) = (storage_key!(0x10_u16), storage_key!(0x20_u16));
+    let mut state = CachedState::from(DictStateReader {
+        storage_view: HashMap::from([((contract_address0, key0), felt!("0x1"))]),
+        address_to_nonce: HashMap::from([(contract_address0, nonce!(2_u8))]),
+        address_to_class_hash: HashMap::from([(contract_address0, cairo0_class_hash)]),
+        class_hash_to_class: HashMap::from([(cairo0_class_hash, cairo0_contract.get_class())]),
+        ..Default::default()
+    });
+
+    // Read and write through the cached state.
+    assert_eq!(state.get_storage_at(contract_address0, key0).unwrap(), felt!("0x1"));
+    assert_eq!(state.get_class_hash_at(contract_address0).unwrap(), cairo0_class_hash);
+    state.set_storage_at(contract_address1, key1, felt!("0x7")).unwrap();
+    state.increment_nonce(contract_address0).unwrap();
+    state.set_contract_class(cairo1_class_hash, cairo1_contract.get_class()).unwrap();
+    state.set_class_hash_at(contract_address1, cairo1_class_hash).unwrap();
+    state.set_compiled_class_hash(cairo1_class_hash, compiled_class_hash!(3_u8)).unwrap();
+    state.get_compiled_contract_class(cairo0_class_hash).unwrap();
+
+    let dump = StateDump::from_cached_state(&state);
+    assert_eq!(
+        dump,
+        StateDump {
+            storage: BTreeMap::from([
+                (contract_address0, BTreeMap::from([(key0, felt!("0x1"))])),
+                (contract_address1, BTreeMap::from([(key1, felt!("0x7"))])),
+            ]),
+            nonces: BTreeMap::from([(contract_address0, nonce!(3_u8))]),
+            class_hashes: BTreeMap::from([
+                (contract_address0, cairo0_class_hash),
+                (contract_address1, cairo1_class_hash),
+            ]),
+            compiled_class_hashes: BTreeMap::from([(
+                cairo1_class_hash,
+                compiled_class_hash!(3_u8)
+            )]),
+            classes: BTreeMap::from([
+                (cairo0_class_hash, cairo0_contract.get_class()),
+                (cairo1_class_hash, cairo1_contract.get_class()),
+            ]),
+        }
+    );
+
+    // The encoding is deterministic, and preserves the dump (including the contract classes).
+    let encoded_dump = dump.encode(format).unwrap();
+    assert_eq!(StateDump::from_cached_state(&state).encode(format).unwrap(), encoded_dump);
+    assert_eq!(StateDump::decode(&encoded_dump, format).unwrap(), dump);
+
+    // Load the state back from a file.
+    let dump_file = tempfile::NamedTempFile::new().unwrap();
+    dump.write_to_file(dump_file.path(), format).unwrap();
+    let file_state = FileStateReader::from_file(dump_file.path(), format).unwrap();
+    assert_eq!(file_state.dump(), &dump);
+    assert_eq!(file_state.get_storage_at(contract_address1, key1).unwrap(), felt!("0x7"));
+    assert_eq!(file_state.get_storage_at(contract_address1, key0).unwrap(), Felt::ZERO);
+    assert_eq!(file_state.get_nonce_at(contract_address0).unwrap(), nonce!(3_u8));
+    assert_eq!(file_state.get_class_hash_at(contract_address1).unwrap(), cairo1_class_hash);
+    assert_eq!(
+        file_state.get_class_hash_at(contract_address!("0x300")).unwrap(),
+        ClassHash::default()
+    );
+    assert_eq!(
+        file_state.get_compiled_class_hash(cairo1_class_hash).unwrap(),
+        compiled_class_hash!(3_u8)
+    );
+    assert_eq!(
+        file_state.get_compiled_contract_class(cairo0_class_hash).unwrap(),
+        cairo0_contract.get_class()
+    );
+    assert_matches!(
+        file_state.get_compiled_contract_class(class_hash!("0x99")).unwrap_err(),
+        StateError::UndeclaredClassHash(class_hash) if class_hash == class_hash!("0x99")
+    );
+}
+
+#[test]
+fn test_dump_state_over_file_state() {
+    let (contract_address0, contract_address1) =
+        (contract_address!("0x100"), contract_address!("0x200"));
+    let (key0, key1) = (storage_key!(0x10_u16), storage_key!(0x20_u16));
+    let file_state = FileStateReader::new(StateDump {
+        storage: BTreeMap::from([(
+            contract_address0,
+            BTreeMap::from([(key0, felt!("0x1")), (key1, felt!("0x2"))]),
+        )]),
+        nonces: BTreeMap::from([(contract_address0, nonce!(1_u8))]),
+        ..Default::default()
+    });
+    let mut state = CachedState::new(file_state);
+    state.set_storage_at(contract_address0, key1, Felt::ZERO).unwrap();
+    state.set_storage_at(contract_address1, key0, felt!("0x3")).unwrap();
+
+    // The dump of the cached state holds the accessed entries only.
+    assert_eq!(
+        StateDump::from_cached_state(&state),
+        StateDump {
+            storage: BTreeMap::from([
+                (contract_address0, BTreeMap::from([(key1, Felt::ZERO)])),
+                (contract_address1, BTreeMap::from([(key0, felt!("0x3"))])),
+            ]),
+            ..Default::default()
+        }
+    );
+
+    // Over a dump, it holds the entire state, including the entries not accessed through the
+    // cache.
+    let dump = StateDump::from_cached_file_state(&state);
+    assert_eq!(
+        dump,
+        StateDump {
+            storage: BTreeMap::from([
+                (contract_address0, BTreeMap::from([(key0, felt!("0x1")), (key1, Felt::ZERO)])),
+                (contract_address1, BTreeMap::from([(key0, felt!("0x3"))])),
+            ]),
+            nonces: BTreeMap::from([(contract_address0, nonce!(1_u8))]),
+            ..Default::default()
+        }
+    );
+    let n_leaves = FileStateReader::new(dump).get_n_initialized_storage_leaves(contract_address0);
+    assert_eq!(n_leaves.unwrap(), Some(1));
+}
+
+#[test]
+fn test_invalid_binary_dump() {
+    let encoded_dump = StateDump {
+        nonces: BTreeMap::from([(contract_address!("0x100"), nonce!(1_u8))]),
+        ..Default::default()
+    }
+    .encode(StateDumpFormat::Binary)
+    .unwrap();
+
+    let truncated_dump = encoded_dump[..encoded_dump.len() - 1].to_vec();
+    let extended_dump = [encoded_dump.as_slice(), &[0_u8]].concat();
+    let unknown_header_dump = [b"NOTADUMP".as_slice(), &encoded_dump[8..]].concat();
+    for invalid_dump in [truncated_dump, extended_dump, unknown_header_dump] {
+        assert_matches!(
+            StateDump::decode(&invalid_dump, StateDumpFormat::Binary).unwrap_err(),
+            StateDumpError::InvalidBinaryDump(_)
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+use crate::state::cached_state::{
+    sort_map, sort_storage, CommitmentStateDiff, SortedStorage, StateChangesKeys, StateMaps,
+    StorageEntry,
+};
+
+fn flatten_storage(storage: SortedStorage) -> impl Iterator<Item = (StorageEntry, Felt)> {
+    storage.into_iter().flat_map(|(address, contract_storage)| {
+        contract_storage.into_iter().map(move |(key, value)| ((address, key), value))
+    })
+}
+
+/// The canonical serialization format of `StateMaps`: all entries are ordered by their keys, and
+/// storage entries are grouped by contract address.
+#[derive(Deserialize, Serialize)]
+struct SerializableStateMaps {
+    nonces: BTreeMap<ContractAddress, Nonce>,
+    class_hashes: BTreeMap<ContractAddress, ClassHash>,
+    storage: SortedStorage,
+    compiled_class_hashes: BTreeMap<ClassHash, CompiledClassHash>,
+    declared_contracts: BTreeMap<ClassHash, bool>,
+}
+
+impl From<&StateMaps> for SerializableStateMaps {
+    fn from(state_maps: &StateMaps) -> Self {
+        Self {
+            nonces: sort_map(&state_maps.nonces),
+            class_hashes: sort_map(&state_maps.class_hashes),
+            storage: sort_storage(&state_maps.storage),
+            compiled_class_hashes: sort_map(&state_maps.compiled_class_hashes),
+            declared_contracts: sort_map(&state_maps.declared_contracts),
+        }
+    }
+}
+
+impl From<SerializableStateMaps> for StateMaps {
+    fn from(state_maps: SerializableStateMaps) -> Self {
+        Self {
+            nonces: state_maps.nonces.into_iter().collect(),
+            class_hashes: state_maps.class_hashes.into_iter().collect(),
+            storage: flatten_storage(state_maps.storage).collect(),
+            compiled_class_hashes: state_maps.compiled_class_hashes.into_iter().collect(),
+            declared_contracts: state_maps.declared_contracts.into_iter().collect(),
+        }
+    }
+}
+
+impl Serialize for StateMaps {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializableStateMaps::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StateMaps {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(SerializableStateMaps::deserialize(deserializer)?.into())
+    }
+}
+
+/// The canonical serialization format of `CommitmentStateDiff`; see `SerializableStateMaps`.
+#[derive(Deserialize, Serialize)]
+struct SerializableCommitmentStateDiff {
+    address_to_class_hash: BTreeMap<ContractAddress, ClassHash>,
+    address_to_nonce: BTreeMap<ContractAddress, Nonce>,
+    storage_updates: SortedStorage,
+    class_hash_to_compiled_class_hash: BTreeMap<ClassHash, CompiledClassHash>,
+}
+
+impl From<&CommitmentStateDiff> for SerializableCommitmentStateDiff {
+    fn from(diff: &CommitmentStateDiff) -> Self {
+        Self {
+            address_to_class_hash: sort_map(&diff.address_to_class_hash),
+            address_to_nonce: sort_map(&diff.address_to_nonce),
+            storage_updates: diff
+                .storage_updates
+                .iter()
+                .map(|(&address, contract_storage)| (address, sort_map(contract_storage)))
+                .collect(),
+            class_hash_to_compiled_class_hash: sort_map(&diff.class_hash_to_compiled_class_hash),
+        }
+    }
+}
+
+impl From<SerializableCommitmentStateDiff> for CommitmentStateDiff {
+    fn from(diff: SerializableCommitmentStateDiff) -> Self {
+        Self {
+            address_to_class_hash: diff.address_to_class_hash.into_iter().collect(),
+            address_to_nonce: diff.address_to_nonce.into_iter().collect(),
+            storage_updates: diff
+                .storage_updates
+                .into_iter()
+                .map(|(address, contract_storage)| {
+                    (address, contract_storage.into_iter().collect())
+                })
+                .collect(),
+            class_hash_to_compiled_class_hash: diff
+                .class_hash_to_compiled_class_hash
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+impl Serialize for CommitmentStateDiff {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializableCommitmentStateDiff::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CommitmentStateDiff {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(SerializableCommitmentStateDiff::deserialize(deserializer)?.into())
+    }
+}
+
+/// The canonical serialization format of `StateChangesKeys`; see `SerializableStateMaps`.
+#[derive(Deserialize, Serialize)]
+struct SerializableStateChangesKeys {
+    nonce_keys: BTreeSet<ContractAddress>,
+    class_hash_keys: BTreeSet<ContractAddress>,
+    storage_keys: BTreeMap<ContractAddress, BTreeSet<StorageKey>>,
+    compiled_class_hash_keys: BTreeSet<ClassHash>,
+    modified_contracts: BTreeSet<ContractAddress>,
+}
+
+impl From<&StateChangesKeys> for SerializableStateChangesKeys {
+    fn from(keys: &StateChangesKeys) -> Self {
+        let mut storage_keys: BTreeMap<ContractAddress, BTreeSet<StorageKey>> = BTreeMap::new();
+        for &(address, key) in &keys.storage_keys {
+            storage_keys.entry(address).or_default().insert(key);
+        }
+        Self {
+            nonce_keys: keys.nonce_keys.iter().copied().collect(),
+            class_hash_keys: keys.class_hash_keys.iter().copied().collect(),
+            storage_keys,
+            compiled_class_hash_keys: keys.compiled_class_hash_keys.iter().copied().collect(),
+            modified_contracts: keys.modified_contracts.iter().copied().collect(),
+        }
+    }
+}
+
+impl From<SerializableStateChangesKeys> for StateChangesKeys {
+    fn from(keys: SerializableStateChangesKeys) -> Self {
+        Self {
+            nonce_keys: keys.nonce_keys.into_iter().collect(),
+            class_hash_keys: keys.class_hash_keys.into_iter().collect(),
+            storage_keys: keys
+                .storage_keys
+                .into_iter()
+                .flat_map(|(address, keys)| keys.into_iter().map(move |key| (address, key)))
+                .collect(),
+            compiled_class_hash_keys: keys.compiled_class_hash_keys.into_iter().collect(),
+            modified_contracts: keys.modified_contracts.into_iter().collect(),
+        }
+    }
+}
+
+impl Serialize for StateChangesKeys {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializableStateChangesKeys::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StateChangesKeys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(SerializableStateChangesKeys::deserialize(deserializer)?.into())
+    }
+}
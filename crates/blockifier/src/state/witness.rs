@@ -0,0 +1,222 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+use crate::execution::contract_class::ContractClass;
+use crate::state::errors::StateError;
+use crate::state::state_dump::{deserialize_classes, serialize_classes};
+use crate::state::state_api::{verify_bulk_read_length, StateReader, StateResult};
+
+#[cfg(test)]
+#[path = "witness_test.rs"]
+pub mod test;
+
+/// The values read from the state during the execution of a block; sufficient to re-execute the
+/// block without access to the state.
+///
+/// Classes are serialized in their portable form, as in a state dump.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockWitness {
+    pub storage: BTreeMap<ContractAddress, BTreeMap<StorageKey, Felt>>,
+    pub nonces: BTreeMap<ContractAddress, Nonce>,
+    pub class_hashes: BTreeMap<ContractAddress, ClassHash>,
+    pub compiled_class_hashes: BTreeMap<ClassHash, CompiledClassHash>,
+    /// Whether each class read is declared.
+    pub declared_classes: BTreeMap<ClassHash, bool>,
+    pub n_initialized_storage_leaves: BTreeMap<ContractAddress, Option<usize>>,
+    #[serde(serialize_with = "serialize_classes", deserialize_with = "deserialize_classes")]
+    pub classes: BTreeMap<ClassHash, ContractClass>,
+}
+
+/// A state entry, missing from a block witness.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WitnessKey {
+    Storage(ContractAddress, StorageKey),
+    Nonce(ContractAddress),
+    ClassHash(ContractAddress),
+    CompiledClassHash(ClassHash),
+    Class(ClassHash),
+    NInitializedStorageLeaves(ContractAddress),
+}
+
+/// Wraps a state reader, and records the values read from it into a block witness.
+/// Note: to record each value once, wrap the reader of the block state (i.e., under its cache).
+pub struct RecordingStateReader<S: StateReader> {
+    pub state: S,
+    witness: RefCell<BlockWitness>,
+}
+
+impl<S: StateReader> RecordingStateReader<S> {
+    pub fn new(state: S) -> Self {
+        Self { state, witness: RefCell::new(BlockWitness::default()) }
+    }
+
+    /// Returns the witness of the reads recorded so far.
+    pub fn witness(&self) -> BlockWitness {
+        self.witness.borrow().clone()
+    }
+
+    pub fn into_witness(self) -> BlockWitness {
+        self.witness.into_inner()
+    }
+
+    fn record_storage(&self, contract_address: ContractAddress, key: StorageKey, value: Felt) {
+        self.witness.borrow_mut().storage.entry(contract_address).or_default().insert(key, value);
+    }
+}
+
+impl<S: StateReader> StateReader for RecordingStateReader<S> {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        let value = self.state.get_storage_at(contract_address, key)?;
+        self.record_storage(contract_address, key, value);
+        Ok(value)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        let nonce = self.state.get_nonce_at(contract_address)?;
+        self.witness.borrow_mut().nonces.insert(contract_address, nonce);
+        Ok(nonce)
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        let class_hash = self.state.get_class_hash_at(contract_address)?;
+        self.witness.borrow_mut().class_hashes.insert(contract_address, class_hash);
+        Ok(class_hash)
+    }
+
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        let result = self.state.get_compiled_contract_class(class_hash);
+        let mut witness = self.witness.borrow_mut();
+        match &result {
+            Ok(contract_class) => {
+                witness.declared_classes.insert(class_hash, true);
+                witness.classes.insert(class_hash, contract_class.clone());
+            }
+            Err(StateError::UndeclaredClassHash(_)) => {
+                witness.declared_classes.insert(class_hash, false);
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        let compiled_class_hash = self.state.get_compiled_class_hash(class_hash)?;
+        self.witness.borrow_mut().compiled_class_hashes.insert(class_hash, compiled_class_hash);
+        Ok(compiled_class_hash)
+    }
+
+    fn get_n_initialized_storage_leaves(
+        &self,
+        contract_address: ContractAddress,
+    ) -> StateResult<Option<usize>> {
+        let n_leaves = self.state.get_n_initialized_storage_leaves(contract_address)?;
+        self.witness.borrow_mut().n_initialized_storage_leaves.insert(contract_address, n_leaves);
+        Ok(n_leaves)
+    }
+
+    fn get_storage_many(
+        &self,
+        storage_keys: &[(ContractAddress, StorageKey)],
+    ) -> StateResult<Vec<Felt>> {
+        let values = self.state.get_storage_many(storage_keys)?;
+        verify_bulk_read_length(storage_keys.len(), values.len())?;
+        for (&(contract_address, key), &value) in storage_keys.iter().zip(&values) {
+            self.record_storage(contract_address, key, value);
+        }
+        Ok(values)
+    }
+
+    fn get_nonces_many(&self, contract_addresses: &[ContractAddress]) -> StateResult<Vec<Nonce>> {
+        let nonces = self.state.get_nonces_many(contract_addresses)?;
+        verify_bulk_read_length(contract_addresses.len(), nonces.len())?;
+        self.witness
+            .borrow_mut()
+            .nonces
+            .extend(contract_addresses.iter().copied().zip(nonces.iter().copied()));
+        Ok(nonces)
+    }
+}
+
+/// A state reader serving reads from a block witness only; reading an entry missing from the
+/// witness fails.
+pub struct WitnessStateReader {
+    witness: BlockWitness,
+}
+
+impl WitnessStateReader {
+    pub fn new(witness: BlockWitness) -> Self {
+        Self { witness }
+    }
+}
+
+impl StateReader for WitnessStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.witness
+            .storage
+            .get(&contract_address)
+            .and_then(|contract_storage| contract_storage.get(&key))
+            .copied()
+            .ok_or(StateError::MissingWitnessEntry(WitnessKey::Storage(contract_address, key)))
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.witness
+            .nonces
+            .get(&contract_address)
+            .copied()
+            .ok_or(StateError::MissingWitnessEntry(WitnessKey::Nonce(contract_address)))
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.witness
+            .class_hashes
+            .get(&contract_address)
+            .copied()
+            .ok_or(StateError::MissingWitnessEntry(WitnessKey::ClassHash(contract_address)))
+    }
+
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        match self.witness.declared_classes.get(&class_hash) {
+            Some(false) => Err(StateError::UndeclaredClassHash(class_hash)),
+            Some(true) => self
+                .witness
+                .classes
+                .get(&class_hash)
+                .cloned()
+                .ok_or(StateError::MissingWitnessEntry(WitnessKey::Class(class_hash))),
+            None => Err(StateError::MissingWitnessEntry(WitnessKey::Class(class_hash))),
+        }
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.witness
+            .compiled_class_hashes
+            .get(&class_hash)
+            .copied()
+            .ok_or(StateError::MissingWitnessEntry(WitnessKey::CompiledClassHash(class_hash)))
+    }
+
+    fn get_n_initialized_storage_leaves(
+        &self,
+        contract_address: ContractAddress,
+    ) -> StateResult<Option<usize>> {
+        self.witness.n_initialized_storage_leaves.get(&contract_address).copied().ok_or(
+            StateError::MissingWitnessEntry(WitnessKey::NInitializedStorageLeaves(
+                contract_address,
+            )),
+        )
+    }
+}
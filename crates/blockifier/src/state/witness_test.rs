@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, HashMap};
+
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use starknet_api::core::{ClassHash, ContractAddress, PatriciaKey};
+use starknet_api::{class_hash, contract_address, felt, patricia_key};
+use starknet_types_core::felt::Felt;
+
+use crate::context::BlockContext;
+use crate::invoke_tx_args;
+use crate::state::cached_state::CachedState;
+use crate::state::errors::StateError;
+use crate::state::state_api::{State, StateReader};
+use crate::state::witness::{BlockWitness, RecordingStateReader, WitnessKey, WitnessStateReader};
+use crate::test_utils::contracts::FeatureContract;
+use crate::test_utils::dict_state_reader::DictStateReader;
+use crate::test_utils::initial_test_state::test_state;
+use crate::test_utils::{create_calldata, CairoVersion, BALANCE};
+use crate::transaction::test_utils::{account_invoke_tx, max_resource_bounds};
+use crate::transaction::transactions::ExecutableTransaction;
+use crate::{nonce, storage_key};
+
+#[test]
+fn test_record_and_replay_witness() {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let (class_hash, undeclared_class_hash) = (test_contract.get_class_hash(), class_hash!("0x99"));
+    let (contract_address0, contract_address1) =
+        (contract_address!("0x100"), contract_address!("0x200"));
+    let (key0, key1) = (storage_key!(0x10_u16), storage_key!(0x20_u16));
+    let mut state = CachedState::new(RecordingStateReader::new(DictStateReader {
+        storage_view: HashMap::from([((contract_address0, key0), felt!("0x1"))]),
+        address_to_nonce: HashMap::from([(contract_address0, nonce!(2_u8))]),
+        address_to_class_hash: HashMap::from([(contract_address0, class_hash)]),
+        class_hash_to_class: HashMap::from([(class_hash, test_contract.get_class())]),
+        ..Default::default()
+    }));
+
+    // Read (and write) through the cached state; only the reads from the underlying state are
+    // recorded.
+    state.set_storage_at(contract_address1, key1, felt!("0x7")).unwrap();
+    assert_eq!(state.get_storage_at(contract_address0, key0).unwrap(), felt!("0x1"));
+    assert_eq!(state.get_storage_at(contract_address1, key1).unwrap(), felt!("0x7"));
+    state.increment_nonce(contract_address0).unwrap();
+    assert_eq!(state.get_class_hash_at(contract_address1).unwrap(), ClassHash::default());
+    state.get_compiled_contract_class(class_hash).unwrap();
+    state.get_compiled_contract_class(undeclared_class_hash).unwrap_err();
+    state.to_state_diff().unwrap();
+
+    let witness = state.state.into_witness();
+    assert_eq!(
+        witness,
+        BlockWitness {
+            storage: BTreeMap::from([
+                (contract_address0, BTreeMap::from([(key0, felt!("0x1"))])),
+                (contract_address1, BTreeMap::from([(key1, Felt::ZERO)])),
+            ]),
+            nonces: BTreeMap::from([(contract_address0, nonce!(2_u8))]),
+            class_hashes: BTreeMap::from([(contract_address1, ClassHash::default())]),
+            declared_classes: BTreeMap::from([(class_hash, true), (undeclared_class_hash, false)]),
+            classes: BTreeMap::from([(class_hash, test_contract.get_class())]),
+            ..Default::default()
+        }
+    );
+
+    // The witness is serializable, including the classes.
+    let serialized_witness = serde_json::to_string(&witness).unwrap();
+    let deserialized_witness: BlockWitness = serde_json::from_str(&serialized_witness).unwrap();
+    assert_eq!(deserialized_witness, witness);
+
+    // Replay the reads from the witness.
+    let witness_state = WitnessStateReader::new(witness);
+    assert_eq!(witness_state.get_storage_at(contract_address0, key0).unwrap(), felt!("0x1"));
+    assert_eq!(witness_state.get_nonce_at(contract_address0).unwrap(), nonce!(2_u8));
+    assert_eq!(
+        witness_state.get_compiled_contract_class(class_hash).unwrap(),
+        test_contract.get_class()
+    );
+    assert_matches!(
+        witness_state.get_compiled_contract_class(undeclared_class_hash).unwrap_err(),
+        StateError::UndeclaredClassHash(hash) if hash == undeclared_class_hash
+    );
+
+    // Reading entries missing from the witness fails.
+    assert_matches!(
+        witness_state.get_storage_at(contract_address0, key1).unwrap_err(),
+        StateError::MissingWitnessEntry(WitnessKey::Storage(address, key))
+        if address == contract_address0 && key == key1
+    );
+    assert_matches!(
+        witness_state.get_class_hash_at(contract_address0).unwrap_err(),
+        StateError::MissingWitnessEntry(WitnessKey::ClassHash(address))
+        if address == contract_address0
+    );
+    let class_less_witness_state =
+        WitnessStateReader::new(BlockWitness { classes: BTreeMap::new(), ..deserialized_witness });
+    assert_matches!(
+        class_less_witness_state.get_compiled_contract_class(class_hash).unwrap_err(),
+        StateError::MissingWitnessEntry(WitnessKey::Class(hash)) if hash == class_hash
+    );
+}
+
+#[test]
+fn test_reexecute_from_serialized_witness() {
+    let block_context = BlockContext::create_for_account_testing();
+    let account = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1);
+    let initial_state =
+        test_state(&block_context.chain_info, BALANCE, &[(account, 1), (test_contract, 1)]).state;
+    let tx = account_invoke_tx(invoke_tx_args! {
+        sender_address: account.get_instance_address(0),
+        calldata: create_calldata(
+            test_contract.get_instance_address(0),
+            "test_storage_read_write",
+            &[felt!(1993_u16), felt!(93_u8)],
+        ),
+        resource_bounds: max_resource_bounds(),
+    });
+
+    // Execute the transaction, recording the witness.
+    let mut state = CachedState::new(RecordingStateReader::new(initial_state));
+    let execution_info = tx.execute(&mut state, &block_context, true, true).unwrap();
+    assert!(!execution_info.is_reverted());
+    let state_diff = state.to_state_diff().unwrap();
+    let witness = state.state.into_witness();
+
+    // Re-execute the transaction from the serialized witness alone.
+    let serialized_witness = serde_json::to_string(&witness).unwrap();
+    let deserialized_witness: BlockWitness = serde_json::from_str(&serialized_witness).unwrap();
+    let mut witness_state = CachedState::new(WitnessStateReader::new(deserialized_witness));
+    assert_eq!(tx.execute(&mut witness_state, &block_context, true, true).unwrap(), execution_info);
+    assert_eq!(witness_state.to_state_diff().unwrap(), state_diff);
+}
@@ -57,4 +57,16 @@ impl StateReader for DictStateReader {
             self.class_hash_to_compiled_class_hash.get(&class_hash).copied().unwrap_or_default();
         Ok(compiled_class_hash)
     }
+
+    fn get_n_initialized_storage_leaves(
+        &self,
+        contract_address: ContractAddress,
+    ) -> StateResult<Option<usize>> {
+        let n_leaves = self
+            .storage_view
+            .iter()
+            .filter(|((address, _), value)| *address == contract_address && **value != Felt::ZERO)
+            .count();
+        Ok(Some(n_leaves))
+    }
 }
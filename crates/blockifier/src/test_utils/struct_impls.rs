@@ -12,6 +12,7 @@ use crate::blockifier::block::{BlockInfo, GasPrices};
 use crate::bouncer::{BouncerConfig, BouncerWeights};
 use crate::context::{BlockContext, ChainInfo, FeeTokenAddresses, TransactionContext};
 use crate::execution::call_info::{CallExecution, CallInfo, Retdata};
+use crate::execution::common_hints::ExecutionMode;
 use crate::execution::contract_class::{ContractClassV0, ContractClassV1};
 use crate::execution::entry_point::{
     CallEntryPoint, EntryPointExecutionContext, EntryPointExecutionResult,
@@ -20,9 +21,9 @@ use crate::fee::fee_utils::get_fee_by_gas_vector;
 use crate::state::state_api::State;
 use crate::test_utils::{
     get_raw_contract_class, CHAIN_ID_NAME, CURRENT_BLOCK_NUMBER, CURRENT_BLOCK_TIMESTAMP,
-    DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_STRK_L1_DATA_GAS_PRICE,
-    DEFAULT_STRK_L1_GAS_PRICE, TEST_ERC20_CONTRACT_ADDRESS, TEST_ERC20_CONTRACT_ADDRESS2,
-    TEST_SEQUENCER_ADDRESS,
+    DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_ETH_L2_GAS_PRICE,
+    DEFAULT_STRK_L1_DATA_GAS_PRICE, DEFAULT_STRK_L1_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE,
+    TEST_ERC20_CONTRACT_ADDRESS, TEST_ERC20_CONTRACT_ADDRESS2, TEST_SEQUENCER_ADDRESS,
 };
 use crate::transaction::objects::{
     DeprecatedTransactionInfo, FeeType, TransactionFeeResult, TransactionInfo, TransactionResources,
@@ -48,12 +49,12 @@ impl CallEntryPoint {
         tx_info: TransactionInfo,
         limit_steps_by_resources: bool,
     ) -> EntryPointExecutionResult<CallInfo> {
-        let tx_context =
-            TransactionContext { block_context: BlockContext::create_for_testing(), tx_info };
-        let mut context =
-            EntryPointExecutionContext::new_invoke(Arc::new(tx_context), limit_steps_by_resources)
-                .unwrap();
-        self.execute(state, &mut ExecutionResources::default(), &mut context)
+        self.execute_directly_given_tx_info_in_mode(
+            state,
+            tx_info,
+            ExecutionMode::Execute,
+            limit_steps_by_resources,
+        )
     }
 
     /// Executes the call directly in validate mode, without account context. Limits the number of
@@ -75,13 +76,26 @@ impl CallEntryPoint {
         tx_info: TransactionInfo,
         limit_steps_by_resources: bool,
     ) -> EntryPointExecutionResult<CallInfo> {
-        let tx_context =
-            TransactionContext { block_context: BlockContext::create_for_testing(), tx_info };
-        let mut context = EntryPointExecutionContext::new_validate(
-            Arc::new(tx_context),
+        self.execute_directly_given_tx_info_in_mode(
+            state,
+            tx_info,
+            ExecutionMode::Validate,
             limit_steps_by_resources,
         )
-        .unwrap();
+    }
+
+    pub fn execute_directly_given_tx_info_in_mode(
+        self,
+        state: &mut dyn State,
+        tx_info: TransactionInfo,
+        mode: ExecutionMode,
+        limit_steps_by_resources: bool,
+    ) -> EntryPointExecutionResult<CallInfo> {
+        let tx_context =
+            TransactionContext { block_context: BlockContext::create_for_testing(), tx_info };
+        let mut context =
+            EntryPointExecutionContext::new(Arc::new(tx_context), mode, limit_steps_by_resources)
+                .unwrap();
         self.execute(state, &mut ExecutionResources::default(), &mut context)
     }
 }
@@ -144,6 +158,8 @@ impl BlockInfo {
                 strk_l1_gas_price: DEFAULT_STRK_L1_GAS_PRICE.try_into().unwrap(),
                 eth_l1_data_gas_price: DEFAULT_ETH_L1_DATA_GAS_PRICE.try_into().unwrap(),
                 strk_l1_data_gas_price: DEFAULT_STRK_L1_DATA_GAS_PRICE.try_into().unwrap(),
+                eth_l2_gas_price: DEFAULT_ETH_L2_GAS_PRICE.try_into().unwrap(),
+                strk_l2_gas_price: DEFAULT_STRK_L2_GAS_PRICE.try_into().unwrap(),
             },
             use_kzg_da: false,
         }
@@ -180,6 +196,7 @@ impl BlockContext {
                     n_events: max_n_events_in_block,
                     ..BouncerWeights::max()
                 },
+                ..Default::default()
             },
             ..Self::create_for_account_testing()
         }
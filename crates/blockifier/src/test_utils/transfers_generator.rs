@@ -6,7 +6,9 @@ use starknet_api::{calldata, felt};
 use starknet_types_core::felt::Felt;
 
 use crate::abi::abi_utils::selector_from_name;
-use crate::blockifier::config::{ConcurrencyConfig, TransactionExecutorConfig};
+use crate::blockifier::config::{
+    BlockFillingPolicy, ConcurrencyConfig, SharedTransactionOrdering, TransactionExecutorConfig,
+};
 use crate::blockifier::transaction_executor::TransactionExecutor;
 use crate::context::{BlockContext, ChainInfo};
 use crate::invoke_tx_args;
@@ -88,8 +90,12 @@ impl TransfersGenerator {
         let chain_info = block_context.chain_info().clone();
         let state =
             test_state(&chain_info, config.balance, &[(account_contract, config.n_accounts)]);
-        let executor_config =
-            TransactionExecutorConfig { concurrency_config: config.concurrency_config.clone() };
+        let executor_config = TransactionExecutorConfig {
+            block_filling_policy: BlockFillingPolicy::default(),
+            concurrency_config: config.concurrency_config.clone(),
+            transaction_ordering: SharedTransactionOrdering::default(),
+            verify_tx_hashes: false,
+        };
         let executor = TransactionExecutor::new(state, block_context, executor_config);
         let account_addresses = (0..config.n_accounts)
             .map(|instance_id| account_contract.get_instance_address(instance_id))
@@ -156,8 +162,8 @@ impl TransfersGenerator {
             txs.push(Transaction::AccountTransaction(account_tx));
         }
         let results = self.executor.execute_txs(&txs);
-        assert_eq!(results.len(), self.config.n_txs);
-        for result in results {
+        assert_eq!(results.results.len(), self.config.n_txs);
+        for (_tx_index, result) in results.results {
             assert!(!result.unwrap().is_reverted());
         }
         // TODO(Avi, 01/06/2024): Run the same transactions concurrently on a new state and compare
@@ -7,6 +7,7 @@ pub mod objects;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
 pub mod transaction_execution;
+pub mod transaction_hash;
 pub mod transaction_types;
 pub mod transaction_utils;
 pub mod transactions;
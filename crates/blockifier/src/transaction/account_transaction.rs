@@ -12,12 +12,17 @@ use crate::context::{BlockContext, TransactionContext};
 use crate::execution::call_info::{CallInfo, Retdata};
 use crate::execution::contract_class::ContractClass;
 use crate::execution::entry_point::{CallEntryPoint, CallType, EntryPointExecutionContext};
+use crate::execution::stack_trace::gen_transaction_execution_error_trace;
 use crate::fee::actual_cost::TransactionReceipt;
 use crate::fee::fee_checks::{FeeCheckReportFields, PostExecutionReport};
+use crate::fee::fee_estimation::{FeeEstimation, FeeEstimationConfig};
 use crate::fee::fee_utils::{
     get_fee_by_gas_vector, get_sequencer_balance_keys, verify_can_pay_committed_bounds,
 };
-use crate::fee::gas_usage::{compute_discounted_gas_from_gas_vector, estimate_minimal_gas_vector};
+use crate::fee::gas_usage::{
+    compute_discounted_gas_from_gas_vector, compute_l1_data_gas_allowance,
+    estimate_minimal_gas_vector,
+};
 use crate::retdata;
 use crate::state::cached_state::{StateChanges, TransactionalState};
 use crate::state::state_api::{State, StateReader, UpdatableState};
@@ -26,7 +31,7 @@ use crate::transaction::errors::{
     TransactionExecutionError, TransactionFeeError, TransactionPreValidationError,
 };
 use crate::transaction::objects::{
-    DeprecatedTransactionInfo, HasRelatedFeeType, TransactionExecutionInfo,
+    DeprecatedTransactionInfo, HasRelatedFeeType, RevertError, TransactionExecutionInfo,
     TransactionExecutionResult, TransactionInfo, TransactionInfoCreator,
     TransactionPreValidationResult,
 };
@@ -132,6 +137,30 @@ impl AccountTransaction {
         signature.0.len()
     }
 
+    /// Estimates the resources and fee of the transaction, by executing it without charging fee.
+    /// The given state is not modified.
+    /// Note: query transactions (see `only_query`) should be used, as their signature does not
+    /// authorize the execution of the transaction on-chain.
+    pub fn estimate_fee<U: UpdatableState>(
+        &self,
+        state: &mut U,
+        block_context: &BlockContext,
+        config: &FeeEstimationConfig,
+    ) -> TransactionExecutionResult<FeeEstimation> {
+        let mut transactional_state = TransactionalState::create_transactional(state);
+        let execution_flags = ExecutionFlags {
+            charge_fee: false,
+            validate: !config.skip_validate,
+            concurrency_mode: false,
+        };
+        let execution_result =
+            self.execute_raw(&mut transactional_state, block_context, execution_flags);
+        transactional_state.abort();
+
+        let tx_context = block_context.to_tx_context(self);
+        FeeEstimation::new(&tx_context, &execution_result?, config)
+    }
+
     fn verify_tx_version(&self, version: TransactionVersion) -> TransactionExecutionResult<()> {
         let allowed_versions: Vec<TransactionVersion> = match self {
             // Support `Declare` of version 0 in order to allow bootstrapping of a new system.
@@ -184,7 +213,7 @@ impl AccountTransaction {
     ) -> TransactionPreValidationResult<()> {
         let minimal_l1_gas_amount_vector =
             estimate_minimal_gas_vector(&tx_context.block_context, self)?;
-        // TODO(Aner, 30/01/24): modify once data gas limit is enforced.
+        // The L1 data gas is paid out of the L1 gas bound.
         let minimal_l1_gas_amount =
             compute_discounted_gas_from_gas_vector(&minimal_l1_gas_amount_vector, tx_context);
 
@@ -199,6 +228,20 @@ impl AccountTransaction {
                 } = context.l1_resource_bounds()?;
 
                 let max_l1_gas_amount_as_u128: u128 = max_l1_gas_amount.into();
+                if max_l1_gas_amount_as_u128 < minimal_l1_gas_amount
+                    && max_l1_gas_amount_as_u128 >= minimal_l1_gas_amount_vector.l1_gas
+                {
+                    // The bound covers the L1 gas, but not the L1 data gas on top of it.
+                    return Err(TransactionFeeError::MaxL1DataGasAmountTooLow {
+                        max_l1_gas_amount,
+                        max_l1_data_gas_amount: compute_l1_data_gas_allowance(
+                            max_l1_gas_amount_as_u128,
+                            minimal_l1_gas_amount_vector.l1_gas,
+                            tx_context,
+                        ),
+                        minimal_l1_data_gas_amount: minimal_l1_gas_amount_vector.l1_data_gas,
+                    })?;
+                }
                 if max_l1_gas_amount_as_u128 < minimal_l1_gas_amount {
                     return Err(TransactionFeeError::MaxL1GasAmountTooLow {
                         max_l1_gas_amount,
@@ -217,6 +260,18 @@ impl AccountTransaction {
                         actual_l1_gas_price: actual_l1_gas_price.into(),
                     })?;
                 }
+
+                if block_context.versioned_constants.enable_l2_gas {
+                    let max_l2_gas_price = context.l2_resource_bounds().max_price_per_unit;
+                    let actual_l2_gas_price =
+                        block_info.gas_prices.get_l2_gas_price_by_fee_type(fee_type);
+                    if max_l2_gas_price < actual_l2_gas_price.into() {
+                        return Err(TransactionFeeError::MaxL2GasPriceTooLow {
+                            max_l2_gas_price,
+                            actual_l2_gas_price: actual_l2_gas_price.into(),
+                        })?;
+                    }
+                }
             }
             TransactionInfo::Deprecated(context) => {
                 let max_fee = context.max_fee;
@@ -460,6 +515,7 @@ impl AccountTransaction {
             &resources,
             validate_call_info.iter().chain(execute_call_info.iter()),
             0,
+            None,
         )?;
 
         let post_execution_report =
@@ -505,21 +561,27 @@ impl AccountTransaction {
         // resource and fee calculation.
         let validate_state_changes = state.get_actual_state_changes()?;
 
-        // Create copies of state and resources for the execution.
-        // Both will be rolled back if the execution is reverted or committed upon success.
+        // Create a copy of the resources and a savepoint of the state for the execution.
+        // Both will be rolled back if the execution is reverted or kept upon success.
         let mut execution_resources = resources.clone();
-        let mut execution_state = TransactionalState::create_transactional(state);
+        let execution_savepoint = state.savepoint();
 
         let execution_result = self.run_execute(
-            &mut execution_state,
+            state,
             &mut execution_resources,
             &mut execution_context,
             remaining_gas,
         );
 
-        // Pre-compute cost in case of revert.
+        // Pre-compute cost in case of revert; the reverted execution is the completed one, if the
+        // post-execution checks fail, or the failed one.
         let execution_steps_consumed =
             n_allotted_execution_steps - execution_context.n_remaining_steps();
+        let failed_call_info = execution_context.take_failed_call_info();
+        let reverted_call_info = match &execution_result {
+            Ok(execute_call_info) => execute_call_info.as_ref(),
+            Err(_) => failed_call_info.as_ref(),
+        };
         let revert_cost = TransactionReceipt::from_account_tx(
             self,
             &tx_context,
@@ -527,49 +589,49 @@ impl AccountTransaction {
             &resources,
             validate_call_info.iter(),
             execution_steps_consumed,
+            reverted_call_info,
         )?;
 
         match execution_result {
             Ok(execute_call_info) => {
-                // When execution succeeded, calculate the actual required fee before committing the
-                // transactional state. If max_fee is insufficient, revert the `run_execute` part.
+                // When execution succeeded, calculate the actual required fee before releasing the
+                // savepoint. If max_fee is insufficient, revert the `run_execute` part.
                 let tx_receipt = TransactionReceipt::from_account_tx(
                     self,
                     &tx_context,
                     &StateChanges::merge(vec![
                         validate_state_changes,
-                        execution_state.get_actual_state_changes()?,
+                        state.get_state_changes_since(&execution_savepoint)?,
                     ]),
                     &execution_resources,
                     validate_call_info.iter().chain(execute_call_info.iter()),
                     0,
+                    None,
                 )?;
                 // Post-execution checks.
-                let post_execution_report = PostExecutionReport::new(
-                    &mut execution_state,
-                    &tx_context,
-                    &tx_receipt,
-                    charge_fee,
-                )?;
+                let post_execution_report =
+                    PostExecutionReport::new(state, &tx_context, &tx_receipt, charge_fee)?;
                 match post_execution_report.error() {
                     Some(post_execution_error) => {
                         // Post-execution check failed. Revert the execution, compute the final fee
                         // to charge and recompute resources used (to be consistent with other
                         // revert case, compute resources by adding consumed execution steps to
                         // validation resources).
-                        execution_state.abort();
+                        state.rollback_to_savepoint(execution_savepoint);
                         Ok(ValidateExecuteCallInfo::new_reverted(
                             validate_call_info,
-                            post_execution_error.to_string(),
+                            execute_call_info,
+                            post_execution_error.into(),
                             TransactionReceipt {
                                 fee: post_execution_report.recommended_fee(),
+                                tip: post_execution_report.recommended_tip(),
                                 ..revert_cost
                             },
                         ))
                     }
                     None => {
-                        // Post-execution check passed, commit the execution.
-                        execution_state.commit();
+                        // Post-execution check passed, keep the execution.
+                        state.release_savepoint(execution_savepoint);
                         Ok(ValidateExecuteCallInfo::new_accepted(
                             validate_call_info,
                             execute_call_info,
@@ -580,14 +642,16 @@ impl AccountTransaction {
             }
             Err(execution_error) => {
                 // Error during execution. Revert, even if the error is sequencer-related.
-                execution_state.abort();
+                state.rollback_to_savepoint(execution_savepoint);
                 let post_execution_report =
                     PostExecutionReport::new(state, &tx_context, &revert_cost, charge_fee)?;
                 Ok(ValidateExecuteCallInfo::new_reverted(
                     validate_call_info,
-                    execution_error.to_string(),
+                    failed_call_info,
+                    gen_transaction_execution_error_trace(&execution_error).into(),
                     TransactionReceipt {
                         fee: post_execution_report.recommended_fee(),
+                        tip: post_execution_report.recommended_tip(),
                         ..revert_cost
                     },
                 ))
@@ -651,14 +715,16 @@ impl<U: UpdatableState> ExecutableTransaction<U> for AccountTransaction {
         )?;
 
         // Run validation and execution.
-        let mut remaining_gas = block_context.versioned_constants.tx_initial_gas();
+        let mut remaining_gas = tx_context.initial_gas()?;
         let ValidateExecuteCallInfo {
             validate_call_info,
             execute_call_info,
+            reverted_call_info,
             revert_error,
             final_cost:
                 TransactionReceipt {
                     fee: final_fee,
+                    tip: final_tip,
                     da_gas: final_da_gas,
                     resources: final_resources,
                     gas: total_gas,
@@ -684,11 +750,14 @@ impl<U: UpdatableState> ExecutableTransaction<U> for AccountTransaction {
             fee_transfer_call_info,
             transaction_receipt: TransactionReceipt {
                 fee: final_fee,
+                tip: final_tip,
                 da_gas: final_da_gas,
                 resources: final_resources,
                 gas: total_gas,
             },
             revert_error,
+            reverted_call_info,
+            bouncer_weights: None,
         };
         Ok(tx_execution_info)
     }
@@ -708,7 +777,8 @@ impl TransactionInfoCreator for AccountTransaction {
 struct ValidateExecuteCallInfo {
     validate_call_info: Option<CallInfo>,
     execute_call_info: Option<CallInfo>,
-    revert_error: Option<String>,
+    reverted_call_info: Option<CallInfo>,
+    revert_error: Option<RevertError>,
     final_cost: TransactionReceipt,
 }
 
@@ -718,17 +788,25 @@ impl ValidateExecuteCallInfo {
         execute_call_info: Option<CallInfo>,
         final_cost: TransactionReceipt,
     ) -> Self {
-        Self { validate_call_info, execute_call_info, revert_error: None, final_cost }
+        Self {
+            validate_call_info,
+            execute_call_info,
+            reverted_call_info: None,
+            revert_error: None,
+            final_cost,
+        }
     }
 
     pub fn new_reverted(
         validate_call_info: Option<CallInfo>,
-        revert_error: String,
+        reverted_call_info: Option<CallInfo>,
+        revert_error: RevertError,
         final_cost: TransactionReceipt,
     ) -> Self {
         Self {
             validate_call_info,
             execute_call_info: None,
+            reverted_call_info,
             revert_error: Some(revert_error),
             final_cost,
         }
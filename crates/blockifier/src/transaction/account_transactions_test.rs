@@ -9,8 +9,8 @@ use starknet_api::core::{calculate_contract_address, ClassHash, ContractAddress,
 use starknet_api::hash::StarkHash;
 use starknet_api::state::StorageKey;
 use starknet_api::transaction::{
-    Calldata, ContractAddressSalt, DeclareTransactionV2, Fee, ResourceBoundsMapping,
-    TransactionHash, TransactionVersion,
+    Calldata, ContractAddressSalt, DeclareTransactionV2, Fee, Resource, ResourceBounds,
+    ResourceBoundsMapping, TransactionHash, TransactionVersion,
 };
 use starknet_api::{calldata, class_hash, contract_address, felt, patricia_key};
 use starknet_types_core::felt::Felt;
@@ -19,6 +19,7 @@ use crate::abi::abi_utils::{
     get_fee_token_var_address, get_storage_var_address, selector_from_name,
 };
 use crate::context::BlockContext;
+use crate::execution::call_info::CallInfo;
 use crate::execution::contract_class::{ContractClass, ContractClassV1};
 use crate::execution::entry_point::EntryPointExecutionContext;
 use crate::execution::syscalls::SyscallSelector;
@@ -33,7 +34,8 @@ use crate::test_utils::initial_test_state::{fund_account, test_state};
 use crate::test_utils::invoke::InvokeTxArgs;
 use crate::test_utils::{
     create_calldata, create_trivial_calldata, get_syscall_resources, get_tx_resources,
-    u64_from_usize, CairoVersion, NonceManager, BALANCE, DEFAULT_STRK_L1_GAS_PRICE, MAX_FEE,
+    u64_from_usize, CairoVersion, NonceManager, BALANCE, DEFAULT_STRK_L1_GAS_PRICE,
+    DEFAULT_STRK_L2_GAS_PRICE, MAX_FEE, MAX_L1_GAS_AMOUNT, MAX_L1_GAS_PRICE,
 };
 use crate::transaction::account_transaction::AccountTransaction;
 use crate::transaction::constants::TRANSFER_ENTRY_POINT_NAME;
@@ -240,7 +242,9 @@ fn test_invoke_tx_from_non_deployed_account(
     match tx_result {
         Ok(info) => {
             //  Make sure the error is because the account wasn't deployed.
-            assert!(info.revert_error.is_some_and(|err_str| err_str.contains(expected_error)));
+            assert!(
+                info.revert_error.is_some_and(|error| error.to_string().contains(expected_error))
+            );
         }
         Err(err) => {
             //  Make sure the error is because the account wasn't deployed.
@@ -300,6 +304,7 @@ fn test_infinite_recursion(
             tx_execution_info
                 .revert_error
                 .unwrap()
+                .to_string()
                 .contains("RunResources has no remaining steps.")
         );
     }
@@ -478,7 +483,14 @@ fn test_recursion_depth_exceeded(
     };
     let tx_execution_info = run_invoke_tx(&mut state, &block_context, invoke_args);
 
-    assert!(tx_execution_info.unwrap().revert_error.unwrap().contains("recursion depth exceeded"));
+    assert!(
+        tx_execution_info
+            .unwrap()
+            .revert_error
+            .unwrap()
+            .to_string()
+            .contains("recursion depth exceeded")
+    );
 }
 
 #[rstest]
@@ -526,6 +538,23 @@ fn test_revert_invoke(
     // Check that the transaction was reverted.
     assert!(tx_execution_info.revert_error.is_some());
 
+    // Check that the partial execution call info is kept, up to the failing frame.
+    assert!(tx_execution_info.execute_call_info.is_none());
+    let reverted_call_info = tx_execution_info.reverted_call_info.as_ref().unwrap();
+    assert!(reverted_call_info.execution.failed);
+    assert_eq!(reverted_call_info.call.storage_address, account_address);
+    let [failing_call_info] = &reverted_call_info.inner_calls[..] else {
+        panic!("Expected a single inner call.");
+    };
+    assert!(failing_call_info.execution.failed);
+    assert!(failing_call_info.inner_calls.is_empty());
+    assert_eq!(failing_call_info.call.storage_address, test_contract_address);
+    assert!(
+        failing_call_info
+            .accessed_storage_keys
+            .contains(&StorageKey::try_from(storage_key).unwrap())
+    );
+
     // Check that the nonce was increased and the fee was deducted.
     assert_eq!(
         state
@@ -996,7 +1025,13 @@ fn test_insufficient_max_fee_reverts(
     .unwrap();
     assert!(tx_execution_info2.is_reverted());
     assert!(tx_execution_info2.transaction_receipt.fee == actual_fee_depth1);
-    assert!(tx_execution_info2.revert_error.unwrap().starts_with("Insufficient max L1 gas:"));
+    assert!(
+        tx_execution_info2
+            .revert_error
+            .unwrap()
+            .to_string()
+            .starts_with("Insufficient max L1 gas:")
+    );
 
     // Invoke the `recurse` function with depth of 824 and the actual fee of depth 1 as max_fee.
     // This call should fail due to no remaining steps (execution steps based on max_fee are bounded
@@ -1015,7 +1050,11 @@ fn test_insufficient_max_fee_reverts(
     assert!(tx_execution_info3.is_reverted());
     assert!(tx_execution_info3.transaction_receipt.fee == actual_fee_depth1);
     assert!(
-        tx_execution_info3.revert_error.unwrap().contains("RunResources has no remaining steps.")
+        tx_execution_info3
+            .revert_error
+            .unwrap()
+            .to_string()
+            .contains("RunResources has no remaining steps.")
     );
 }
 
@@ -1382,3 +1421,87 @@ fn test_concurrent_fee_transfer_when_sender_is_sequencer(
         assert_eq!(state.get_storage_at(fee_token_address, seq_key).unwrap(), felt!(seq_value));
     }
 }
+
+/// Tests that if L2 gas is enabled, the Sierra gas consumed by Cairo 1 calls, including those of a
+/// reverted execution, is charged as L2 gas instead of their VM resources, and that the initial gas
+/// is capped by the L2 gas bound.
+#[rstest]
+fn test_sierra_gas_charged_as_l2_gas(
+    block_context: BlockContext,
+    #[values(false, true)] reverted: bool,
+) {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1);
+    let account = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1);
+    let l2_gas_bound = 10 * MAX_L1_GAS_AMOUNT;
+    let resource_bounds = ResourceBoundsMapping::try_from(vec![
+        (
+            Resource::L1Gas,
+            ResourceBounds { max_amount: MAX_L1_GAS_AMOUNT, max_price_per_unit: MAX_L1_GAS_PRICE },
+        ),
+        (
+            Resource::L2Gas,
+            ResourceBounds {
+                max_amount: l2_gas_bound,
+                max_price_per_unit: DEFAULT_STRK_L2_GAS_PRICE,
+            },
+        ),
+    ])
+    .unwrap();
+    let calldata = match reverted {
+        false => create_calldata(
+            test_contract.get_instance_address(0),
+            "test_storage_read_write",
+            &[felt!(1993_u16), felt!(93_u8)],
+        ),
+        true => create_calldata(test_contract.get_instance_address(0), "fail", &[]),
+    };
+    let run_tx = |enable_l2_gas: bool| {
+        let mut block_context = block_context.clone();
+        block_context.versioned_constants.enable_l2_gas = enable_l2_gas;
+        let state = &mut test_state(
+            &block_context.chain_info,
+            BALANCE,
+            &[(test_contract, 1), (account, 1)],
+        );
+        let tx_args = invoke_tx_args! {
+            sender_address: account.get_instance_address(0),
+            calldata: calldata.clone(),
+            resource_bounds: resource_bounds.clone(),
+        };
+        let tx_execution_info = run_invoke_tx(state, &block_context, tx_args).unwrap();
+        assert_eq!(tx_execution_info.is_reverted(), reverted);
+        tx_execution_info
+    };
+    let tx_execution_info = run_tx(true);
+    let l2_gas_disabled_resources = run_tx(false).transaction_receipt.resources;
+
+    // The initial gas is capped by the L2 gas bound.
+    let validate_call_info = tx_execution_info.validate_call_info.as_ref().unwrap();
+    assert_eq!(validate_call_info.call.initial_gas, l2_gas_bound);
+
+    // The Sierra gas of the calls is charged as L2 gas, instead of their steps.
+    let resources = &tx_execution_info.transaction_receipt.resources;
+    let (sierra_gas, sierra_gas_vm_resources) = CallInfo::summarize_sierra_gas_usage(
+        tx_execution_info
+            .validate_call_info
+            .iter()
+            .chain(tx_execution_info.execute_call_info.iter()),
+    );
+    let (reverted_sierra_gas, _) =
+        CallInfo::summarize_sierra_gas_usage(tx_execution_info.reverted_call_info.iter());
+    assert_eq!(reverted_sierra_gas > 0, reverted);
+    assert_eq!(resources.sierra_gas, sierra_gas + reverted_sierra_gas);
+    assert!(resources.sierra_gas > validate_call_info.execution.gas_consumed);
+    assert_eq!(tx_execution_info.transaction_receipt.gas.l2_gas, u128::from(resources.sierra_gas));
+    assert_eq!(l2_gas_disabled_resources.sierra_gas, 0);
+    assert_eq!(
+        resources.vm_resources.n_steps,
+        l2_gas_disabled_resources.vm_resources.n_steps - sierra_gas_vm_resources.n_steps
+    );
+
+    // All the reverted calls are charged by Sierra gas.
+    if reverted {
+        assert!(l2_gas_disabled_resources.n_reverted_steps > 0);
+        assert_eq!(resources.n_reverted_steps, 0);
+    }
+}
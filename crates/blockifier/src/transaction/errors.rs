@@ -1,12 +1,16 @@
 use num_bigint::BigUint;
-use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, Nonce};
-use starknet_api::transaction::{Fee, TransactionVersion};
+use starknet_api::core::{
+    ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, Nonce,
+};
+use starknet_api::transaction::{Fee, TransactionHash, TransactionVersion};
 use starknet_api::StarknetApiError;
 use starknet_types_core::felt::FromStrError;
 use thiserror::Error;
 
 use crate::execution::call_info::Retdata;
-use crate::execution::errors::{ConstructorEntryPointExecutionError, EntryPointExecutionError};
+use crate::execution::errors::{
+    ConstructorEntryPointExecutionError, ContractClassError, EntryPointExecutionError,
+};
 use crate::execution::stack_trace::gen_transaction_execution_error_trace;
 use crate::fee::fee_checks::FeeCheckError;
 use crate::state::errors::StateError;
@@ -31,6 +35,15 @@ pub enum TransactionFeeError {
     MaxFeeExceedsBalance { max_fee: Fee, balance: BigUint },
     #[error("Max fee ({}) is too low. Minimum fee: {}.", max_fee.0, min_fee.0)]
     MaxFeeTooLow { min_fee: Fee, max_fee: Fee },
+    #[error(
+        "Max L1 gas amount ({max_l1_gas_amount}) covers {max_l1_data_gas_amount} L1 data gas, \
+         which is lower than the minimal data gas amount: {minimal_l1_data_gas_amount}."
+    )]
+    MaxL1DataGasAmountTooLow {
+        max_l1_gas_amount: u64,
+        max_l1_data_gas_amount: u128,
+        minimal_l1_data_gas_amount: u128,
+    },
     #[error(
         "Max L1 gas price ({max_l1_gas_price}) is lower than the actual gas price: \
          {actual_l1_gas_price}."
@@ -41,6 +54,11 @@ pub enum TransactionFeeError {
          {minimal_l1_gas_amount}."
     )]
     MaxL1GasAmountTooLow { max_l1_gas_amount: u64, minimal_l1_gas_amount: u64 },
+    #[error(
+        "Max L2 gas price ({max_l2_gas_price}) is lower than the actual gas price: \
+         {actual_l2_gas_price}."
+    )]
+    MaxL2GasPriceTooLow { max_l2_gas_price: u128, actual_l2_gas_price: u128 },
     #[error("Missing L1 gas bounds in resource bounds.")]
     MissingL1GasBounds,
     #[error(transparent)]
@@ -49,22 +67,18 @@ pub enum TransactionFeeError {
 
 #[derive(Debug, Error)]
 pub enum TransactionExecutionError {
+    #[error(transparent)]
+    ContractClassError(#[from] ContractClassError),
     #[error(
         "Declare transaction version {} must have a contract class of Cairo \
          version {cairo_version:?}.", **declare_version
     )]
     ContractClassVersionMismatch { declare_version: TransactionVersion, cairo_version: u64 },
-    #[error(
-        "Contract constructor execution has failed:\n{}",
-        String::from(gen_transaction_execution_error_trace(self))
-    )]
+    #[error("{}", gen_transaction_execution_error_trace(self))]
     ContractConstructorExecutionFailed(#[from] ConstructorEntryPointExecutionError),
     #[error("Class with hash {:#064x} is already declared.", **class_hash)]
     DeclareTransactionError { class_hash: ClassHash },
-    #[error(
-        "Transaction execution has failed:\n{}",
-        String::from(gen_transaction_execution_error_trace(self))
-    )]
+    #[error("{}", gen_transaction_execution_error_trace(self))]
     ExecutionError {
         error: EntryPointExecutionError,
         class_hash: ClassHash,
@@ -75,6 +89,24 @@ pub enum TransactionExecutionError {
     FeeCheckError(#[from] FeeCheckError),
     #[error(transparent)]
     FromStr(#[from] FromStrError),
+    #[error(
+        "Transaction hash {:#064x} does not match the computed hash {:#064x}.", tx_hash.0,
+        expected_tx_hash.0
+    )]
+    InvalidTransactionHash { tx_hash: TransactionHash, expected_tx_hash: TransactionHash },
+    #[error(
+        "Class hash {:#064x} does not match the hash of the declared class {:#064x}.", **class_hash,
+        **expected_class_hash
+    )]
+    InvalidClassHash { class_hash: ClassHash, expected_class_hash: ClassHash },
+    #[error(
+        "Compiled class hash {:#064x} does not match the hash of the compiled class {:#064x}.",
+        compiled_class_hash.0, expected_compiled_class_hash.0
+    )]
+    InvalidCompiledClassHash {
+        compiled_class_hash: CompiledClassHash,
+        expected_compiled_class_hash: CompiledClassHash,
+    },
     #[error("The `validate` entry point should return `VALID`. Got {actual:?}.")]
     InvalidValidateReturnData { actual: Retdata },
     #[error(
@@ -94,10 +126,7 @@ pub enum TransactionExecutionError {
     TryFromIntError(#[from] std::num::TryFromIntError),
     #[error("Transaction size exceeds the maximum block capacity.")]
     TransactionTooLarge,
-    #[error(
-        "Transaction validation has failed:\n{}",
-        String::from(gen_transaction_execution_error_trace(self))
-    )]
+    #[error("{}", gen_transaction_execution_error_trace(self))]
     ValidateTransactionError {
         error: EntryPointExecutionError,
         class_hash: ClassHash,
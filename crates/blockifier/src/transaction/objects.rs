@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
@@ -15,9 +16,12 @@ use strum_macros::EnumIter;
 
 use crate::abi::constants as abi_constants;
 use crate::blockifier::block::BlockInfo;
+use crate::bouncer::BouncerWeights;
 use crate::execution::call_info::{CallInfo, ExecutionSummary, MessageL1CostInfo, OrderedEvent};
+use crate::execution::stack_trace::ErrorStack;
 use crate::fee::actual_cost::TransactionReceipt;
 use crate::fee::eth_gas_constants;
+use crate::fee::fee_checks::FeeCheckError;
 use crate::fee::fee_utils::{calculate_l1_gas_by_vm_usage, get_fee_by_gas_vector};
 use crate::fee::gas_usage::{
     get_consumed_message_to_l2_emissions_cost, get_da_gas_cost,
@@ -129,6 +133,11 @@ impl CurrentTransactionInfo {
             None => Err(TransactionFeeError::MissingL1GasBounds),
         }
     }
+
+    /// Fetch the L2 resource bounds; missing bounds are zero.
+    pub fn l2_resource_bounds(&self) -> ResourceBounds {
+        self.resource_bounds.0.get(&Resource::L2Gas).copied().unwrap_or_default()
+    }
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -143,19 +152,24 @@ pub struct DeprecatedTransactionInfo {
 pub struct GasVector {
     pub l1_gas: u128,
     pub l1_data_gas: u128,
+    pub l2_gas: u128,
 }
 
 impl GasVector {
     pub fn from_l1_gas(l1_gas: u128) -> Self {
-        Self { l1_gas, l1_data_gas: 0 }
+        Self { l1_gas, ..Default::default() }
     }
 
     pub fn from_l1_data_gas(l1_data_gas: u128) -> Self {
-        Self { l1_gas: 0, l1_data_gas }
+        Self { l1_data_gas, ..Default::default() }
+    }
+
+    pub fn from_l2_gas(l2_gas: u128) -> Self {
+        Self { l2_gas, ..Default::default() }
     }
 
     /// Computes the cost (in fee token units) of the gas vector (saturating on overflow).
-    pub fn saturated_cost(&self, gas_price: u128, blob_gas_price: u128) -> Fee {
+    pub fn saturated_cost(&self, gas_price: u128, blob_gas_price: u128, l2_gas_price: u128) -> Fee {
         let l1_gas_cost = self.l1_gas.checked_mul(gas_price).unwrap_or_else(|| {
             log::warn!(
                 "L1 gas cost overflowed: multiplication of {} by {} resulted in overflow.",
@@ -172,14 +186,26 @@ impl GasVector {
             );
             u128::MAX
         });
-        let total = l1_gas_cost.checked_add(l1_data_gas_cost).unwrap_or_else(|| {
+        let l2_gas_cost = self.l2_gas.checked_mul(l2_gas_price).unwrap_or_else(|| {
             log::warn!(
-                "Total gas cost overflowed: addition of {} and {} resulted in overflow.",
-                l1_gas_cost,
-                l1_data_gas_cost
+                "L2 gas cost overflowed: multiplication of {} by {} resulted in overflow.",
+                self.l2_gas,
+                l2_gas_price
             );
             u128::MAX
         });
+        let total = [l1_gas_cost, l1_data_gas_cost, l2_gas_cost]
+            .into_iter()
+            .try_fold(0_u128, |total, cost| total.checked_add(cost))
+            .unwrap_or_else(|| {
+                log::warn!(
+                    "Total gas cost overflowed: addition of {}, {} and {} resulted in overflow.",
+                    l1_gas_cost,
+                    l1_data_gas_cost,
+                    l2_gas_cost
+                );
+                u128::MAX
+            });
         Fee(total)
     }
 }
@@ -194,6 +220,36 @@ pub struct CommonAccountFields {
     pub only_query: bool,
 }
 
+/// The reason a transaction was reverted.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub enum RevertError {
+    /// The execution failed; holds the error stack of the failing call.
+    Execution(ErrorStack),
+    /// A post-execution check failed (e.g., the actual fee exceeds the max fee).
+    PostExecution(FeeCheckError),
+}
+
+impl Display for RevertError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Execution(error_stack) => write!(f, "{error_stack}"),
+            Self::PostExecution(fee_check_error) => write!(f, "{fee_check_error}"),
+        }
+    }
+}
+
+impl From<ErrorStack> for RevertError {
+    fn from(error_stack: ErrorStack) -> Self {
+        Self::Execution(error_stack)
+    }
+}
+
+impl From<FeeCheckError> for RevertError {
+    fn from(fee_check_error: FeeCheckError) -> Self {
+        Self::PostExecution(fee_check_error)
+    }
+}
+
 /// Contains the information gathered by the execution of a transaction.
 #[derive(Debug, Default, PartialEq)]
 pub struct TransactionExecutionInfo {
@@ -203,7 +259,11 @@ pub struct TransactionExecutionInfo {
     pub execute_call_info: Option<CallInfo>,
     /// Fee transfer call info; [None] for `L1Handler`.
     pub fee_transfer_call_info: Option<CallInfo>,
-    pub revert_error: Option<String>,
+    pub revert_error: Option<RevertError>,
+    /// The partial execution call info of a reverted transaction, up to the failure point; [None]
+    /// if the transaction was not reverted. The failing frame is the innermost one marked as
+    /// failed. On a failed post-execution check, this is the complete execution call info.
+    pub reverted_call_info: Option<CallInfo>,
     /// The receipt of the transaction.
     /// Including the actual fee that was charged (in units of the relevant fee token),
     /// actual gas consumption the transaction is charged for data availability,
@@ -211,6 +271,9 @@ pub struct TransactionExecutionInfo {
     /// (including L1 gas and additional OS resources estimation),
     /// and total gas consumed.
     pub transaction_receipt: TransactionReceipt,
+    /// The marginal weights of the transaction in the block, as counted by the bouncer; [None] if
+    /// the transaction was not added to a block (e.g., simulated).
+    pub bouncer_weights: Option<BouncerWeights>,
 }
 
 impl TransactionExecutionInfo {
@@ -423,14 +486,19 @@ impl StarknetResources {
 #[derive(Default, Clone, Debug, PartialEq)]
 pub struct TransactionResources {
     pub starknet_resources: StarknetResources,
+    /// Excludes the VM resources of the calls charged by Sierra gas.
     pub vm_resources: ExecutionResources,
+    /// Excludes the steps of the calls charged by Sierra gas.
     pub n_reverted_steps: usize,
+    /// The Sierra gas consumed by the transaction's calls, including a reverted execution, charged
+    /// as L2 gas; zero if L2 gas is not enabled in the versioned constants.
+    pub sierra_gas: u64,
 }
 
 impl TransactionResources {
-    /// Computes and returns the total L1 gas consumption.
+    /// Computes and returns the total gas consumption.
     /// We add the l1_gas_usage (which may include, for example, the direct cost of L2-to-L1
-    /// messages) to the gas consumed by Cairo VM resource.
+    /// messages) to the gas consumed by Cairo VM resource, and the Sierra gas as L2 gas.
     pub fn to_gas_vector(
         &self,
         versioned_constants: &VersionedConstants,
@@ -441,7 +509,8 @@ impl TransactionResources {
                 versioned_constants,
                 &self.vm_resources,
                 self.n_reverted_steps,
-            )?)
+            )?
+            + GasVector::from_l2_gas(self.sierra_gas.into()))
     }
 
     pub fn to_resources_mapping(
@@ -450,7 +519,7 @@ impl TransactionResources {
         use_kzg_da: bool,
         with_reverted_steps: bool,
     ) -> ResourcesMapping {
-        let GasVector { l1_gas, l1_data_gas } =
+        let GasVector { l1_gas, l1_data_gas, .. } =
             self.starknet_resources.to_gas_vector(versioned_constants, use_kzg_da);
         let mut resources = self.vm_resources.to_resources_mapping();
         resources.0.extend(HashMap::from([
@@ -543,7 +612,7 @@ pub trait HasRelatedFeeType {
     }
 }
 
-#[derive(Clone, Copy, Hash, EnumIter, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Hash, EnumIter, Eq, PartialEq)]
 pub enum FeeType {
     Strk,
     Eth,
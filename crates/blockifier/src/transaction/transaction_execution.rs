@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use starknet_api::core::{calculate_contract_address, ContractAddress};
-use starknet_api::transaction::{Fee, Transaction as StarknetApiTransaction, TransactionHash};
+use starknet_api::transaction::{Fee, Tip, Transaction as StarknetApiTransaction, TransactionHash};
 
 use crate::bouncer::verify_tx_weights_in_bounds;
 use crate::context::BlockContext;
@@ -89,6 +89,14 @@ impl Transaction {
             _ => unimplemented!(),
         }
     }
+
+    /// Returns the tip offered by the transaction; transactions of older versions offer no tip.
+    pub fn tip(&self) -> Tip {
+        match self.create_tx_info() {
+            TransactionInfo::Current(context) => context.tip,
+            TransactionInfo::Deprecated(_) => Tip::default(),
+        }
+    }
 }
 
 impl TransactionInfoCreator for Transaction {
@@ -118,6 +126,7 @@ impl<U: UpdatableState> ExecutableTransaction<U> for L1HandlerTransaction {
 
         let TransactionReceipt {
             fee: actual_fee,
+            tip: _,
             da_gas,
             resources: actual_resources,
             gas: total_gas,
@@ -142,11 +151,14 @@ impl<U: UpdatableState> ExecutableTransaction<U> for L1HandlerTransaction {
             fee_transfer_call_info: None,
             transaction_receipt: TransactionReceipt {
                 fee: Fee::default(),
+                tip: Fee::default(),
                 da_gas,
                 resources: actual_resources,
                 gas: total_gas,
             },
             revert_error: None,
+            reverted_call_info: None,
+            bouncer_weights: None,
         })
     }
 }
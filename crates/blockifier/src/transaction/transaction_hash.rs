@@ -0,0 +1,326 @@
+use starknet_api::core::{ChainId, ContractAddress};
+use starknet_api::data_availability::DataAvailabilityMode;
+use starknet_api::transaction::{
+    DeclareTransaction as StarknetApiDeclareTransaction,
+    DeployAccountTransaction as StarknetApiDeployAccountTransaction,
+    InvokeTransaction as StarknetApiInvokeTransaction,
+    L1HandlerTransaction as StarknetApiL1HandlerTransaction, Resource, ResourceBounds,
+    ResourceBoundsMapping, Tip, TransactionHash, TransactionVersion,
+};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+
+use crate::transaction::account_transaction::AccountTransaction;
+use crate::transaction::constants;
+use crate::transaction::errors::TransactionExecutionError;
+use crate::transaction::objects::TransactionExecutionResult;
+use crate::transaction::transaction_execution::Transaction;
+
+#[cfg(test)]
+#[path = "transaction_hash_test.rs"]
+pub mod test;
+
+const DECLARE_PREFIX: &[u8] = b"declare";
+const DEPLOY_ACCOUNT_PREFIX: &[u8] = b"deploy_account";
+const INVOKE_PREFIX: &[u8] = b"invoke";
+const L1_HANDLER_PREFIX: &[u8] = b"l1_handler";
+const L1_GAS_NAME: &[u8] = b"L1_GAS";
+const L2_GAS_NAME: &[u8] = b"L2_GAS";
+const DATA_AVAILABILITY_MODE_BITS: u32 = 32;
+
+/// Computes the hash of the given transaction, as defined by the Starknet protocol.
+pub fn calculate_tx_hash(tx: &Transaction, chain_id: &ChainId) -> TransactionHash {
+    match tx {
+        Transaction::AccountTransaction(AccountTransaction::Declare(tx)) => {
+            calculate_declare_tx_hash(&tx.tx, chain_id, tx.only_query())
+        }
+        Transaction::AccountTransaction(AccountTransaction::DeployAccount(tx)) => {
+            calculate_deploy_account_tx_hash(&tx.tx, chain_id, tx.contract_address, tx.only_query)
+        }
+        Transaction::AccountTransaction(AccountTransaction::Invoke(tx)) => {
+            calculate_invoke_tx_hash(&tx.tx, chain_id, tx.only_query)
+        }
+        Transaction::L1HandlerTransaction(tx) => calculate_l1_handler_tx_hash(&tx.tx, chain_id),
+    }
+}
+
+/// Verifies that the hash the given transaction was created with is its actual hash.
+pub fn verify_tx_hash(tx: &Transaction, chain_id: &ChainId) -> TransactionExecutionResult<()> {
+    let tx_hash = match tx {
+        Transaction::AccountTransaction(AccountTransaction::Declare(tx)) => tx.tx_hash(),
+        Transaction::AccountTransaction(AccountTransaction::DeployAccount(tx)) => tx.tx_hash,
+        Transaction::AccountTransaction(AccountTransaction::Invoke(tx)) => tx.tx_hash,
+        Transaction::L1HandlerTransaction(tx) => tx.tx_hash,
+    };
+    let expected_tx_hash = calculate_tx_hash(tx, chain_id);
+    if tx_hash != expected_tx_hash {
+        return Err(TransactionExecutionError::InvalidTransactionHash {
+            tx_hash,
+            expected_tx_hash,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn calculate_invoke_tx_hash(
+    tx: &StarknetApiInvokeTransaction,
+    chain_id: &ChainId,
+    only_query: bool,
+) -> TransactionHash {
+    let hash = match tx {
+        StarknetApiInvokeTransaction::V0(tx) => Pedersen::hash_array(&[
+            ascii_as_felt(INVOKE_PREFIX),
+            signed_version(TransactionVersion::ZERO, only_query),
+            *tx.contract_address.0.key(),
+            tx.entry_point_selector.0,
+            Pedersen::hash_array(&tx.calldata.0),
+            Felt::from(tx.max_fee.0),
+            chain_id_as_felt(chain_id),
+        ]),
+        StarknetApiInvokeTransaction::V1(tx) => Pedersen::hash_array(&[
+            ascii_as_felt(INVOKE_PREFIX),
+            signed_version(TransactionVersion::ONE, only_query),
+            *tx.sender_address.0.key(),
+            // Entry point selector; not part of the transaction since version 1.
+            Felt::ZERO,
+            Pedersen::hash_array(&tx.calldata.0),
+            Felt::from(tx.max_fee.0),
+            chain_id_as_felt(chain_id),
+            tx.nonce.0,
+        ]),
+        StarknetApiInvokeTransaction::V3(tx) => {
+            let mut elements = common_v3_elements(
+                INVOKE_PREFIX,
+                signed_version(TransactionVersion::THREE, only_query),
+                tx.sender_address,
+                CommonV3Fields {
+                    tip: tx.tip,
+                    resource_bounds: &tx.resource_bounds,
+                    paymaster_data: &tx.paymaster_data.0,
+                    nonce: tx.nonce.0,
+                    nonce_data_availability_mode: tx.nonce_data_availability_mode,
+                    fee_data_availability_mode: tx.fee_data_availability_mode,
+                },
+                chain_id,
+            );
+            elements.extend([
+                Poseidon::hash_array(&tx.account_deployment_data.0),
+                Poseidon::hash_array(&tx.calldata.0),
+            ]);
+            Poseidon::hash_array(&elements)
+        }
+    };
+
+    TransactionHash(hash)
+}
+
+pub fn calculate_declare_tx_hash(
+    tx: &StarknetApiDeclareTransaction,
+    chain_id: &ChainId,
+    only_query: bool,
+) -> TransactionHash {
+    let hash = match tx {
+        StarknetApiDeclareTransaction::V0(tx) => Pedersen::hash_array(&[
+            ascii_as_felt(DECLARE_PREFIX),
+            signed_version(TransactionVersion::ZERO, only_query),
+            *tx.sender_address.0.key(),
+            Felt::ZERO,
+            Pedersen::hash_array(&[]),
+            Felt::from(tx.max_fee.0),
+            chain_id_as_felt(chain_id),
+            tx.class_hash.0,
+        ]),
+        StarknetApiDeclareTransaction::V1(tx) => Pedersen::hash_array(&[
+            ascii_as_felt(DECLARE_PREFIX),
+            signed_version(TransactionVersion::ONE, only_query),
+            *tx.sender_address.0.key(),
+            Felt::ZERO,
+            Pedersen::hash_array(&[tx.class_hash.0]),
+            Felt::from(tx.max_fee.0),
+            chain_id_as_felt(chain_id),
+            tx.nonce.0,
+        ]),
+        StarknetApiDeclareTransaction::V2(tx) => Pedersen::hash_array(&[
+            ascii_as_felt(DECLARE_PREFIX),
+            signed_version(TransactionVersion::TWO, only_query),
+            *tx.sender_address.0.key(),
+            Felt::ZERO,
+            Pedersen::hash_array(&[tx.class_hash.0]),
+            Felt::from(tx.max_fee.0),
+            chain_id_as_felt(chain_id),
+            tx.nonce.0,
+            tx.compiled_class_hash.0,
+        ]),
+        StarknetApiDeclareTransaction::V3(tx) => {
+            let mut elements = common_v3_elements(
+                DECLARE_PREFIX,
+                signed_version(TransactionVersion::THREE, only_query),
+                tx.sender_address,
+                CommonV3Fields {
+                    tip: tx.tip,
+                    resource_bounds: &tx.resource_bounds,
+                    paymaster_data: &tx.paymaster_data.0,
+                    nonce: tx.nonce.0,
+                    nonce_data_availability_mode: tx.nonce_data_availability_mode,
+                    fee_data_availability_mode: tx.fee_data_availability_mode,
+                },
+                chain_id,
+            );
+            elements.extend([
+                Poseidon::hash_array(&tx.account_deployment_data.0),
+                tx.class_hash.0,
+                tx.compiled_class_hash.0,
+            ]);
+            Poseidon::hash_array(&elements)
+        }
+    };
+
+    TransactionHash(hash)
+}
+
+/// Note: the address of the deployed contract is not part of the transaction; it is given
+/// explicitly.
+pub fn calculate_deploy_account_tx_hash(
+    tx: &StarknetApiDeployAccountTransaction,
+    chain_id: &ChainId,
+    contract_address: ContractAddress,
+    only_query: bool,
+) -> TransactionHash {
+    let hash = match tx {
+        StarknetApiDeployAccountTransaction::V1(tx) => {
+            let mut constructor_elements = vec![tx.class_hash.0, tx.contract_address_salt.0];
+            constructor_elements.extend(tx.constructor_calldata.0.iter());
+            Pedersen::hash_array(&[
+                ascii_as_felt(DEPLOY_ACCOUNT_PREFIX),
+                signed_version(TransactionVersion::ONE, only_query),
+                *contract_address.0.key(),
+                Felt::ZERO,
+                Pedersen::hash_array(&constructor_elements),
+                Felt::from(tx.max_fee.0),
+                chain_id_as_felt(chain_id),
+                tx.nonce.0,
+            ])
+        }
+        StarknetApiDeployAccountTransaction::V3(tx) => {
+            let mut elements = common_v3_elements(
+                DEPLOY_ACCOUNT_PREFIX,
+                signed_version(TransactionVersion::THREE, only_query),
+                contract_address,
+                CommonV3Fields {
+                    tip: tx.tip,
+                    resource_bounds: &tx.resource_bounds,
+                    paymaster_data: &tx.paymaster_data.0,
+                    nonce: tx.nonce.0,
+                    nonce_data_availability_mode: tx.nonce_data_availability_mode,
+                    fee_data_availability_mode: tx.fee_data_availability_mode,
+                },
+                chain_id,
+            );
+            elements.extend([
+                Poseidon::hash_array(&tx.constructor_calldata.0),
+                tx.class_hash.0,
+                tx.contract_address_salt.0,
+            ]);
+            Poseidon::hash_array(&elements)
+        }
+    };
+
+    TransactionHash(hash)
+}
+
+pub fn calculate_l1_handler_tx_hash(
+    tx: &StarknetApiL1HandlerTransaction,
+    chain_id: &ChainId,
+) -> TransactionHash {
+    TransactionHash(Pedersen::hash_array(&[
+        ascii_as_felt(L1_HANDLER_PREFIX),
+        tx.version.0,
+        *tx.contract_address.0.key(),
+        tx.entry_point_selector.0,
+        Pedersen::hash_array(&tx.calldata.0),
+        // No max fee in L1 handler transactions.
+        Felt::ZERO,
+        chain_id_as_felt(chain_id),
+        tx.nonce.0,
+    ]))
+}
+
+/// The fields shared by all V3 transactions that take part in their hash.
+struct CommonV3Fields<'a> {
+    tip: Tip,
+    resource_bounds: &'a ResourceBoundsMapping,
+    paymaster_data: &'a [Felt],
+    nonce: Felt,
+    nonce_data_availability_mode: DataAvailabilityMode,
+    fee_data_availability_mode: DataAvailabilityMode,
+}
+
+fn common_v3_elements(
+    prefix: &[u8],
+    version: Felt,
+    sender_address: ContractAddress,
+    fields: CommonV3Fields<'_>,
+    chain_id: &ChainId,
+) -> Vec<Felt> {
+    let resource_bound = |resource: Resource, resource_name: &[u8]| {
+        let bounds = fields.resource_bounds.0.get(&resource).copied().unwrap_or_default();
+        concat_resource_bounds(&bounds, resource_name)
+    };
+    let tip_and_resource_bounds_hash = Poseidon::hash_array(&[
+        Felt::from(fields.tip.0),
+        resource_bound(Resource::L1Gas, L1_GAS_NAME),
+        resource_bound(Resource::L2Gas, L2_GAS_NAME),
+    ]);
+    let nonce_data_availability_mode =
+        data_availability_mode_as_u64(fields.nonce_data_availability_mode);
+    let fee_data_availability_mode =
+        data_availability_mode_as_u64(fields.fee_data_availability_mode);
+    let data_availability_modes =
+        (nonce_data_availability_mode << DATA_AVAILABILITY_MODE_BITS) + fee_data_availability_mode;
+
+    vec![
+        ascii_as_felt(prefix),
+        version,
+        *sender_address.0.key(),
+        tip_and_resource_bounds_hash,
+        Poseidon::hash_array(fields.paymaster_data),
+        chain_id_as_felt(chain_id),
+        fields.nonce,
+        Felt::from(data_availability_modes),
+    ]
+}
+
+/// Packs the bounds of a resource into a single felt: the resource name (60 bits), the max
+/// amount (64 bits) and the max price per unit (128 bits).
+fn concat_resource_bounds(bounds: &ResourceBounds, resource_name: &[u8]) -> Felt {
+    let mut bytes = [0_u8; 32];
+    bytes[8 - resource_name.len()..8].copy_from_slice(resource_name);
+    bytes[8..16].copy_from_slice(&bounds.max_amount.to_be_bytes());
+    bytes[16..].copy_from_slice(&bounds.max_price_per_unit.to_be_bytes());
+    Felt::from_bytes_be(&bytes)
+}
+
+fn data_availability_mode_as_u64(mode: DataAvailabilityMode) -> u64 {
+    match mode {
+        DataAvailabilityMode::L1 => 0,
+        DataAvailabilityMode::L2 => 1,
+    }
+}
+
+/// Returns the version as it is signed: with the query bit set, for transactions that are only
+/// meant for querying (e.g., fee estimation).
+fn signed_version(version: TransactionVersion, only_query: bool) -> Felt {
+    match only_query {
+        true => version.0 + Felt::TWO.pow(constants::QUERY_VERSION_BASE_BIT),
+        false => version.0,
+    }
+}
+
+fn ascii_as_felt(string: &[u8]) -> Felt {
+    Felt::from_bytes_be_slice(string)
+}
+
+fn chain_id_as_felt(chain_id: &ChainId) -> Felt {
+    ascii_as_felt(chain_id.to_string().as_bytes())
+}
@@ -0,0 +1,121 @@
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use serde::Deserialize;
+use starknet_api::core::{calculate_contract_address, ChainId, ContractAddress};
+use starknet_api::transaction::{
+    ResourceBounds, Transaction as StarknetApiTransaction, TransactionHash, TransactionVersion,
+};
+use starknet_types_core::felt::Felt;
+
+use crate::invoke_tx_args;
+use crate::test_utils::create_calldata;
+use crate::transaction::account_transaction::AccountTransaction;
+use crate::transaction::errors::TransactionExecutionError;
+use crate::transaction::test_utils::account_invoke_tx;
+use crate::transaction::transaction_execution::Transaction;
+use crate::transaction::transaction_hash::{
+    calculate_declare_tx_hash, calculate_deploy_account_tx_hash, calculate_invoke_tx_hash,
+    calculate_l1_handler_tx_hash, calculate_tx_hash, concat_resource_bounds, verify_tx_hash,
+    L1_GAS_NAME,
+};
+
+/// Mainnet transactions of each supported type and version, with their hashes.
+const TRANSACTION_HASH_JSON: &str = include_str!("../../resources/test_data/transaction_hash.json");
+
+#[derive(Deserialize)]
+struct TransactionHashTestData {
+    transaction: StarknetApiTransaction,
+    transaction_hash: TransactionHash,
+    only_query_transaction_hash: TransactionHash,
+    chain_id: ChainId,
+}
+
+fn invoke_tx(version: TransactionVersion, only_query: bool) -> Transaction {
+    Transaction::AccountTransaction(account_invoke_tx(invoke_tx_args! {
+        calldata: create_calldata(Default::default(), "foo", &[Felt::ONE]),
+        version,
+        only_query,
+    }))
+}
+
+#[rstest]
+fn test_tx_hash_depends_on_query_bit_and_chain_id(
+    #[values(TransactionVersion::ZERO, TransactionVersion::ONE, TransactionVersion::THREE)]
+    version: TransactionVersion,
+) {
+    let tx_hash = calculate_tx_hash(&invoke_tx(version, false), &ChainId::Mainnet);
+
+    assert_ne!(calculate_tx_hash(&invoke_tx(version, true), &ChainId::Mainnet), tx_hash);
+    assert_ne!(calculate_tx_hash(&invoke_tx(version, false), &ChainId::Sepolia), tx_hash);
+}
+
+#[test]
+fn test_concat_resource_bounds() {
+    let bounds = ResourceBounds { max_amount: 7, max_price_per_unit: 93 };
+    let expected = Felt::from_bytes_be_slice(L1_GAS_NAME) * Felt::TWO.pow(192_u32)
+        + Felt::from(7_u8) * Felt::TWO.pow(128_u32)
+        + Felt::from(93_u8);
+
+    assert_eq!(concat_resource_bounds(&bounds, L1_GAS_NAME), expected);
+}
+
+#[test]
+fn test_verify_tx_hash() {
+    let chain_id = ChainId::Mainnet;
+    let mut tx = invoke_tx(TransactionVersion::THREE, false);
+    let expected_tx_hash = calculate_tx_hash(&tx, &chain_id);
+
+    assert_matches!(
+        verify_tx_hash(&tx, &chain_id).unwrap_err(),
+        TransactionExecutionError::InvalidTransactionHash { tx_hash, expected_tx_hash: expected }
+        if tx_hash == TransactionHash::default() && expected == expected_tx_hash
+    );
+
+    let Transaction::AccountTransaction(AccountTransaction::Invoke(invoke_tx)) = &mut tx else {
+        panic!("Expected an invoke transaction.");
+    };
+    invoke_tx.tx_hash = expected_tx_hash;
+    verify_tx_hash(&tx, &chain_id).unwrap();
+}
+
+#[test]
+fn test_tx_hash_known_answers() {
+    let test_data: Vec<TransactionHashTestData> =
+        serde_json::from_str(TRANSACTION_HASH_JSON).unwrap();
+
+    for TransactionHashTestData {
+        transaction,
+        transaction_hash,
+        only_query_transaction_hash,
+        chain_id,
+    } in test_data
+    {
+        let calculate_hash = |only_query| match &transaction {
+            StarknetApiTransaction::Declare(tx) => {
+                calculate_declare_tx_hash(tx, &chain_id, only_query)
+            }
+            StarknetApiTransaction::DeployAccount(tx) => {
+                let contract_address = calculate_contract_address(
+                    tx.contract_address_salt(),
+                    tx.class_hash(),
+                    &tx.constructor_calldata(),
+                    ContractAddress::default(),
+                )
+                .unwrap();
+                calculate_deploy_account_tx_hash(tx, &chain_id, contract_address, only_query)
+            }
+            StarknetApiTransaction::Invoke(tx) => {
+                calculate_invoke_tx_hash(tx, &chain_id, only_query)
+            }
+            StarknetApiTransaction::L1Handler(tx) => calculate_l1_handler_tx_hash(tx, &chain_id),
+            StarknetApiTransaction::Deploy(_) => panic!("Deploy transactions are not supported."),
+        };
+
+        assert_eq!(calculate_hash(false), transaction_hash);
+        // L1 handler transactions cannot be sent as queries.
+        if !matches!(transaction, StarknetApiTransaction::L1Handler(_)) {
+            assert_eq!(calculate_hash(true), only_query_transaction_hash);
+        }
+    }
+}
@@ -4,6 +4,7 @@ use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use starknet_api::calldata;
 use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
 use starknet_api::deprecated_contract_class::EntryPointType;
+use starknet_api::state::ContractClass as SierraContractClass;
 use starknet_api::transaction::{
     AccountDeploymentData, Calldata, ContractAddressSalt, DeclareTransactionV2,
     DeclareTransactionV3, Fee, TransactionHash, TransactionSignature, TransactionVersion,
@@ -13,6 +14,7 @@ use starknet_types_core::felt::Felt;
 use crate::abi::abi_utils::selector_from_name;
 use crate::context::{BlockContext, TransactionContext};
 use crate::execution::call_info::CallInfo;
+use crate::execution::class_hash::{compute_compiled_class_hash, DeclaredClass};
 use crate::execution::contract_class::{ClassInfo, ContractClass};
 use crate::execution::entry_point::{
     CallEntryPoint, CallType, ConstructorContext, EntryPointExecutionContext,
@@ -28,6 +30,7 @@ use crate::transaction::objects::{
     TransactionExecutionInfo, TransactionExecutionResult, TransactionInfo, TransactionInfoCreator,
 };
 use crate::transaction::transaction_utils::{update_remaining_gas, verify_contract_class_version};
+use crate::versioned_constants::VersionedConstants;
 
 #[cfg(test)]
 #[path = "transactions_test.rs"]
@@ -149,6 +152,42 @@ impl DeclareTransaction {
         Self::create(declare_tx, tx_hash, class_info, true)
     }
 
+    fn create_from_sierra(
+        declare_tx: starknet_api::transaction::DeclareTransaction,
+        tx_hash: TransactionHash,
+        sierra_class: &SierraContractClass,
+        versioned_constants: &VersionedConstants,
+        only_query: bool,
+    ) -> TransactionExecutionResult<Self> {
+        let class_info = ClassInfo::try_from_sierra(
+            sierra_class,
+            &versioned_constants.sierra_compilation_limits,
+        )?;
+        let declare_tx = Self::create(declare_tx, tx_hash, class_info, only_query)?;
+        declare_tx.verify_compiled_class_hash()?;
+        Ok(declare_tx)
+    }
+
+    /// Creates a declare transaction of the given Sierra class, compiling it; the compiled class
+    /// hash of the transaction is verified against the compiled class.
+    pub fn new_from_sierra(
+        declare_tx: starknet_api::transaction::DeclareTransaction,
+        tx_hash: TransactionHash,
+        sierra_class: &SierraContractClass,
+        versioned_constants: &VersionedConstants,
+    ) -> TransactionExecutionResult<Self> {
+        Self::create_from_sierra(declare_tx, tx_hash, sierra_class, versioned_constants, false)
+    }
+
+    pub fn new_from_sierra_for_query(
+        declare_tx: starknet_api::transaction::DeclareTransaction,
+        tx_hash: TransactionHash,
+        sierra_class: &SierraContractClass,
+        versioned_constants: &VersionedConstants,
+    ) -> TransactionExecutionResult<Self> {
+        Self::create_from_sierra(declare_tx, tx_hash, sierra_class, versioned_constants, true)
+    }
+
     implement_inner_tx_getter_calls!((class_hash, ClassHash), (signature, TransactionSignature));
 
     pub fn tx(&self) -> &starknet_api::transaction::DeclareTransaction {
@@ -167,6 +206,66 @@ impl DeclareTransaction {
         self.only_query
     }
 
+    /// Verifies that the class hash of the transaction is the hash of the given class (the class
+    /// as declared, e.g., the Sierra class) and that its compiled class hash, if any, is the hash
+    /// of the compiled class.
+    pub fn verify_class_hashes(
+        &self,
+        declared_class: &DeclaredClass,
+    ) -> TransactionExecutionResult<()> {
+        let contract_class = self.contract_class();
+        let cairo_version = match contract_class {
+            ContractClass::V0(_) => 0,
+            ContractClass::V1(_) => 1,
+        };
+        if declared_class.cairo_version() != cairo_version {
+            return Err(TransactionExecutionError::ContractClassVersionMismatch {
+                declare_version: self.tx.version(),
+                cairo_version,
+            });
+        }
+
+        let class_hash = self.class_hash();
+        let expected_class_hash = declared_class.class_hash();
+        if class_hash != expected_class_hash {
+            return Err(TransactionExecutionError::InvalidClassHash {
+                class_hash,
+                expected_class_hash,
+            });
+        }
+
+        self.verify_compiled_class_hash()
+    }
+
+    /// Verifies that the compiled class hash of the transaction, if any, is the hash of the
+    /// compiled class.
+    pub fn verify_compiled_class_hash(&self) -> TransactionExecutionResult<()> {
+        let compiled_class_hash = match &self.tx {
+            starknet_api::transaction::DeclareTransaction::V0(_)
+            | starknet_api::transaction::DeclareTransaction::V1(_) => return Ok(()),
+            starknet_api::transaction::DeclareTransaction::V2(DeclareTransactionV2 {
+                compiled_class_hash,
+                ..
+            })
+            | starknet_api::transaction::DeclareTransaction::V3(DeclareTransactionV3 {
+                compiled_class_hash,
+                ..
+            }) => *compiled_class_hash,
+        };
+        let ContractClass::V1(contract_class) = self.contract_class() else {
+            unreachable!("Declare transactions of version 2 and above declare Cairo 1 classes.");
+        };
+        let expected_compiled_class_hash = compute_compiled_class_hash(&contract_class)?;
+        if compiled_class_hash != expected_compiled_class_hash {
+            return Err(TransactionExecutionError::InvalidCompiledClassHash {
+                compiled_class_hash,
+                expected_compiled_class_hash,
+            });
+        }
+
+        Ok(())
+    }
+
     fn try_declare<S: State>(
         &self,
         state: &mut S,
@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use assert_matches::assert_matches;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use num_bigint::BigUint;
@@ -9,12 +10,14 @@ use num_traits::Pow;
 use once_cell::sync::Lazy;
 use pretty_assertions::assert_eq;
 use rstest::{fixture, rstest};
-use starknet_api::core::{ChainId, ClassHash, ContractAddress, EthAddress, Nonce, PatriciaKey};
+use starknet_api::core::{
+    ChainId, ClassHash, CompiledClassHash, ContractAddress, EthAddress, Nonce, PatriciaKey,
+};
 use starknet_api::deprecated_contract_class::EntryPointType;
-use starknet_api::state::StorageKey;
+use starknet_api::state::{ContractClass as SierraContractClass, StorageKey};
 use starknet_api::transaction::{
-    Calldata, EventContent, EventData, EventKey, Fee, L2ToL1Payload, ResourceBoundsMapping,
-    TransactionSignature, TransactionVersion,
+    Calldata, DeclareTransactionV2, EventContent, EventData, EventKey, Fee, L2ToL1Payload, Resource, ResourceBounds,
+    ResourceBoundsMapping, TransactionHash, TransactionSignature, TransactionVersion,
 };
 use starknet_api::{calldata, class_hash, contract_address, felt, patricia_key};
 use starknet_types_core::felt::Felt;
@@ -36,7 +39,8 @@ use crate::execution::syscalls::SyscallSelector;
 use crate::fee::actual_cost::TransactionReceipt;
 use crate::fee::fee_utils::balance_to_big_uint;
 use crate::fee::gas_usage::{
-    estimate_minimal_gas_vector, get_da_gas_cost, get_onchain_data_segment_length,
+    compute_discounted_gas_from_gas_vector, estimate_minimal_gas_vector, get_da_gas_cost,
+    get_onchain_data_segment_length,
 };
 use crate::state::cached_state::{CachedState, StateChangesCount, TransactionalState};
 use crate::state::errors::StateError;
@@ -72,7 +76,9 @@ use crate::transaction::test_utils::{
     GET_SEQUENCER_ADDRESS, INVALID, VALID,
 };
 use crate::transaction::transaction_types::TransactionType;
-use crate::transaction::transactions::{ExecutableTransaction, L1HandlerTransaction};
+use crate::transaction::transactions::{
+    DeclareTransaction, ExecutableTransaction, L1HandlerTransaction,
+};
 use crate::versioned_constants::VersionedConstants;
 use crate::{
     check_transaction_execution_error_for_custom_hint,
@@ -468,11 +474,14 @@ fn test_invoke_tx(
         fee_transfer_call_info: expected_fee_transfer_call_info,
         transaction_receipt: TransactionReceipt {
             fee: expected_actual_fee,
+            tip: Fee(0),
             da_gas,
             resources: expected_actual_resources,
             gas: total_gas,
         },
         revert_error: None,
+        reverted_call_info: None,
+        bouncer_weights: None,
     };
 
     // Test execution info result.
@@ -922,6 +931,79 @@ fn test_insufficient_resource_bounds(
     );
 }
 
+#[rstest]
+fn test_insufficient_l1_data_gas_and_l2_gas_bounds() {
+    let mut block_context = BlockContext::create_for_account_testing_with_kzg(true);
+    block_context.versioned_constants.enable_l2_gas = true;
+    let block_context = &block_context;
+    let account_contract = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let state = &mut test_state(
+        &block_context.chain_info,
+        BALANCE,
+        &[(account_contract, 1), (test_contract, 1)],
+    );
+    let valid_invoke_tx_args = invoke_tx_args! {
+        sender_address: account_contract.get_instance_address(0),
+        calldata: create_trivial_calldata(test_contract.get_instance_address(0)),
+    };
+    let gas_prices = &block_context.block_info.gas_prices;
+    let l1_gas_price = u128::from(gas_prices.strk_l1_gas_price);
+    let l2_gas_price = u128::from(gas_prices.strk_l2_gas_price);
+    let resource_bounds = |l1_gas_amount: u128, l2_gas_price: u128| {
+        ResourceBoundsMapping::try_from(vec![
+            (
+                Resource::L1Gas,
+                ResourceBounds {
+                    max_amount: l1_gas_amount.try_into().unwrap(),
+                    max_price_per_unit: l1_gas_price,
+                },
+            ),
+            (Resource::L2Gas, ResourceBounds { max_amount: 0, max_price_per_unit: l2_gas_price }),
+        ])
+        .unwrap()
+    };
+
+    let tx = &account_invoke_tx(valid_invoke_tx_args.clone());
+    let minimal_gas_vector = estimate_minimal_gas_vector(block_context, tx).unwrap();
+    assert!(minimal_gas_vector.l1_data_gas > 0);
+    let minimal_l1_gas = compute_discounted_gas_from_gas_vector(
+        &minimal_gas_vector,
+        &block_context.to_tx_context(tx),
+    );
+
+    // The L1 gas bound covers the L1 gas, but not the L1 data gas.
+    let invalid_tx = account_invoke_tx(invoke_tx_args! {
+        resource_bounds: resource_bounds(minimal_gas_vector.l1_gas, l2_gas_price),
+        ..valid_invoke_tx_args.clone()
+    });
+    let execution_error = invalid_tx.execute(state, block_context, true, true).unwrap_err();
+    assert_matches!(
+        execution_error,
+        TransactionExecutionError::TransactionPreValidationError(
+            TransactionPreValidationError::TransactionFeeError(
+                TransactionFeeError::MaxL1DataGasAmountTooLow {
+                    max_l1_data_gas_amount, minimal_l1_data_gas_amount, ..
+                }))
+        if max_l1_data_gas_amount == 0
+            && minimal_l1_data_gas_amount == minimal_gas_vector.l1_data_gas
+    );
+
+    // Max L2 gas price too low.
+    let invalid_tx = account_invoke_tx(invoke_tx_args! {
+        resource_bounds: resource_bounds(minimal_l1_gas, l2_gas_price - 1),
+        ..valid_invoke_tx_args
+    });
+    let execution_error = invalid_tx.execute(state, block_context, true, true).unwrap_err();
+    assert_matches!(
+        execution_error,
+        TransactionExecutionError::TransactionPreValidationError(
+            TransactionPreValidationError::TransactionFeeError(
+                TransactionFeeError::MaxL2GasPriceTooLow { max_l2_gas_price, actual_l2_gas_price }))
+        if max_l2_gas_price == l2_gas_price - 1 && actual_l2_gas_price == l2_gas_price
+    );
+}
+
 // TODO(Aner, 21/01/24) modify test for 4844.
 #[rstest]
 fn test_actual_fee_gt_resource_bounds(
@@ -954,7 +1036,7 @@ fn test_actual_fee_gt_resource_bounds(
     );
 
     let execution_result = invalid_tx.execute(state, block_context, true, true).unwrap();
-    let execution_error = execution_result.revert_error.unwrap();
+    let execution_error = execution_result.revert_error.unwrap().to_string();
     // Test error.
     assert!(execution_error.starts_with("Insufficient max L1 gas:"));
     // Test that fee was charged.
@@ -1187,11 +1269,14 @@ fn test_declare_tx(
         fee_transfer_call_info: expected_fee_transfer_call_info,
         transaction_receipt: TransactionReceipt {
             fee: expected_actual_fee,
+            tip: Fee(0),
             da_gas,
             resources: expected_actual_resources,
             gas: expected_total_gas,
         },
         revert_error: None,
+        reverted_call_info: None,
+        bouncer_weights: None,
     };
 
     // Test execution info result.
@@ -1360,11 +1445,14 @@ fn test_deploy_account_tx(
         fee_transfer_call_info: expected_fee_transfer_call_info,
         transaction_receipt: TransactionReceipt {
             fee: expected_actual_fee,
+            tip: Fee(0),
             da_gas,
             resources: actual_resources,
             gas: expected_total_gas,
         },
         revert_error: None,
+        reverted_call_info: None,
+        bouncer_weights: None,
     };
 
     // Test execution info result.
@@ -1815,7 +1903,7 @@ fn test_l1_handler(#[values(false, true)] use_kzg_da: bool) {
     // TODO(Nimrod, 1/5/2024): Change these hard coded values to match to the transaction resources
     // (currently matches only starknet resources).
     let expected_gas = match use_kzg_da {
-        true => GasVector { l1_gas: 16023, l1_data_gas: 128 },
+        true => GasVector { l1_gas: 16023, l1_data_gas: 128, l2_gas: 0 },
         false => GasVector::from_l1_gas(17675),
     };
     let expected_da_gas = match use_kzg_da {
@@ -1880,11 +1968,14 @@ fn test_l1_handler(#[values(false, true)] use_kzg_da: bool) {
         fee_transfer_call_info: None,
         transaction_receipt: TransactionReceipt {
             fee: Fee(0),
+            tip: Fee(0),
             da_gas: expected_da_gas,
             resources: expected_tx_resources,
             gas: total_gas,
         },
         revert_error: None,
+        reverted_call_info: None,
+        bouncer_weights: None,
     };
 
     // Check the actual returned execution info.
@@ -1945,6 +2036,7 @@ fn test_execute_tx_with_invalid_transaction_version(
         execution_info
             .revert_error
             .unwrap()
+            .to_string()
             .contains(format!("ASSERT_EQ instruction failed: {} != 3.", invalid_version).as_str())
     );
 }
@@ -2038,7 +2130,7 @@ fn test_emit_event_exceeds_limit(
     let execution_info = account_tx.execute(state, block_context, true, true).unwrap();
     match &expected_error {
         Some(expected_error) => {
-            let error_string = execution_info.revert_error.unwrap();
+            let error_string = execution_info.revert_error.unwrap().to_string();
             assert!(error_string.contains(&format!("{}", expected_error)));
         }
         None => {
@@ -2052,3 +2144,45 @@ fn test_balance_print() {
     let int = balance_to_big_uint(&Felt::from(16_u64), &Felt::from(1_u64));
     assert!(format!("{}", int) == (BigUint::from(u128::MAX) + BigUint::from(17_u128)).to_string());
 }
+
+#[rstest]
+fn test_declare_from_sierra_compiled_class_hash(
+    #[values(true, false)] is_valid_compiled_class_hash: bool,
+) {
+    let sierra_class: SierraContractClass =
+        serde_json::from_str(include_str!("../../resources/test_data/hello_starknet.sierra.json"))
+            .unwrap();
+    let casm_contract_class: CasmContractClass =
+        serde_json::from_str(include_str!("../../resources/test_data/hello_starknet.casm.json"))
+            .unwrap();
+    let expected_compiled_class_hash = CompiledClassHash(casm_contract_class.compiled_class_hash());
+    let compiled_class_hash = if is_valid_compiled_class_hash {
+        expected_compiled_class_hash
+    } else {
+        CompiledClassHash(expected_compiled_class_hash.0 + Felt::ONE)
+    };
+    let declare_tx = starknet_api::transaction::DeclareTransaction::V2(DeclareTransactionV2 {
+        compiled_class_hash,
+        ..Default::default()
+    });
+
+    let result = DeclareTransaction::new_from_sierra(
+        declare_tx,
+        TransactionHash::default(),
+        &sierra_class,
+        VersionedConstants::latest_constants(),
+    );
+    if is_valid_compiled_class_hash {
+        result.unwrap();
+    } else {
+        assert_matches!(
+            result.unwrap_err(),
+            TransactionExecutionError::InvalidCompiledClassHash {
+                compiled_class_hash: actual_compiled_class_hash,
+                expected_compiled_class_hash: actual_expected_compiled_class_hash,
+            }
+            if actual_compiled_class_hash == compiled_class_hash
+                && actual_expected_compiled_class_hash == expected_compiled_class_hash
+        );
+    }
+}
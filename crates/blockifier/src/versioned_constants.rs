@@ -97,6 +97,15 @@ pub struct VersionedConstants {
     // Transactions settings.
     #[serde(default)]
     pub disable_cairo0_redeclaration: bool,
+    // If true, the Sierra gas consumed by transactions is charged as L2 gas, and bounded by the
+    // L2 gas resource bound of V3 transactions.
+    #[serde(default)]
+    pub enable_l2_gas: bool,
+    #[serde(default = "SierraCompilationLimits::max")]
+    pub sierra_compilation_limits: SierraCompilationLimits,
+    // The parameters of the gas price update rule between consecutive blocks.
+    #[serde(default)]
+    pub fee_market: FeeMarketConstants,
 
     // Cairo OS constants.
     // Note: if loaded from a json file, there are some assumptions made on its structure.
@@ -290,6 +299,69 @@ impl EventLimits {
     }
 }
 
+/// Limits on the Sierra classes that may be compiled as part of a declare transaction.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+pub struct SierraCompilationLimits {
+    pub min_sierra_version: SierraVersion,
+    pub max_sierra_version: SierraVersion,
+    pub max_bytecode_size: usize,
+}
+
+impl SierraCompilationLimits {
+    fn max() -> Self {
+        Self {
+            min_sierra_version: SierraVersion::default(),
+            max_sierra_version: SierraVersion { major: u64::MAX, minor: u64::MAX, patch: u64::MAX },
+            max_bytecode_size: usize::MAX,
+        }
+    }
+}
+
+/// Parameters of the EIP-1559-style gas price update rule: the target usage of each block resource
+/// is its block capacity divided by the elasticity multiplier, and a block that uses the whole
+/// capacity raises the price by a fraction of `(elasticity_multiplier - 1) /
+/// max_price_change_denominator`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub struct FeeMarketConstants {
+    pub elasticity_multiplier: u128,
+    pub max_price_change_denominator: u128,
+    pub min_gas_price: u128,
+}
+
+impl Default for FeeMarketConstants {
+    fn default() -> Self {
+        Self { elasticity_multiplier: 2, max_price_change_denominator: 8, min_gas_price: 1 }
+    }
+}
+
+/// The version of a Sierra program, in the form "major.minor.patch".
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct SierraVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl std::fmt::Display for SierraVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl<'de> Deserialize<'de> for SierraVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw_version = String::deserialize(deserializer)?;
+        let parsed_version: Option<Vec<u64>> =
+            raw_version.split('.').map(|part| part.parse().ok()).collect();
+        match parsed_version.as_deref() {
+            Some(&[major, minor, patch]) => Ok(Self { major, minor, patch }),
+            _ => Err(DeserializationError::custom(format!(
+                "Invalid Sierra version {raw_version:?}; expected \"major.minor.patch\"."
+            ))),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 // Serde trick for adding validations via a customr deserializer, without forgoing the derive.
 // See: https://github.com/serde-rs/serde/issues/1220.
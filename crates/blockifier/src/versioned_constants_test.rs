@@ -124,6 +124,7 @@ fn test_default_values() {
 
     assert_eq!(versioned_constants.tx_event_limits, EventLimits::max());
     assert_eq!(versioned_constants.l2_resource_gas_costs, L2ResourceGasCosts::default());
+    assert_eq!(versioned_constants.sierra_compilation_limits, SierraCompilationLimits::max());
 
     // Calldata factor was initialized as 0, and did not affect the expected result, even if
     // calldata length is nonzero.
@@ -233,3 +234,17 @@ fn test_old_json_parsing() {
 fn test_all_jsons_in_enum() {
     assert_eq!(StarknetVersion::iter().count(), all_jsons_in_dir().count());
 }
+
+#[test]
+fn test_sierra_version_parsing() {
+    let limits: SierraCompilationLimits = serde_json::from_str(
+        r#"{"min_sierra_version": "1.1.0", "max_sierra_version": "1.6.0", "max_bytecode_size": 7}"#,
+    )
+    .unwrap();
+    assert_eq!(limits.min_sierra_version, SierraVersion { major: 1, minor: 1, patch: 0 });
+    assert_eq!(limits.max_sierra_version.to_string(), "1.6.0");
+    assert!(limits.min_sierra_version < limits.max_sierra_version);
+
+    let error = serde_json::from_str::<SierraVersion>(r#""1.6""#).unwrap_err();
+    assert!(error.to_string().contains("Invalid Sierra version"));
+}
@@ -91,8 +91,12 @@ pub enum NativeBlockifierInputError {
     PyFeltParseError(#[from] FromStrError),
     #[error(transparent)]
     StarknetApiError(#[from] StarknetApiError),
+    #[error("Unknown block filling policy: {0}.")]
+    UnknownBlockFillingPolicy(String),
     #[error("Unknown builtin: {0}.")]
     UnknownBuiltin(String),
+    #[error("Unknown transaction ordering: {0}.")]
+    UnknownTransactionOrdering(String),
     #[error("Contract class of version {version} is unsupported.")]
     UnsupportedContractClassVersion { version: usize },
     #[error("Transaction of type {tx_type:?} is unsupported in version {version}.")]
@@ -111,6 +115,10 @@ pub enum InvalidNativeBlockifierInputError {
     InvalidDataGasPriceWei(u128),
     #[error("Invalid Fri data gas price: {0}.")]
     InvalidDataGasPriceFri(u128),
+    #[error("Invalid Wei L2 gas price: {0}.")]
+    InvalidL2GasPriceWei(u128),
+    #[error("Invalid Fri L2 gas price: {0}.")]
+    InvalidL2GasPriceFri(u128),
 }
 
 create_exception!(native_blockifier, UndeclaredClassHashError, PyException);
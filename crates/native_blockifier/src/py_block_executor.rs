@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use blockifier::blockifier::block::pre_process_block;
 use blockifier::blockifier::config::TransactionExecutorConfig;
 use blockifier::blockifier::transaction_executor::{TransactionExecutor, TransactionExecutorError};
-use blockifier::bouncer::BouncerConfig;
+use blockifier::bouncer::{BouncerConfig, BouncerWeights};
 use blockifier::context::{BlockContext, ChainInfo, FeeTokenAddresses};
 use blockifier::execution::call_info::CallInfo;
 use blockifier::state::cached_state::CachedState;
@@ -21,7 +21,10 @@ use starknet_api::transaction::Fee;
 use starknet_types_core::felt::Felt;
 
 use crate::errors::{NativeBlockifierError, NativeBlockifierResult};
-use crate::py_objects::{PyBouncerConfig, PyConcurrencyConfig, PyVersionedConstantsOverrides};
+use crate::py_objects::{
+    PyBouncerConfig, PyConcurrencyConfig, PyTransactionExecutorConfig,
+    PyVersionedConstantsOverrides,
+};
 use crate::py_state_diff::{PyBlockInfo, PyStateDiff};
 use crate::py_transaction::{py_tx, PyClassInfo, PY_TX_PARSING_ERR};
 use crate::py_utils::{int_to_chain_id, into_block_number_hash_pair, PyFelt};
@@ -45,10 +48,12 @@ pub(crate) struct ThinTransactionExecutionInfo {
     pub execute_call_info: Option<CallInfo>,
     pub fee_transfer_call_info: Option<CallInfo>,
     pub actual_fee: Fee,
+    pub tip: Fee,
     pub da_gas: GasVector,
     pub actual_resources: ResourcesMapping,
     pub revert_error: Option<String>,
     pub total_gas: GasVector,
+    pub bouncer_weights: Option<BouncerWeights>,
 }
 
 impl ThinTransactionExecutionInfo {
@@ -61,14 +66,16 @@ impl ThinTransactionExecutionInfo {
             execute_call_info: tx_execution_info.execute_call_info,
             fee_transfer_call_info: tx_execution_info.fee_transfer_call_info,
             actual_fee: tx_execution_info.transaction_receipt.fee,
+            tip: tx_execution_info.transaction_receipt.tip,
             da_gas: tx_execution_info.transaction_receipt.da_gas,
             actual_resources: tx_execution_info.transaction_receipt.resources.to_resources_mapping(
                 block_context.versioned_constants(),
                 block_context.block_info().use_kzg_da,
                 true,
             ),
-            revert_error: tx_execution_info.revert_error,
+            revert_error: tx_execution_info.revert_error.map(|error| error.to_string()),
             total_gas: tx_execution_info.transaction_receipt.gas,
+            bouncer_weights: tx_execution_info.bouncer_weights,
         }
     }
     pub fn serialize(self) -> RawTransactionExecutionResult {
@@ -91,10 +98,10 @@ pub struct PyBlockExecutor {
 #[pymethods]
 impl PyBlockExecutor {
     #[new]
-    #[pyo3(signature = (bouncer_config, concurrency_config, general_config, global_contract_cache_size, target_storage_config, py_versioned_constants_overrides))]
+    #[pyo3(signature = (bouncer_config, tx_executor_config, general_config, global_contract_cache_size, target_storage_config, py_versioned_constants_overrides))]
     pub fn create(
         bouncer_config: PyBouncerConfig,
-        concurrency_config: PyConcurrencyConfig,
+        tx_executor_config: PyTransactionExecutorConfig,
         general_config: PyGeneralConfig,
         global_contract_cache_size: usize,
         target_storage_config: StorageConfig,
@@ -109,9 +116,9 @@ impl PyBlockExecutor {
 
         Self {
             bouncer_config: bouncer_config.try_into().expect("Failed to parse bouncer config."),
-            tx_executor_config: TransactionExecutorConfig {
-                concurrency_config: concurrency_config.into(),
-            },
+            tx_executor_config: tx_executor_config
+                .try_into()
+                .expect("Failed to parse transaction executor config."),
             chain_info: general_config.starknet_os_config.into_chain_info(),
             versioned_constants,
             tx_executor: None,
@@ -177,14 +184,16 @@ impl PyBlockExecutor {
         Ok(Python::with_gil(|py| PyBytes::new(py, &serialized_tx_execution_info).into()))
     }
 
-    /// Executes the given transactions on the Blockifier state.
-    /// Stops if and when there is no more room in the block, and returns the executed transactions'
-    /// results as a PyList of (success (bool), serialized result (bytes)) tuples.
+    /// Executes the given transactions on the Blockifier state, filling the block according to the
+    /// configured block filling policy.
+    /// Returns the executed transactions' results as a PyList of
+    /// (tx index (int), success (bool), serialized result (bytes)) tuples, along with the indices
+    /// of the transactions rejected for lack of room in the block.
     #[pyo3(signature = (txs_with_class_infos))]
     pub fn execute_txs(
         &mut self,
         txs_with_class_infos: Vec<(&PyAny, Option<PyClassInfo>)>,
-    ) -> Py<PyList> {
+    ) -> (Py<PyList>, Vec<usize>) {
         // Parse Py transactions.
         let txs: Vec<Transaction> = txs_with_class_infos
             .into_iter()
@@ -200,11 +209,14 @@ impl PyBlockExecutor {
         // Process results.
         // TODO(Yoni, 15/5/2024): serialize concurrently.
         let block_context = &self.tx_executor().block_context;
-        let serialized_results: Vec<(bool, RawTransactionExecutionResult)> = results
+        // Note: there might be less results than txs; transactions rejected for lack of room in
+        // the block, and those following the point where the block filling stopped, have none.
+        let serialized_results: Vec<(usize, bool, RawTransactionExecutionResult)> = results
+            .results
             .into_iter()
-            // Note: there might be less results than txs (if there is no room for all of them).
-            .map(|result| match result {
+            .map(|(tx_index, result)| match result {
                 Ok(tx_execution_info) => (
+                    tx_index,
                     true,
                     ThinTransactionExecutionInfo::from_tx_execution_info(
                         block_context,
@@ -212,24 +224,26 @@ impl PyBlockExecutor {
                     )
                     .serialize(),
                 ),
-                Err(error) => (false, serialize_failure_reason(error)),
+                Err(error) => (tx_index, false, serialize_failure_reason(error)),
             })
             .collect();
 
         // Convert to Py types and allocate it on Python's heap, to be visible for Python's
         // garbage collector.
-        Python::with_gil(|py| {
-            let py_serialized_results: Vec<(bool, Py<PyBytes>)> = serialized_results
+        let py_serialized_results: Py<PyList> = Python::with_gil(|py| {
+            let py_serialized_results: Vec<(usize, bool, Py<PyBytes>)> = serialized_results
                 .into_iter()
-                .map(|(success, execution_result)| {
+                .map(|(tx_index, success, execution_result)| {
                     // Note that PyList converts the inner elements recursively, yet the default
                     // conversion of the execution result (Vec<u8>) is to a list of integers, which
                     // might be less efficient than bytes.
-                    (success, PyBytes::new(py, &execution_result).into())
+                    (tx_index, success, PyBytes::new(py, &execution_result).into())
                 })
                 .collect();
             PyList::new(py, py_serialized_results).into()
-        })
+        });
+
+        (py_serialized_results, results.rejected_for_capacity)
     }
 
     /// Returns the state diff, a list of contract class hash with the corresponding list of
@@ -353,9 +367,11 @@ impl PyBlockExecutor {
                     state_diff_size: max_state_diff_size,
                     ..BouncerWeights::max()
                 },
+                ..Default::default()
             },
             tx_executor_config: TransactionExecutorConfig {
                 concurrency_config: concurrency_config.into(),
+                ..Default::default()
             },
             storage: Box::new(PapyrusStorage::new_for_testing(
                 path,
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
 use blockifier::abi::constants;
-use blockifier::blockifier::config::ConcurrencyConfig;
+use blockifier::blockifier::config::{
+    ArrivalOrdering, BlockFillingPolicy, ConcurrencyConfig, SharedTransactionOrdering, TipOrdering,
+    TransactionExecutorConfig,
+};
 use blockifier::bouncer::{BouncerConfig, BouncerWeights, BuiltinCount, HashMapWrapper};
 use blockifier::versioned_constants::{VersionedConstants, VersionedConstantsOverrides};
 use cairo_vm::types::builtin_name::BuiltinName;
@@ -107,6 +110,7 @@ impl TryFrom<PyBouncerConfig> for BouncerConfig {
             block_max_capacity: hash_map_into_bouncer_weights(
                 py_bouncer_config.full_total_weights.clone(),
             )?,
+            ..Default::default()
         })
     }
 }
@@ -134,6 +138,8 @@ fn hash_map_into_bouncer_weights(
     mut data: HashMap<String, usize>,
 ) -> NativeBlockifierResult<BouncerWeights> {
     let gas = data.remove(constants::L1_GAS_USAGE).expect("gas_weight must be present");
+    // L2 gas is not limited unless configured.
+    let l2_gas = data.remove(constants::L2_GAS_USAGE).unwrap_or(usize::MAX);
     let n_steps = data.remove(constants::N_STEPS_RESOURCE).expect("n_steps must be present");
     let message_segment_length = data
         .remove(constants::MESSAGE_SEGMENT_LENGTH)
@@ -143,6 +149,7 @@ fn hash_map_into_bouncer_weights(
     let n_events = data.remove(constants::N_EVENTS).expect("n_events must be present");
     Ok(BouncerWeights {
         gas,
+        l2_gas,
         n_steps,
         message_segment_length,
         state_diff_size,
@@ -167,3 +174,66 @@ impl From<PyConcurrencyConfig> for ConcurrencyConfig {
         }
     }
 }
+
+/// A block filling policy, by its name: `STOP_AT_FIRST_REJECTION`, `SKIP_REJECTED` or
+/// `STOP_AFTER_CONSECUTIVE_REJECTIONS`.
+#[derive(Debug, FromPyObject)]
+pub struct PyBlockFillingPolicy {
+    pub policy: String,
+    // The maximal number of skipped transactions, or the number of consecutive rejections to stop
+    // after; unused by `STOP_AT_FIRST_REJECTION`.
+    pub n_rejections: usize,
+}
+
+impl TryFrom<PyBlockFillingPolicy> for BlockFillingPolicy {
+    type Error = NativeBlockifierInputError;
+
+    fn try_from(py_block_filling_policy: PyBlockFillingPolicy) -> Result<Self, Self::Error> {
+        let PyBlockFillingPolicy { policy, n_rejections } = py_block_filling_policy;
+        match policy.as_str() {
+            "STOP_AT_FIRST_REJECTION" => Ok(Self::StopAtFirstRejection),
+            "SKIP_REJECTED" => Ok(Self::SkipRejected { max_skips: n_rejections }),
+            "STOP_AFTER_CONSECUTIVE_REJECTIONS" => {
+                Ok(Self::StopAfterConsecutiveRejections { n_rejections })
+            }
+            _ => Err(NativeBlockifierInputError::UnknownBlockFillingPolicy(policy)),
+        }
+    }
+}
+
+/// The transaction executor configuration; the transaction ordering is given by its name:
+/// `ARRIVAL` or `TIP`.
+#[derive(Debug, FromPyObject)]
+pub struct PyTransactionExecutorConfig {
+    pub concurrency_config: PyConcurrencyConfig,
+    pub block_filling_policy: PyBlockFillingPolicy,
+    pub transaction_ordering: String,
+}
+
+impl TryFrom<PyTransactionExecutorConfig> for TransactionExecutorConfig {
+    type Error = NativeBlockifierInputError;
+
+    fn try_from(py_tx_executor_config: PyTransactionExecutorConfig) -> Result<Self, Self::Error> {
+        let PyTransactionExecutorConfig {
+            concurrency_config,
+            block_filling_policy,
+            transaction_ordering,
+        } = py_tx_executor_config;
+        let transaction_ordering = match transaction_ordering.as_str() {
+            "ARRIVAL" => SharedTransactionOrdering::new(ArrivalOrdering),
+            "TIP" => SharedTransactionOrdering::new(TipOrdering),
+            _ => {
+                return Err(NativeBlockifierInputError::UnknownTransactionOrdering(
+                    transaction_ordering,
+                ));
+            }
+        };
+
+        Ok(TransactionExecutorConfig {
+            block_filling_policy: block_filling_policy.try_into()?,
+            concurrency_config: concurrency_config.into(),
+            transaction_ordering,
+            verify_tx_hashes: false,
+        })
+    }
+}
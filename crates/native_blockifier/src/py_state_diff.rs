@@ -4,8 +4,8 @@ use std::convert::TryFrom;
 use blockifier::blockifier::block::{BlockInfo, GasPrices};
 use blockifier::state::cached_state::CommitmentStateDiff;
 use blockifier::test_utils::{
-    DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_STRK_L1_DATA_GAS_PRICE,
-    DEFAULT_STRK_L1_GAS_PRICE,
+    DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_ETH_L2_GAS_PRICE,
+    DEFAULT_STRK_L1_DATA_GAS_PRICE, DEFAULT_STRK_L1_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE,
 };
 use indexmap::IndexMap;
 use pyo3::prelude::*;
@@ -130,16 +130,43 @@ pub struct PyResourcePrice {
     pub price_in_fri: u128,
 }
 
-#[derive(FromPyObject)]
+impl PyResourcePrice {
+    fn default_l2_gas_price() -> Self {
+        Self { price_in_wei: DEFAULT_ETH_L2_GAS_PRICE, price_in_fri: DEFAULT_STRK_L2_GAS_PRICE }
+    }
+}
+
 pub struct PyBlockInfo {
     pub block_number: u64,
     pub block_timestamp: u64,
     pub l1_gas_price: PyResourcePrice,
     pub l1_data_gas_price: PyResourcePrice,
+    pub l2_gas_price: PyResourcePrice,
     pub sequencer_address: PyFelt,
     pub use_kzg_da: bool,
 }
 
+/// The L2 gas price is optional (either missing or `None`), for compatibility with block infos that
+/// precede it; implement `FromPyObject` explicitly.
+impl<'source> FromPyObject<'source> for PyBlockInfo {
+    fn extract(block_info: &'source PyAny) -> PyResult<Self> {
+        let l2_gas_price: Option<PyResourcePrice> = match block_info.hasattr("l2_gas_price")? {
+            true => block_info.getattr("l2_gas_price")?.extract()?,
+            false => None,
+        };
+
+        Ok(Self {
+            block_number: block_info.getattr("block_number")?.extract()?,
+            block_timestamp: block_info.getattr("block_timestamp")?.extract()?,
+            l1_gas_price: block_info.getattr("l1_gas_price")?.extract()?,
+            l1_data_gas_price: block_info.getattr("l1_data_gas_price")?.extract()?,
+            l2_gas_price: l2_gas_price.unwrap_or_else(PyResourcePrice::default_l2_gas_price),
+            sequencer_address: block_info.getattr("sequencer_address")?.extract()?,
+            use_kzg_da: block_info.getattr("use_kzg_da")?.extract()?,
+        })
+    }
+}
+
 /// Block info cannot have gas prices set to zero; implement `Default` explicitly.
 impl Default for PyBlockInfo {
     fn default() -> Self {
@@ -154,6 +181,7 @@ impl Default for PyBlockInfo {
                 price_in_wei: DEFAULT_ETH_L1_DATA_GAS_PRICE,
                 price_in_fri: DEFAULT_STRK_L1_DATA_GAS_PRICE,
             },
+            l2_gas_price: PyResourcePrice::default_l2_gas_price(),
             sequencer_address: PyFelt::default(),
             use_kzg_da: bool::default(),
         }
@@ -209,6 +237,24 @@ impl TryFrom<PyBlockInfo> for BlockInfo {
                             ),
                         )
                     })?,
+                eth_l2_gas_price: block_info.l2_gas_price.price_in_wei.try_into().map_err(
+                    |_| {
+                        NativeBlockifierInputError::InvalidNativeBlockifierInputError(
+                            InvalidNativeBlockifierInputError::InvalidL2GasPriceWei(
+                                block_info.l2_gas_price.price_in_wei,
+                            ),
+                        )
+                    },
+                )?,
+                strk_l2_gas_price: block_info.l2_gas_price.price_in_fri.try_into().map_err(
+                    |_| {
+                        NativeBlockifierInputError::InvalidNativeBlockifierInputError(
+                            InvalidNativeBlockifierInputError::InvalidL2GasPriceFri(
+                                block_info.l2_gas_price.price_in_fri,
+                            ),
+                        )
+                    },
+                )?,
             },
             use_kzg_da: block_info.use_kzg_da,
         })
@@ -37,7 +37,7 @@ impl PyValidator {
         py_versioned_constants_overrides: PyVersionedConstantsOverrides,
     ) -> NativeBlockifierResult<Self> {
         // Create the state.
-        let state_reader = PyStateReader::new(state_reader_proxy);
+        let state_reader = PyStateReader::new(state_reader_proxy)?;
         let state = CachedState::new(state_reader);
 
         // Create the block context.
@@ -1,7 +1,7 @@
 use blockifier::execution::contract_class::{ContractClass, ContractClassV0, ContractClassV1};
 use blockifier::state::errors::StateError;
 use blockifier::state::global_cache::GlobalContractCache;
-use blockifier::state::state_api::{StateReader, StateResult};
+use blockifier::state::state_api::{verify_bulk_read_length, StateReader, StateResult};
 use papyrus_storage::compiled_class::CasmStorageReader;
 use papyrus_storage::db::RO;
 use papyrus_storage::state::StateStorageReader;
@@ -139,4 +139,48 @@ impl StateReader for PapyrusReader {
     fn get_compiled_class_hash(&self, _class_hash: ClassHash) -> StateResult<CompiledClassHash> {
         todo!()
     }
+
+    // Reads all values in a single RO transaction.
+    fn get_storage_many(
+        &self,
+        storage_keys: &[(ContractAddress, StorageKey)],
+    ) -> StateResult<Vec<Felt>> {
+        let state_number = StateNumber(self.latest_block);
+        let values: Vec<Felt> = self
+            .reader()?
+            .get_state_reader()
+            .and_then(|sr| {
+                storage_keys
+                    .iter()
+                    .map(|(contract_address, key)| {
+                        sr.get_storage_at(state_number, contract_address, key)
+                    })
+                    .collect()
+            })
+            .map_err(|error| StateError::StateReadError(error.to_string()))?;
+        verify_bulk_read_length(storage_keys.len(), values.len())?;
+
+        Ok(values)
+    }
+
+    // Reads all nonces in a single RO transaction.
+    fn get_nonces_many(&self, contract_addresses: &[ContractAddress]) -> StateResult<Vec<Nonce>> {
+        let state_number = StateNumber(self.latest_block);
+        let nonces: Vec<Nonce> = self
+            .reader()?
+            .get_state_reader()
+            .and_then(|sr| {
+                contract_addresses
+                    .iter()
+                    .map(|contract_address| {
+                        sr.get_nonce_at(state_number, contract_address)
+                            .map(|nonce| nonce.unwrap_or_default())
+                    })
+                    .collect()
+            })
+            .map_err(|error| StateError::StateReadError(error.to_string()))?;
+        verify_bulk_read_length(contract_addresses.len(), nonces.len())?;
+
+        Ok(nonces)
+    }
 }
@@ -78,6 +78,16 @@ fn test_entry_point_with_papyrus_state() -> papyrus_storage::StorageResult<()> {
     let value_from_state = state.get_storage_at(storage_address, storage_key).unwrap();
     assert_eq!(value_from_state, value);
 
+    // Bulk reads agree with single reads.
+    let unset_storage_key = StorageKey::try_from(felt!(1_u8)).unwrap();
+    let storage_keys = [(storage_address, storage_key), (storage_address, unset_storage_key)];
+    assert_eq!(state.get_storage_many(&storage_keys).unwrap(), vec![value, Felt::ZERO]);
+    assert_eq!(state.state.get_storage_many(&storage_keys).unwrap(), vec![Felt::ZERO, Felt::ZERO]);
+    assert_eq!(
+        state.state.get_nonces_many(&[storage_address]).unwrap(),
+        vec![state.state.get_nonce_at(storage_address).unwrap()]
+    );
+
     Ok(())
 }
 
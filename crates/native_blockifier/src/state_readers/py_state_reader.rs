@@ -1,6 +1,6 @@
 use blockifier::execution::contract_class::{ContractClass, ContractClassV0, ContractClassV1};
 use blockifier::state::errors::StateError;
-use blockifier::state::state_api::{StateReader, StateResult};
+use blockifier::state::state_api::{verify_bulk_read_length, StateReader, StateResult};
 use pyo3::{FromPyObject, PyAny, PyErr, PyObject, PyResult, Python};
 use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
 use starknet_api::state::StorageKey;
@@ -22,11 +22,19 @@ pub struct PyStateReader {
     // Once PyObject is instantiated, the underlying Python object ref count is increased.
     // Once it is dropped, the ref count is decreased the next time the GIL is acquired in pyo3.
     state_reader_proxy: PyObject,
+    // Older proxies may not implement the bulk reads; in that case, the values are read one by
+    // one.
+    has_get_storage_many: bool,
+    has_get_nonces_many: bool,
 }
 
 impl PyStateReader {
-    pub fn new(state_reader_proxy: &PyAny) -> Self {
-        Self { state_reader_proxy: PyObject::from(state_reader_proxy) }
+    pub fn new(state_reader_proxy: &PyAny) -> PyResult<Self> {
+        Ok(Self {
+            state_reader_proxy: PyObject::from(state_reader_proxy),
+            has_get_storage_many: state_reader_proxy.hasattr("get_storage_many")?,
+            has_get_nonces_many: state_reader_proxy.hasattr("get_nonces_many")?,
+        })
     }
 }
 
@@ -93,6 +101,53 @@ impl StateReader for PyStateReader {
         .map(|felt| CompiledClassHash(felt.0))
         .map_err(|err| StateError::StateReadError(err.to_string()))
     }
+
+    fn get_storage_many(
+        &self,
+        storage_keys: &[(ContractAddress, StorageKey)],
+    ) -> StateResult<Vec<Felt>> {
+        if !self.has_get_storage_many {
+            return storage_keys
+                .iter()
+                .map(|&(contract_address, key)| self.get_storage_at(contract_address, key))
+                .collect();
+        }
+
+        let values: Vec<Felt> = Python::with_gil(|py| -> PyResult<Vec<PyFelt>> {
+            let storage_keys: Vec<(PyFelt, PyFelt)> = storage_keys
+                .iter()
+                .map(|&(contract_address, key)| (PyFelt::from(contract_address), PyFelt::from(key)))
+                .collect();
+            let args = (ON_CHAIN_STORAGE_DOMAIN, storage_keys);
+            self.state_reader_proxy.as_ref(py).call_method1("get_storage_many", args)?.extract()
+        })
+        .map(|felts| felts.into_iter().map(|felt| felt.0).collect())
+        .map_err(|err| StateError::StateReadError(err.to_string()))?;
+        verify_bulk_read_length(storage_keys.len(), values.len())?;
+
+        Ok(values)
+    }
+
+    fn get_nonces_many(&self, contract_addresses: &[ContractAddress]) -> StateResult<Vec<Nonce>> {
+        if !self.has_get_nonces_many {
+            return contract_addresses
+                .iter()
+                .map(|&contract_address| self.get_nonce_at(contract_address))
+                .collect();
+        }
+
+        let nonces: Vec<Nonce> = Python::with_gil(|py| -> PyResult<Vec<PyFelt>> {
+            let contract_addresses: Vec<PyFelt> =
+                contract_addresses.iter().copied().map(PyFelt::from).collect();
+            let args = (ON_CHAIN_STORAGE_DOMAIN, contract_addresses);
+            self.state_reader_proxy.as_ref(py).call_method1("get_nonces_many", args)?.extract()
+        })
+        .map(|nonces| nonces.into_iter().map(|nonce| Nonce(nonce.0)).collect())
+        .map_err(|err| StateError::StateReadError(err.to_string()))?;
+        verify_bulk_read_length(contract_addresses.len(), nonces.len())?;
+
+        Ok(nonces)
+    }
 }
 
 #[derive(FromPyObject)]